@@ -0,0 +1,336 @@
+//! Rapport d'ancienneté : répartition des octets par âge des fichiers et
+//! plus gros dossiers dont tout le contenu est ancien ("dormants").
+
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use eframe::egui;
+use egui_extras::{Column, TableBuilder};
+
+use crate::tree_table::{format_modified, right_aligned};
+use crate::{format_bytes, node_context_menu, Node, NodeActions};
+
+const DAY: u64 = 86_400;
+const ROW_HEIGHT: f32 = 20.0;
+/// Nombre maximal de dossiers dormants listés.
+const MAX_STALE_DIRS: usize = 200;
+/// Seuils proposés en un clic (jours).
+const PRESETS: [(u64, &str); 5] = [
+    (30, "1 mois"),
+    (90, "3 mois"),
+    (180, "6 mois"),
+    (365, "1 an"),
+    (730, "2 ans"),
+];
+
+/// Date prise en compte pour l'âge d'un fichier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgeBasis {
+    Modified,
+    Accessed,
+}
+
+impl AgeBasis {
+    pub fn label(self) -> &'static str {
+        match self {
+            AgeBasis::Modified => "Dernière modification",
+            AgeBasis::Accessed => "Dernier accès",
+        }
+    }
+
+    /// Date du nœud (pour un dossier : la plus récente de son contenu).
+    pub fn time_of(self, node: &Node) -> Option<SystemTime> {
+        match self {
+            AgeBasis::Modified => node.modified,
+            AgeBasis::Accessed => node.accessed,
+        }
+    }
+}
+
+/// Tranches d'âge du rapport (bornes supérieures en jours, la dernière
+/// tranche regroupe tout le reste).
+const BUCKETS: [(Option<u64>, &str); 4] = [
+    (Some(7), "Dernière semaine"),
+    (Some(30), "Dernier mois"),
+    (Some(365), "Dernière année"),
+    (None, "Plus d'un an"),
+];
+
+#[derive(Debug, Clone, Copy, Default)]
+struct BucketTotals {
+    bytes: u64,
+    files: u64,
+}
+
+/// Un dossier dont tout le contenu est plus ancien que le seuil.
+struct StaleDir {
+    path: PathBuf,
+    name: String,
+    size: u64,
+    file_count: u64,
+    last: Option<SystemTime>,
+}
+
+/// Résultat d'un calcul, valable pour une date de référence et un seuil.
+struct AgeReport {
+    basis: AgeBasis,
+    threshold_days: u64,
+    buckets: [BucketTotals; BUCKETS.len()],
+    /// Fichiers sans date exploitable.
+    unknown: BucketTotals,
+    stale_dirs: Vec<StaleDir>,
+    /// Total des dossiers dormants (y compris ceux au-delà de la liste).
+    stale_bytes: u64,
+}
+
+/// Etat de la vue : critères choisis et rapport en cache.
+pub struct AgeState {
+    basis: AgeBasis,
+    threshold_days: u64,
+    report: Option<AgeReport>,
+}
+
+impl Default for AgeState {
+    fn default() -> Self {
+        Self {
+            basis: AgeBasis::Modified,
+            threshold_days: 365,
+            report: None,
+        }
+    }
+}
+
+impl AgeState {
+    /// A appeler quand l'arbre change (nouveau scan).
+    pub fn invalidate(&mut self) {
+        self.report = None;
+    }
+
+    fn refresh(&mut self, root: &Node) {
+        let up_to_date = self
+            .report
+            .as_ref()
+            .is_some_and(|r| r.basis == self.basis && r.threshold_days == self.threshold_days);
+        if !up_to_date {
+            self.report = Some(compute_report(root, self.basis, self.threshold_days));
+        }
+    }
+}
+
+fn days_ago(now: SystemTime, days: u64) -> SystemTime {
+    now.checked_sub(Duration::from_secs(days * DAY))
+        .unwrap_or(SystemTime::UNIX_EPOCH)
+}
+
+fn compute_report(root: &Node, basis: AgeBasis, threshold_days: u64) -> AgeReport {
+    let now = SystemTime::now();
+    let bucket_limits: Vec<Option<SystemTime>> = BUCKETS
+        .iter()
+        .map(|(days, _)| days.map(|d| days_ago(now, d)))
+        .collect();
+    let cutoff = days_ago(now, threshold_days);
+
+    let mut report = AgeReport {
+        basis,
+        threshold_days,
+        buckets: [BucketTotals::default(); BUCKETS.len()],
+        unknown: BucketTotals::default(),
+        stale_dirs: Vec::new(),
+        stale_bytes: 0,
+    };
+
+    // (nœud, un ancêtre est déjà dormant)
+    let mut stack: Vec<(&Node, bool)> = vec![(root, false)];
+    while let Some((node, inside_stale)) = stack.pop() {
+        if !node.is_dir {
+            let totals = match basis.time_of(node) {
+                Some(t) => {
+                    let index = bucket_limits
+                        .iter()
+                        .position(|limit| limit.is_none_or(|l| t >= l))
+                        .unwrap_or(BUCKETS.len() - 1);
+                    &mut report.buckets[index]
+                }
+                None => &mut report.unknown,
+            };
+            totals.bytes += node.size;
+            totals.files += 1;
+            continue;
+        }
+
+        // Seul le dossier dormant le plus haut est listé, pas ses sous-dossiers
+        let stale = node.file_count > 0 && basis.time_of(node).is_some_and(|t| t < cutoff);
+        if stale && !inside_stale {
+            report.stale_bytes += node.size;
+            report.stale_dirs.push(StaleDir {
+                path: node.path.clone(),
+                name: node.name.clone(),
+                size: node.size,
+                file_count: node.file_count,
+                last: basis.time_of(node),
+            });
+        }
+        stack.extend(node.children.iter().map(|c| (c, inside_stale || stale)));
+    }
+
+    report
+        .stale_dirs
+        .sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.path.cmp(&b.path)));
+    report.stale_dirs.truncate(MAX_STALE_DIRS);
+    report
+}
+
+/// Dessin du rapport d'ancienneté. Renvoie le critère d'ancienneté
+/// (jours, date de référence) à appliquer à l'arborescence si l'utilisateur
+/// le demande.
+pub fn draw_age(
+    ui: &mut egui::Ui,
+    root: &Node,
+    state: &mut AgeState,
+    actions: &mut NodeActions,
+) -> Option<(u64, AgeBasis)> {
+    let mut requested_filter = None;
+
+    ui.horizontal(|ui| {
+        ui.label("Date de référence :");
+        for basis in [AgeBasis::Modified, AgeBasis::Accessed] {
+            ui.selectable_value(&mut state.basis, basis, basis.label());
+        }
+    });
+    if state.basis == AgeBasis::Accessed {
+        ui.small(
+            "La date d'accès dépend des options de montage \
+             (relatime, noatime) : elle peut être approximative.",
+        );
+    }
+
+    ui.horizontal(|ui| {
+        ui.label("Seuil d'ancienneté :");
+        ui.add(
+            egui::DragValue::new(&mut state.threshold_days)
+                .clamp_range(1..=36_500)
+                .suffix(" jours"),
+        );
+        for (days, label) in PRESETS {
+            ui.selectable_value(&mut state.threshold_days, days, label);
+        }
+        ui.separator();
+        if ui
+            .button("Filtrer l'arborescence")
+            .on_hover_text("N'afficher que les fichiers plus anciens que le seuil")
+            .clicked()
+        {
+            requested_filter = Some((state.threshold_days, state.basis));
+        }
+    });
+
+    state.refresh(root);
+    let Some(report) = &state.report else {
+        return requested_filter;
+    };
+    let root_size = root.size.max(1);
+
+    ui.add_space(6.0);
+    ui.strong("Répartition par âge");
+    egui::Grid::new("age_buckets")
+        .num_columns(4)
+        .striped(true)
+        .spacing([16.0, 4.0])
+        .show(ui, |ui| {
+            let rows = BUCKETS
+                .iter()
+                .map(|(_, label)| *label)
+                .zip(report.buckets.iter())
+                .chain((report.unknown.files > 0).then_some(("Date inconnue", &report.unknown)));
+            for (label, totals) in rows {
+                let fraction = totals.bytes as f32 / root_size as f32;
+                ui.label(label);
+                right_aligned(ui, format_bytes(totals.bytes));
+                right_aligned(ui, format!("{} fichiers", totals.files));
+                ui.add(
+                    egui::ProgressBar::new(fraction)
+                        .desired_width(200.0)
+                        .text(format!("{:.1}%", fraction * 100.0)),
+                );
+                ui.end_row();
+            }
+        });
+
+    ui.add_space(8.0);
+    ui.strong(format!(
+        "Dossiers dormants ({} : rien depuis plus de {} jours)",
+        state.basis.label().to_lowercase(),
+        report.threshold_days
+    ));
+    ui.small(format!(
+        "{} au total ({:.1}% de la racine). Double-clic : afficher dans l'arborescence.",
+        format_bytes(report.stale_bytes),
+        report.stale_bytes as f64 / root_size as f64 * 100.0
+    ));
+    ui.add_space(4.0);
+
+    TableBuilder::new(ui)
+        .striped(true)
+        .resizable(true)
+        .sense(egui::Sense::click())
+        .auto_shrink([false, false])
+        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+        .column(Column::initial(220.0).at_least(100.0).clip(true))
+        .column(Column::initial(90.0).at_least(60.0))
+        .column(Column::initial(80.0).at_least(60.0))
+        .column(Column::initial(120.0).at_least(100.0))
+        .column(Column::remainder().at_least(150.0).clip(true))
+        .header(ROW_HEIGHT + 4.0, |mut header| {
+            for title in ["Nom", "Taille", "Fichiers", "Plus récent", "Chemin complet"] {
+                header.col(|ui| {
+                    ui.strong(title);
+                });
+            }
+        })
+        .body(|body| {
+            body.rows(ROW_HEIGHT, report.stale_dirs.len(), |mut row| {
+                let entry = &report.stale_dirs[row.index()];
+                let is_selected = actions.selected_node_path.as_ref() == Some(&entry.path);
+                row.set_selected(is_selected);
+
+                row.col(|ui| {
+                    ui.add(
+                        egui::Label::new(&entry.name)
+                            .selectable(false)
+                            .truncate(true),
+                    );
+                });
+                row.col(|ui| {
+                    right_aligned(ui, format_bytes(entry.size));
+                });
+                row.col(|ui| {
+                    right_aligned(ui, entry.file_count.to_string());
+                });
+                row.col(|ui| {
+                    ui.label(format_modified(entry.last));
+                });
+                row.col(|ui| {
+                    ui.add(
+                        egui::Label::new(
+                            egui::RichText::new(entry.path.to_string_lossy()).monospace(),
+                        )
+                        .selectable(false)
+                        .truncate(true),
+                    );
+                });
+
+                let resp = row.response();
+                if resp.clicked() {
+                    actions.selected_node_path = Some(entry.path.clone());
+                }
+                if resp.double_clicked() {
+                    actions.pending_reveal = Some(entry.path.clone());
+                }
+                resp.context_menu(|ui| {
+                    node_context_menu(ui, &entry.path, true, actions);
+                });
+            });
+        });
+
+    requested_filter
+}
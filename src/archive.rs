@@ -0,0 +1,965 @@
+//! Archives compressées d'un fichier ou d'un dossier (tar.zst ou zip),
+//! créées en arrière-plan puis relues entièrement et comparées à l'original
+//! avant de proposer sa suppression.
+//!
+//! Formats écrits et relus par les crates `tar` (en-têtes GNU : noms et
+//! cibles de liens longs, fichiers de plus de 8 Go), `zstd` et `zip`
+//! (deflate, Zip64 au-delà de 4 Go ou 65 535 entrées). Liens symboliques
+//! conservés ; FIFO, sockets et périphériques ignorés et signalés.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{Datelike, Local, TimeZone, Timelike};
+use eframe::egui;
+use tar::{EntryType, HeaderMode};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::format_bytes;
+
+const BUFFER: usize = 1024 * 1024;
+/// Niveau zstd par défaut (bon compromis vitesse / taille).
+const ZSTD_LEVEL: i32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    TarZst,
+    Zip,
+}
+
+impl ArchiveFormat {
+    pub const ALL: [ArchiveFormat; 2] = [ArchiveFormat::TarZst, ArchiveFormat::Zip];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ArchiveFormat::TarZst => "tar.zst (meilleure compression)",
+            ArchiveFormat::Zip => "zip (ouvrable partout)",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ArchiveFormat::TarZst => "tar.zst",
+            ArchiveFormat::Zip => "zip",
+        }
+    }
+
+    /// Format d'après le nom de l'archive.
+    pub fn from_path(path: &Path) -> Self {
+        if path
+            .extension()
+            .is_some_and(|e| e.eq_ignore_ascii_case("zip"))
+        {
+            ArchiveFormat::Zip
+        } else {
+            ArchiveFormat::TarZst
+        }
+    }
+}
+
+/// Suivi de l'avancement (pause et annulation vérifiées à chaque appel).
+pub trait Progress {
+    /// Début du traitement d'un élément.
+    fn entry(&self, path: &Path) -> io::Result<()>;
+    /// Octets de l'original lus.
+    fn bytes(&self, count: u64) -> io::Result<()>;
+}
+
+/// Archive créée, à vérifier.
+pub struct Created {
+    /// Chemin retenu (premier nom libre à partir de celui demandé).
+    pub path: PathBuf,
+    /// Noms des entrées écrites (dossiers, fichiers, liens).
+    pub names: Vec<Vec<u8>>,
+    /// Eléments non archivés (fichiers spéciaux, illisibles).
+    pub warnings: Vec<String>,
+}
+
+/// Archive relue et conforme à ce qui a été écrit.
+#[derive(Debug, Clone)]
+pub struct Verified {
+    pub path: PathBuf,
+    pub original_size: u64,
+    pub archive_size: u64,
+    /// Eléments de l'original absents de l'archive ([`Created::warnings`]) :
+    /// l'original ne peut pas être supprimé sans les perdre.
+    pub skipped: Vec<String>,
+}
+
+/// Archive à côté de l'original : "dossier.tar.zst".
+pub fn archive_path(src: &Path, format: ArchiveFormat) -> PathBuf {
+    let name = src
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "archive".to_string());
+    src.with_file_name(format!("{name}.{}", format.extension()))
+}
+
+/// Variante numérotée du nom demandé : "dossier (2).tar.zst"…
+fn numbered(dest: &Path, n: u32) -> PathBuf {
+    if n == 1 {
+        return dest.to_path_buf();
+    }
+    let ext = ArchiveFormat::from_path(dest).extension();
+    let name = dest
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    match name.strip_suffix(&format!(".{ext}")) {
+        Some(stem) => dest.with_file_name(format!("{stem} ({n}).{ext}")),
+        None => dest.with_file_name(format!("{name} ({n})")),
+    }
+}
+
+/// Premier nom libre à côté de l'original (aperçu : le nom définitif est
+/// choisi à la création, voir [`create`]).
+pub fn default_archive_path(src: &Path, format: ArchiveFormat) -> PathBuf {
+    let dest = archive_path(src, format);
+    (1u32..)
+        .map(|n| numbered(&dest, n))
+        .find(|candidate| fs::symlink_metadata(candidate).is_err())
+        .unwrap_or(dest)
+}
+
+/// Crée le fichier de l'archive sans jamais écraser un élément existant :
+/// `dest`, sinon "… (2).ext", "… (3).ext"…
+fn create_new_file(dest: &Path) -> io::Result<(PathBuf, fs::File)> {
+    for n in 1u32.. {
+        let candidate = numbered(dest, n);
+        match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&candidate)
+        {
+            Ok(file) => return Ok((candidate, file)),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Err(io::Error::from(io::ErrorKind::AlreadyExists))
+}
+
+/// "1,2 Go → 300 Mo (25 % de la taille d'origine)".
+pub fn ratio_label(original: u64, archive: u64) -> String {
+    let percent = if original > 0 {
+        archive as f64 / original as f64 * 100.0
+    } else {
+        100.0
+    };
+    format!(
+        "{} → {} ({percent:.0} % de la taille d'origine)",
+        format_bytes(original),
+        format_bytes(archive)
+    )
+}
+
+/// Elément à archiver : chemin réel et nom dans l'archive (séparateur '/').
+struct Entry {
+    path: PathBuf,
+    name: Vec<u8>,
+    meta: fs::Metadata,
+}
+
+/// Recense les éléments à archiver, dossier avant son contenu.
+fn collect_entries(
+    path: &Path,
+    name: Vec<u8>,
+    entries: &mut Vec<Entry>,
+    warnings: &mut Vec<String>,
+) {
+    let meta = match fs::symlink_metadata(path) {
+        Ok(meta) => meta,
+        Err(e) => {
+            warnings.push(format!("{} : {e}", path.to_string_lossy()));
+            return;
+        }
+    };
+    let file_type = meta.file_type();
+    if !(file_type.is_dir() || file_type.is_file() || file_type.is_symlink()) {
+        warnings.push(format!(
+            "{} : fichier spécial non archivé",
+            path.to_string_lossy()
+        ));
+        return;
+    }
+    let is_dir = file_type.is_dir();
+    entries.push(Entry {
+        path: path.to_path_buf(),
+        name: name.clone(),
+        meta,
+    });
+    if !is_dir {
+        return;
+    }
+    match fs::read_dir(path) {
+        Ok(children) => {
+            for child in children.flatten() {
+                let mut child_name = name.clone();
+                child_name.push(b'/');
+                child_name.extend(os_bytes(Path::new(&child.file_name())));
+                collect_entries(&child.path(), child_name, entries, warnings);
+            }
+        }
+        Err(e) => warnings.push(format!("{} : {e}", path.to_string_lossy())),
+    }
+}
+
+/// Crée l'archive de `src` sous le premier nom libre à partir de `dest`
+/// (format d'après son extension) ; une archive incomplète est supprimée.
+pub fn create(src: &Path, dest: &Path, progress: &dyn Progress) -> io::Result<Created> {
+    let root_name = src
+        .file_name()
+        .map(|n| os_bytes(Path::new(n)))
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Chemin sans nom"))?;
+    let mut entries = Vec::new();
+    let mut warnings = Vec::new();
+    collect_entries(src, root_name, &mut entries, &mut warnings);
+
+    let (path, file) = create_new_file(dest)?;
+    let out = BufWriter::with_capacity(BUFFER, file);
+    let written = match ArchiveFormat::from_path(dest) {
+        ArchiveFormat::TarZst => write_tar_zst(&entries, out, progress),
+        ArchiveFormat::Zip => write_zip(&entries, out, progress, &mut warnings),
+    }
+    .and_then(|(out, written)| {
+        out.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+        Ok(written)
+    });
+    let names = match written {
+        Ok(names) => names,
+        Err(e) => {
+            // Seul le fichier créé ici est supprimé
+            let _ = fs::remove_file(&path);
+            return Err(e);
+        }
+    };
+
+    Ok(Created {
+        path,
+        names,
+        warnings,
+    })
+}
+
+/// Relit toute l'archive : chaque entrée doit correspondre à l'original
+/// (contenu identique à l'octet près pour les fichiers, même cible pour les
+/// liens), et l'archive doit contenir exactement les entrées `expected`
+/// ([`Created::names`]), ni plus ni moins.
+pub fn verify(
+    src: &Path,
+    dest: &Path,
+    expected: &[Vec<u8>],
+    progress: &dyn Progress,
+) -> io::Result<()> {
+    let base = src.parent().unwrap_or(Path::new(""));
+    let input = BufReader::with_capacity(BUFFER, fs::File::open(dest)?);
+    let found = match ArchiveFormat::from_path(dest) {
+        ArchiveFormat::TarZst => verify_tar_zst(base, input, progress)?,
+        ArchiveFormat::Zip => verify_zip(base, input, progress)?,
+    };
+    let found: HashSet<&[u8]> = found.iter().map(|n| trim_slash(n)).collect();
+    if let Some(missing) = expected.iter().find(|n| !found.contains(trim_slash(n))) {
+        return Err(invalid_archive(format!(
+            "entrée manquante : {}",
+            String::from_utf8_lossy(missing)
+        )));
+    }
+    // Toutes présentes : une entrée de plus est inattendue ou en double
+    if found.len() != expected.len() {
+        return Err(invalid_archive(format!(
+            "{} entrées trouvées sur {}",
+            found.len(),
+            expected.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Nom d'entrée sans le '/' final des dossiers.
+fn trim_slash(name: &[u8]) -> &[u8] {
+    name.strip_suffix(b"/").unwrap_or(name)
+}
+
+fn invalid_archive(message: String) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("Vérification de l'archive échouée : {message}"),
+    )
+}
+
+/// Compare une entrée relue à l'original correspondant.
+fn check_entry(
+    base: &Path,
+    name: &[u8],
+    kind: EntryKind,
+    content: &mut dyn Read,
+    progress: &dyn Progress,
+) -> io::Result<()> {
+    let original = base.join(path_from_bytes(trim_slash(name)));
+    progress.entry(&original)?;
+    let shown = original.to_string_lossy();
+    let meta =
+        fs::symlink_metadata(&original).map_err(|e| invalid_archive(format!("{shown} : {e}")))?;
+
+    match kind {
+        EntryKind::Dir => {
+            if !meta.is_dir() {
+                return Err(invalid_archive(format!("{shown} : dossier attendu")));
+            }
+        }
+        EntryKind::Symlink => {
+            let mut target = Vec::new();
+            content.read_to_end(&mut target)?;
+            if target != os_bytes(&fs::read_link(&original)?) {
+                return Err(invalid_archive(format!(
+                    "{shown} : cible du lien différente"
+                )));
+            }
+        }
+        EntryKind::File => {
+            let mut archived = blake3::Hasher::new();
+            io::copy(content, &mut archived)?;
+            if archived.finalize() != hash_original(&original, progress)? {
+                return Err(invalid_archive(format!("{shown} : contenu différent")));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[derive(Clone, Copy)]
+enum EntryKind {
+    Dir,
+    File,
+    Symlink,
+}
+
+fn hash_original(path: &Path, progress: &dyn Progress) -> io::Result<blake3::Hash> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = blake3::Hasher::new();
+    let mut buffer = vec![0u8; BUFFER];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        progress.bytes(read as u64)?;
+    }
+    Ok(hasher.finalize())
+}
+
+/// Contenu d'un fichier à archiver, limité à la taille recensée ; erreur
+/// s'il a raccourci depuis (un fichier qui a grandi est détecté à la
+/// vérification).
+struct Contents<'a> {
+    file: io::Take<fs::File>,
+    entry: &'a Entry,
+    remaining: u64,
+    progress: &'a dyn Progress,
+}
+
+impl<'a> Contents<'a> {
+    fn open(entry: &'a Entry, progress: &'a dyn Progress) -> io::Result<Self> {
+        let len = entry.meta.len();
+        Ok(Self {
+            file: fs::File::open(&entry.path)?.take(len),
+            entry,
+            remaining: len,
+            progress,
+        })
+    }
+}
+
+impl Read for Contents<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.file.read(buf)?;
+        if read == 0 && self.remaining > 0 && !buf.is_empty() {
+            return Err(io::Error::other(format!(
+                "{} : modifié pendant la compression",
+                self.entry.path.to_string_lossy()
+            )));
+        }
+        self.remaining -= read as u64;
+        self.progress.bytes(read as u64)?;
+        Ok(read)
+    }
+}
+
+// --- tar.zst -----------------------------------------------------------------
+
+fn write_tar_zst<W: Write>(
+    entries: &[Entry],
+    out: W,
+    progress: &dyn Progress,
+) -> io::Result<(W, Vec<Vec<u8>>)> {
+    let encoder = zstd::Encoder::new(out, ZSTD_LEVEL)?;
+    let mut builder = tar::Builder::new(encoder);
+    for entry in entries {
+        progress.entry(&entry.path)?;
+        let name = path_from_bytes(&entry.name);
+        let mut header = tar::Header::new_gnu();
+        header.set_metadata_in_mode(&entry.meta, HeaderMode::Complete);
+        let file_type = entry.meta.file_type();
+        if file_type.is_symlink() {
+            header.set_entry_type(EntryType::Symlink);
+            header.set_size(0);
+            let target = fs::read_link(&entry.path)?;
+            builder.append_link(&mut header, &name, target)?;
+        } else if file_type.is_dir() {
+            header.set_entry_type(EntryType::Directory);
+            header.set_size(0);
+            builder.append_data(&mut header, &name, io::empty())?;
+        } else {
+            header.set_entry_type(EntryType::Regular);
+            header.set_size(entry.meta.len());
+            builder.append_data(&mut header, &name, Contents::open(entry, progress)?)?;
+        }
+    }
+    let mut out = builder.into_inner()?.finish()?;
+    out.flush()?;
+    Ok((out, entries.iter().map(|e| e.name.clone()).collect()))
+}
+
+fn verify_tar_zst<R: io::BufRead>(
+    base: &Path,
+    input: R,
+    progress: &dyn Progress,
+) -> io::Result<Vec<Vec<u8>>> {
+    let mut archive = tar::Archive::new(zstd::Decoder::with_buffer(input)?);
+    let mut found = Vec::new();
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let name = entry.path_bytes().into_owned();
+        match entry.header().entry_type() {
+            EntryType::Directory => {
+                check_entry(base, &name, EntryKind::Dir, &mut io::empty(), progress)?
+            }
+            EntryType::Regular => check_entry(base, &name, EntryKind::File, &mut entry, progress)?,
+            EntryType::Symlink => {
+                let target = entry.link_name_bytes().unwrap_or_default().into_owned();
+                check_entry(
+                    base,
+                    &name,
+                    EntryKind::Symlink,
+                    &mut target.as_slice(),
+                    progress,
+                )?
+            }
+            other => {
+                return Err(invalid_archive(format!(
+                    "{} : type d'entrée inattendu ({other:?})",
+                    String::from_utf8_lossy(&name)
+                )))
+            }
+        }
+        found.push(name);
+    }
+    Ok(found)
+}
+
+// --- zip ---------------------------------------------------------------------
+
+/// Les noms zip sont en UTF-8 : les autres éléments sont signalés et
+/// ignorés (avec leur contenu pour un dossier).
+fn write_zip<W: Write + io::Seek>(
+    entries: &[Entry],
+    out: W,
+    progress: &dyn Progress,
+    warnings: &mut Vec<String>,
+) -> io::Result<(W, Vec<Vec<u8>>)> {
+    let mut zip = ZipWriter::new(out);
+    let mut written = Vec::new();
+    for entry in entries {
+        progress.entry(&entry.path)?;
+        let Ok(name) = std::str::from_utf8(&entry.name) else {
+            warnings.push(format!(
+                "{} : nom non UTF-8, non archivé dans le zip",
+                entry.path.to_string_lossy()
+            ));
+            continue;
+        };
+        let options = SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Deflated)
+            .unix_permissions(unix_mode(&entry.meta))
+            .last_modified_time(zip_time(&entry.meta))
+            .large_file(entry.meta.len() >= u64::from(u32::MAX));
+        let file_type = entry.meta.file_type();
+        if file_type.is_symlink() {
+            let target = fs::read_link(&entry.path)?;
+            let Ok(target) = String::from_utf8(os_bytes(&target)) else {
+                warnings.push(format!(
+                    "{} : cible non UTF-8, non archivé dans le zip",
+                    entry.path.to_string_lossy()
+                ));
+                continue;
+            };
+            zip.add_symlink(name, target, options)?;
+        } else if file_type.is_dir() {
+            zip.add_directory(name, options)?;
+        } else {
+            zip.start_file(name, options)?;
+            io::copy(&mut Contents::open(entry, progress)?, &mut zip)?;
+        }
+        written.push(entry.name.clone());
+    }
+    let mut out = zip.finish()?;
+    out.flush()?;
+    Ok((out, written))
+}
+
+fn verify_zip<R: Read + io::Seek>(
+    base: &Path,
+    input: R,
+    progress: &dyn Progress,
+) -> io::Result<Vec<Vec<u8>>> {
+    let mut archive = ZipArchive::new(input)?;
+    let mut found = Vec::with_capacity(archive.len());
+    for index in 0..archive.len() {
+        // Le CRC-32 est aussi contrôlé par le lecteur en fin d'entrée
+        let mut file = archive.by_index(index)?;
+        let name = file.name_raw().to_vec();
+        let kind = if file.is_dir() {
+            EntryKind::Dir
+        } else if file.is_symlink() {
+            EntryKind::Symlink
+        } else {
+            EntryKind::File
+        };
+        check_entry(base, &name, kind, &mut file, progress)?;
+        found.push(name);
+    }
+    Ok(found)
+}
+
+/// Date zip (heure locale, de 1980 à 2107).
+fn zip_time(meta: &fs::Metadata) -> zip::DateTime {
+    let local = meta
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .and_then(|d| Local.timestamp_opt(d.as_secs() as i64, 0).single());
+    local
+        .and_then(|t| {
+            zip::DateTime::from_date_and_time(
+                u16::try_from(t.year()).ok()?,
+                t.month() as u8,
+                t.day() as u8,
+                t.hour() as u8,
+                t.minute() as u8,
+                t.second() as u8,
+            )
+            .ok()
+        })
+        .unwrap_or_default()
+}
+
+// --- métadonnées et chemins -------------------------------------------------
+
+#[cfg(unix)]
+fn unix_mode(meta: &fs::Metadata) -> u32 {
+    use std::os::unix::fs::MetadataExt;
+
+    meta.mode() & 0o7777
+}
+
+#[cfg(not(unix))]
+fn unix_mode(meta: &fs::Metadata) -> u32 {
+    match (meta.is_dir(), meta.permissions().readonly()) {
+        (true, _) => 0o755,
+        (false, true) => 0o444,
+        (false, false) => 0o644,
+    }
+}
+
+#[cfg(unix)]
+fn os_bytes(path: &Path) -> Vec<u8> {
+    use std::os::unix::ffi::OsStrExt;
+
+    path.as_os_str().as_bytes().to_vec()
+}
+
+#[cfg(not(unix))]
+fn os_bytes(path: &Path) -> Vec<u8> {
+    path.to_string_lossy().replace('\\', "/").into_bytes()
+}
+
+#[cfg(unix)]
+fn path_from_bytes(bytes: &[u8]) -> PathBuf {
+    use std::os::unix::ffi::OsStrExt;
+
+    PathBuf::from(std::ffi::OsStr::from_bytes(bytes))
+}
+
+#[cfg(not(unix))]
+fn path_from_bytes(bytes: &[u8]) -> PathBuf {
+    PathBuf::from(String::from_utf8_lossy(bytes).into_owned())
+}
+
+// --- interface ---------------------------------------------------------------
+
+/// Choix du format avant compression.
+pub struct CompressDialog {
+    pub source: PathBuf,
+    format: ArchiveFormat,
+    /// Aperçu du nom de l'archive, recalculé au changement de format.
+    preview: PathBuf,
+}
+
+pub enum CompressAction {
+    None,
+    /// Archive demandée (voir [`archive_path`] ; un nom libre est choisi à
+    /// la création).
+    Start(PathBuf),
+    Cancel,
+}
+
+impl CompressDialog {
+    pub fn new(source: PathBuf) -> Self {
+        let format = ArchiveFormat::TarZst;
+        Self {
+            preview: default_archive_path(&source, format),
+            source,
+            format,
+        }
+    }
+}
+
+pub fn draw_compress_window(ctx: &egui::Context, dialog: &mut CompressDialog) -> CompressAction {
+    let mut action = CompressAction::None;
+
+    egui::Window::new("Compresser")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+        .show(ctx, |ui| {
+            ui.label("Archiver :");
+            ui.monospace(dialog.source.to_string_lossy());
+            ui.add_space(6.0);
+
+            for format in ArchiveFormat::ALL {
+                if ui
+                    .radio_value(&mut dialog.format, format, format.label())
+                    .changed()
+                {
+                    dialog.preview = default_archive_path(&dialog.source, format);
+                }
+            }
+            ui.add_space(6.0);
+            ui.label("Archive créée :");
+            ui.monospace(dialog.preview.to_string_lossy());
+            ui.small(
+                "L'archive est relue et comparée à l'original ; sa suppression n'est proposée qu'ensuite.",
+            );
+            ui.add_space(8.0);
+
+            ui.horizontal(|ui| {
+                if ui.button("Annuler").clicked() {
+                    action = CompressAction::Cancel;
+                }
+                if ui.button("Compresser").clicked() {
+                    action = CompressAction::Start(archive_path(&dialog.source, dialog.format));
+                }
+            });
+        });
+
+    action
+}
+
+/// Archive vérifiée : proposition de supprimer l'original.
+pub struct ArchiveReview {
+    pub source: PathBuf,
+    pub archived: Verified,
+}
+
+impl ArchiveReview {
+    /// Seulement si tout l'original est dans l'archive.
+    pub fn can_remove_original(&self) -> bool {
+        self.archived.skipped.is_empty()
+    }
+}
+
+pub enum ReviewAction {
+    None,
+    RemoveOriginal,
+    Keep,
+}
+
+pub fn draw_archive_review(ctx: &egui::Context, review: &ArchiveReview) -> ReviewAction {
+    let mut action = ReviewAction::None;
+
+    egui::Window::new("Archive vérifiée")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+        .show(ctx, |ui| {
+            let archived = &review.archived;
+            let complete = review.can_remove_original();
+            if complete {
+                ui.label("Archive créée et vérifiée :");
+            } else {
+                ui.label("Archive créée et vérifiée, mais incomplète :");
+            }
+            ui.monospace(archived.path.to_string_lossy());
+            ui.label(ratio_label(archived.original_size, archived.archive_size));
+            ui.add_space(6.0);
+            if complete {
+                ui.label("Supprimer l'original ?");
+                ui.monospace(review.source.to_string_lossy());
+            } else {
+                ui.colored_label(
+                    egui::Color32::YELLOW,
+                    format!(
+                        "{} élément(s) non archivé(s) : l'original ne peut pas être supprimé.",
+                        archived.skipped.len()
+                    ),
+                );
+                egui::ScrollArea::vertical()
+                    .max_height(200.0)
+                    .show(ui, |ui| {
+                        for skipped in &archived.skipped {
+                            ui.small(skipped);
+                        }
+                    });
+            }
+            ui.add_space(8.0);
+
+            ui.horizontal(|ui| {
+                if ui.button("Conserver l'original").clicked() {
+                    action = ReviewAction::Keep;
+                }
+                let remove = egui::Button::new(
+                    egui::RichText::new("Supprimer l'original…").color(egui::Color32::RED),
+                );
+                if ui.add_enabled(complete, remove).clicked() {
+                    action = ReviewAction::RemoveOriginal;
+                }
+            });
+        });
+
+    action
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct NoProgress;
+
+    impl Progress for NoProgress {
+        fn entry(&self, _path: &Path) -> io::Result<()> {
+            Ok(())
+        }
+
+        fn bytes(&self, _count: u64) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    struct Cancelled;
+
+    impl Progress for Cancelled {
+        fn entry(&self, _path: &Path) -> io::Result<()> {
+            Err(io::Error::from(io::ErrorKind::Interrupted))
+        }
+
+        fn bytes(&self, _count: u64) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Dossier de test vide, propre à chaque test.
+    fn scratch(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("treesize_archive_{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Arborescence avec noms longs (> 100 octets, limite ustar), chemin de
+    /// plus de 255 octets, fichier vide et lien à cible longue.
+    fn sample_tree(root: &Path) -> PathBuf {
+        let src = root.join("source");
+        let long = "n".repeat(150);
+        let deep = src.join(&long).join(&long);
+        fs::create_dir_all(&deep).unwrap();
+        fs::write(src.join("petit.txt"), b"bonjour").unwrap();
+        fs::write(src.join("vide"), b"").unwrap();
+        fs::write(src.join("élément accentué.txt"), "é".repeat(1000)).unwrap();
+        let data: Vec<u8> = (0..3_000_000u32).map(|i| (i % 251) as u8).collect();
+        fs::write(deep.join(format!("{long}.bin")), data).unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(format!("{long}/{long}/{long}.bin"), src.join("lien")).unwrap();
+        src
+    }
+
+    fn round_trip(src: &Path, format: ArchiveFormat) -> Created {
+        let created = create(src, &archive_path(src, format), &NoProgress).unwrap();
+        assert!(created.warnings.is_empty(), "{:?}", created.warnings);
+        verify(src, &created.path, &created.names, &NoProgress).unwrap();
+        created
+    }
+
+    #[test]
+    fn tar_zst_round_trip_with_long_names() {
+        let root = scratch("tar");
+        let src = sample_tree(&root);
+        let created = round_trip(&src, ArchiveFormat::TarZst);
+        assert_eq!(created.path, root.join("source.tar.zst"));
+        assert_eq!(created.names.len(), if cfg!(unix) { 8 } else { 7 });
+
+        // Une entrée attendue absente de l'archive fait échouer la vérification
+        let mut expected = created.names.clone();
+        expected.push(b"source/absent".to_vec());
+        let err = verify(&src, &created.path, &expected, &NoProgress).unwrap_err();
+        assert!(err.to_string().contains("source/absent"), "{err}");
+
+        // Un original modifié après coup aussi
+        fs::write(src.join("petit.txt"), b"bonsoir").unwrap();
+        let err = verify(&src, &created.path, &created.names, &NoProgress).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn zip_round_trip_with_long_names() {
+        let root = scratch("zip");
+        let src = sample_tree(&root);
+        let created = round_trip(&src, ArchiveFormat::Zip);
+
+        // Entrée de l'archive qui n'était pas attendue
+        let expected = &created.names[1..];
+        assert!(verify(&src, &created.path, expected, &NoProgress).is_err());
+
+        fs::remove_file(src.join("vide")).unwrap();
+        assert!(verify(&src, &created.path, &created.names, &NoProgress).is_err());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    /// Deux compressions du même élément : la première archive est gardée.
+    #[test]
+    fn never_overwrites_an_existing_archive() {
+        let root = scratch("unique");
+        let src = root.join("dossier");
+        fs::create_dir(&src).unwrap();
+        fs::write(src.join("a.txt"), b"a").unwrap();
+        let first = round_trip(&src, ArchiveFormat::TarZst).path;
+        let before = fs::read(&first).unwrap();
+        let second = round_trip(&src, ArchiveFormat::TarZst).path;
+        assert_eq!(first, root.join("dossier.tar.zst"));
+        assert_eq!(second, root.join("dossier (2).tar.zst"));
+        assert_eq!(fs::read(&first).unwrap(), before);
+
+        // Création annulée : seul le fichier qu'elle a créé est supprimé
+        let dest = archive_path(&src, ArchiveFormat::TarZst);
+        assert!(create(&src, &dest, &Cancelled).is_err());
+        assert!(first.exists() && second.exists());
+        assert!(!root.join("dossier (3).tar.zst").exists());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    /// Plus de 65 535 entrées : fin de répertoire central Zip64.
+    #[test]
+    fn zip64_many_entries_round_trip() {
+        let root = scratch("zip64");
+        let src = root.join("beaucoup");
+        fs::create_dir(&src).unwrap();
+        for i in 0..66_000 {
+            fs::write(src.join(i.to_string()), b"").unwrap();
+        }
+        let dest = round_trip(&src, ArchiveFormat::Zip).path;
+        let bytes = fs::read(&dest).unwrap();
+        assert!(bytes.windows(4).any(|w| w == b"PK\x06\x06"));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    /// Archive vérifiée d'un original dont une partie n'a pas pu être
+    /// archivée : l'original ne doit pas être supprimé.
+    fn review_of(src: &Path, format: ArchiveFormat) -> ArchiveReview {
+        let created = create(src, &archive_path(src, format), &NoProgress).unwrap();
+        verify(src, &created.path, &created.names, &NoProgress).unwrap();
+        ArchiveReview {
+            source: src.to_path_buf(),
+            archived: Verified {
+                archive_size: fs::metadata(&created.path).unwrap().len(),
+                path: created.path,
+                original_size: 0,
+                skipped: created.warnings,
+            },
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn unreadable_subdirectory_blocks_removal() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let root = scratch("unreadable");
+        let src = root.join("dossier");
+        let locked = src.join("verrouillé");
+        fs::create_dir_all(&locked).unwrap();
+        fs::write(locked.join("secret.txt"), b"secret").unwrap();
+        fs::write(src.join("a.txt"), b"a").unwrap();
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o000)).unwrap();
+        // Administrateur : le dossier reste lisible, rien à vérifier
+        let readable = fs::read_dir(&locked).is_ok();
+        if !readable {
+            for format in ArchiveFormat::ALL {
+                let review = review_of(&src, format);
+                assert!(!review.can_remove_original());
+                assert!(review.archived.skipped[0].contains("verrouillé"));
+            }
+        }
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn skipped_entries_block_removal() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let root = scratch("skipped");
+        let src = root.join("dossier");
+        fs::create_dir(&src).unwrap();
+        fs::write(src.join("a.txt"), b"a").unwrap();
+        assert!(review_of(&src, ArchiveFormat::Zip).can_remove_original());
+
+        // Nom non UTF-8 : ignoré dans le zip seulement
+        fs::write(src.join(std::ffi::OsStr::from_bytes(b"nom \xff")), b"b").unwrap();
+        assert!(!review_of(&src, ArchiveFormat::Zip).can_remove_original());
+        assert!(review_of(&src, ArchiveFormat::TarZst).can_remove_original());
+
+        // FIFO : ignorée dans les deux formats
+        let fifo = std::ffi::CString::new(src.join("tube").as_os_str().as_bytes()).unwrap();
+        // SAFETY: chemin C valide (terminé par un NUL) pendant l'appel.
+        assert_eq!(unsafe { libc::mkfifo(fifo.as_ptr(), 0o644) }, 0);
+        for format in ArchiveFormat::ALL {
+            let review = review_of(&src, format);
+            assert!(!review.can_remove_original());
+            assert!(review.archived.skipped.iter().any(|w| w.contains("tube")));
+        }
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    /// Fichier de plus de 4 Go (creux) dans les deux formats ; lent hors
+    /// `--release`.
+    #[test]
+    #[ignore]
+    fn files_over_4_gib_round_trip() {
+        let root = scratch("big");
+        let src = root.join("gros");
+        fs::create_dir(&src).unwrap();
+        let file = fs::File::create(src.join("creux.bin")).unwrap();
+        file.set_len((1 << 32) + 4096).unwrap();
+        drop(file);
+        for format in ArchiveFormat::ALL {
+            round_trip(&src, format);
+        }
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,418 @@
+//! Journal d'audit des opérations destructrices (suppressions, mises à la
+//! corbeille, déplacements, renommages, écrasements) faites par
+//! l'application : fichier texte en ajout seul, jamais réécrit, dans le
+//! dossier de données de l'utilisateur (`~/.local/share/treesize_rust/audit.log`).
+//!
+//! Format (champs séparés par des tabulations, textes encodés en `%XX`) :
+//! `<horodatage> <utilisateur> <opération> <source> <destination|-> <octets> <ok|erreur>`.
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use chrono::{Local, TimeZone};
+use eframe::egui;
+use egui_extras::{Column, TableBuilder};
+
+use crate::format_bytes;
+use crate::journal::{decode_path, encode_path};
+
+const ROW_HEIGHT: f32 = 20.0;
+const FILE_NAME: &str = "audit.log";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditOp {
+    Delete,
+    Trash,
+    Move,
+    Rename,
+    Overwrite,
+}
+
+impl AuditOp {
+    const ALL: [AuditOp; 5] = [
+        AuditOp::Delete,
+        AuditOp::Trash,
+        AuditOp::Move,
+        AuditOp::Rename,
+        AuditOp::Overwrite,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            AuditOp::Delete => "Suppression définitive",
+            AuditOp::Trash => "Mise à la corbeille",
+            AuditOp::Move => "Déplacement",
+            AuditOp::Rename => "Renommage",
+            AuditOp::Overwrite => "Ecrasement",
+        }
+    }
+
+    fn code(self) -> &'static str {
+        match self {
+            AuditOp::Delete => "delete",
+            AuditOp::Trash => "trash",
+            AuditOp::Move => "move",
+            AuditOp::Rename => "rename",
+            AuditOp::Overwrite => "overwrite",
+        }
+    }
+
+    fn from_code(code: &str) -> Option<Self> {
+        AuditOp::ALL.into_iter().find(|op| op.code() == code)
+    }
+}
+
+/// Une ligne du journal d'audit.
+struct AuditRecord {
+    /// Horodatage Unix (secondes).
+    time: i64,
+    user: String,
+    op: AuditOp,
+    source: PathBuf,
+    dest: Option<PathBuf>,
+    bytes: u64,
+    /// None : réussite ; sinon message d'erreur.
+    error: Option<String>,
+}
+
+impl AuditRecord {
+    fn time_label(&self) -> String {
+        Local
+            .timestamp_opt(self.time, 0)
+            .single()
+            .map(|t| t.format("%d/%m/%Y %H:%M:%S").to_string())
+            .unwrap_or_default()
+    }
+
+    fn result_label(&self) -> &str {
+        self.error.as_deref().unwrap_or("OK")
+    }
+
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\n",
+            self.time,
+            encode_text(&self.user),
+            self.op.code(),
+            encode_path(&self.source),
+            self.dest.as_deref().map_or("-".to_string(), encode_path),
+            self.bytes,
+            self.error
+                .as_deref()
+                .map_or("ok".to_string(), |e| format!("erreur:{}", encode_text(e)))
+        )
+    }
+
+    fn parse(line: &str) -> Option<Self> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let [time, user, op, source, dest, bytes, result] = fields.as_slice() else {
+            return None;
+        };
+        Some(Self {
+            time: time.parse().ok()?,
+            user: decode_text(user),
+            op: AuditOp::from_code(op)?,
+            source: decode_path(source),
+            dest: (*dest != "-").then(|| decode_path(dest)),
+            bytes: bytes.parse().ok()?,
+            error: result.strip_prefix("erreur:").map(decode_text),
+        })
+    }
+}
+
+fn encode_text(text: &str) -> String {
+    encode_path(Path::new(text))
+}
+
+fn decode_text(text: &str) -> String {
+    decode_path(text).to_string_lossy().into_owned()
+}
+
+fn log_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|d| d.join("treesize_rust").join(FILE_NAME))
+}
+
+/// Utilisateur qui lance l'application.
+fn current_user() -> &'static str {
+    static USER: OnceLock<String> = OnceLock::new();
+    USER.get_or_init(|| {
+        #[cfg(unix)]
+        {
+            // SAFETY: getuid ne peut pas échouer.
+            let uid = unsafe { libc::getuid() };
+            crate::owners::user_name(uid).unwrap_or_else(|| format!("uid {uid}"))
+        }
+        #[cfg(not(unix))]
+        {
+            std::env::var("USERNAME")
+                .or_else(|_| std::env::var("USER"))
+                .unwrap_or_else(|_| "?".to_string())
+        }
+    })
+}
+
+/// Inscrit une opération et son résultat dans le journal d'audit (depuis
+/// n'importe quel thread). L'échec de l'écriture est ignoré : l'opération
+/// a déjà eu lieu.
+pub fn record<T>(
+    op: AuditOp,
+    source: &Path,
+    dest: Option<&Path>,
+    bytes: u64,
+    result: &io::Result<T>,
+) {
+    // Une ligne entière à la fois, entre threads
+    static WRITE: Mutex<()> = Mutex::new(());
+
+    let Some(path) = log_path() else {
+        return;
+    };
+    let line = AuditRecord {
+        time: Local::now().timestamp(),
+        user: current_user().to_string(),
+        op,
+        source: source.to_path_buf(),
+        dest: dest.map(Path::to_path_buf),
+        bytes,
+        error: result.as_ref().err().map(ToString::to_string),
+    }
+    .to_line();
+
+    let _guard = WRITE.lock().unwrap_or_else(|e| e.into_inner());
+    let _ = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| OpenOptions::new().create(true).append(true).open(&path))
+        .and_then(|mut file| file.write_all(line.as_bytes()));
+}
+
+/// Fenêtre de consultation du journal d'audit.
+#[derive(Default)]
+pub struct AuditViewer {
+    pub open: bool,
+    /// Du plus récent au plus ancien.
+    records: Vec<AuditRecord>,
+    query: String,
+    op: Option<AuditOp>,
+    failures_only: bool,
+    message: Option<String>,
+}
+
+impl AuditViewer {
+    pub fn show(&mut self) {
+        self.open = true;
+        self.reload();
+    }
+
+    /// Relit le fichier (les lignes illisibles sont ignorées).
+    pub fn reload(&mut self) {
+        let text = log_path()
+            .and_then(|p| fs::read_to_string(p).ok())
+            .unwrap_or_default();
+        self.records = text.lines().filter_map(AuditRecord::parse).collect();
+        self.records.reverse();
+    }
+
+    fn matches(&self, record: &AuditRecord) -> bool {
+        if self.op.is_some_and(|op| op != record.op) {
+            return false;
+        }
+        if self.failures_only && record.error.is_none() {
+            return false;
+        }
+        let query = self.query.trim().to_lowercase();
+        if query.is_empty() {
+            return true;
+        }
+        let dest = record.dest.as_deref().unwrap_or(Path::new(""));
+        [
+            record.user.as_str(),
+            &record.source.to_string_lossy(),
+            &dest.to_string_lossy(),
+        ]
+        .iter()
+        .any(|field| field.to_lowercase().contains(&query))
+    }
+}
+
+/// Enregistre les lignes affichées en CSV (séparateur `;`).
+fn export(shown: &[&AuditRecord]) -> Option<String> {
+    let target = rfd::FileDialog::new()
+        .set_file_name("audit.csv")
+        .add_filter("CSV", &["csv"])
+        .save_file()?;
+    let mut csv = String::from("date;utilisateur;opération;source;destination;octets;résultat\n");
+    for record in shown {
+        let dest = record
+            .dest
+            .as_ref()
+            .map(|d| d.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let fields = [
+            record.time_label(),
+            record.user.clone(),
+            record.op.label().to_string(),
+            record.source.to_string_lossy().to_string(),
+            dest,
+            record.bytes.to_string(),
+            record.result_label().to_string(),
+        ];
+        let fields: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        csv.push_str(&fields.join(";"));
+        csv.push('\n');
+    }
+    Some(match fs::write(&target, csv) {
+        Ok(()) => format!(
+            "{} ligne(s) exportée(s) vers {}",
+            shown.len(),
+            target.to_string_lossy()
+        ),
+        Err(e) => format!("Export impossible : {e}"),
+    })
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([';', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+pub fn draw_audit_window(ctx: &egui::Context, viewer: &mut AuditViewer) {
+    let mut open = viewer.open;
+    let mut reload = false;
+    egui::Window::new("Journal d'audit")
+        .open(&mut open)
+        .default_size([900.0, 420.0])
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Rechercher :");
+                ui.add(
+                    egui::TextEdit::singleline(&mut viewer.query)
+                        .hint_text("utilisateur ou chemin")
+                        .desired_width(220.0),
+                );
+                egui::ComboBox::from_id_source("audit_op")
+                    .selected_text(viewer.op.map_or("Toutes les opérations", AuditOp::label))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut viewer.op, None, "Toutes les opérations");
+                        for op in AuditOp::ALL {
+                            ui.selectable_value(&mut viewer.op, Some(op), op.label());
+                        }
+                    });
+                ui.checkbox(&mut viewer.failures_only, "Echecs seulement");
+            });
+
+            let shown: Vec<&AuditRecord> = viewer
+                .records
+                .iter()
+                .filter(|r| viewer.matches(r))
+                .collect();
+
+            ui.horizontal(|ui| {
+                ui.label(format!(
+                    "{} opération(s) sur {}",
+                    shown.len(),
+                    viewer.records.len()
+                ));
+                if ui.button("Recharger").clicked() {
+                    reload = true;
+                }
+                if ui
+                    .add_enabled(!shown.is_empty(), egui::Button::new("Exporter (CSV)…"))
+                    .clicked()
+                {
+                    if let Some(message) = export(&shown) {
+                        viewer.message = Some(message);
+                    }
+                }
+                if let Some(path) = log_path() {
+                    ui.weak(path.to_string_lossy());
+                }
+            });
+            if let Some(message) = &viewer.message {
+                ui.label(message);
+            }
+            ui.add_space(4.0);
+
+            if shown.is_empty() {
+                ui.weak("Aucune opération enregistrée.");
+            } else {
+                draw_records(ui, &shown);
+            }
+        });
+    viewer.open = open;
+    if reload {
+        viewer.message = None;
+        viewer.reload();
+    }
+}
+
+fn draw_records(ui: &mut egui::Ui, records: &[&AuditRecord]) {
+    TableBuilder::new(ui)
+        .striped(true)
+        .resizable(true)
+        .auto_shrink([false, false])
+        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+        .column(Column::initial(140.0).at_least(100.0))
+        .column(Column::initial(90.0).at_least(60.0).clip(true))
+        .column(Column::initial(130.0).at_least(80.0))
+        .column(Column::initial(250.0).at_least(100.0).clip(true))
+        .column(Column::initial(200.0).at_least(80.0).clip(true))
+        .column(Column::initial(80.0).at_least(60.0))
+        .column(Column::remainder().at_least(80.0).clip(true))
+        .header(ROW_HEIGHT + 4.0, |mut header| {
+            for title in [
+                "Date",
+                "Utilisateur",
+                "Opération",
+                "Source",
+                "Destination",
+                "Taille",
+                "Résultat",
+            ] {
+                header.col(|ui| {
+                    ui.strong(title);
+                });
+            }
+        })
+        .body(|body| {
+            body.rows(ROW_HEIGHT, records.len(), |mut row| {
+                let record = records[row.index()];
+                row.col(|ui| {
+                    ui.label(record.time_label());
+                });
+                row.col(|ui| {
+                    ui.label(&record.user);
+                });
+                row.col(|ui| {
+                    ui.label(record.op.label());
+                });
+                row.col(|ui| {
+                    let source = record.source.to_string_lossy();
+                    ui.label(source.as_ref()).on_hover_text(source.as_ref());
+                });
+                row.col(|ui| {
+                    if let Some(dest) = &record.dest {
+                        let dest = dest.to_string_lossy();
+                        ui.label(dest.as_ref()).on_hover_text(dest.as_ref());
+                    }
+                });
+                row.col(|ui| {
+                    ui.label(format_bytes(record.bytes));
+                });
+                row.col(|ui| match &record.error {
+                    None => {
+                        ui.label("OK");
+                    }
+                    Some(error) => {
+                        ui.colored_label(egui::Color32::RED, error)
+                            .on_hover_text(error);
+                    }
+                });
+            });
+        });
+}
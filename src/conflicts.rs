@@ -0,0 +1,389 @@
+//! Conflits de collage : éléments déjà présents à la destination, détectés
+//! avant toute écriture, et choix de l'utilisateur pour chacun (remplacer,
+//! ignorer, conserver les deux).
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use eframe::egui;
+
+use crate::jobs::measure;
+use crate::tree_table::format_modified;
+use crate::{format_bytes, trash};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    /// L'élément existant est remplacé.
+    Overwrite,
+    /// L'élément existant est conservé, la source n'est pas collée.
+    Skip,
+    /// La source est collée sous un nouveau nom ("nom (2).ext").
+    KeepBoth,
+}
+
+/// Taille et date d'un des deux éléments en conflit.
+#[derive(Debug, Clone)]
+struct EntryInfo {
+    is_dir: bool,
+    size: u64,
+    modified: Option<SystemTime>,
+}
+
+impl EntryInfo {
+    /// Taille d'un dossier : total de son contenu.
+    fn new(path: &Path, meta: &fs::Metadata) -> Self {
+        Self {
+            is_dir: meta.is_dir(),
+            size: if meta.is_dir() {
+                measure(path).1
+            } else {
+                meta.len()
+            },
+            modified: meta.modified().ok(),
+        }
+    }
+
+    fn size_label(&self) -> String {
+        if self.is_dir {
+            format!("Dossier ({})", format_bytes(self.size))
+        } else {
+            format_bytes(self.size)
+        }
+    }
+}
+
+/// Elément source dont la destination existe déjà.
+#[derive(Debug, Clone)]
+pub struct Conflict {
+    src: PathBuf,
+    dest: PathBuf,
+    source: EntryInfo,
+    existing: EntryInfo,
+    /// Collage d'un élément sur lui-même (même fichier sur le disque) : il
+    /// ne peut être que conservé en double ou ignoré, jamais remplacé.
+    same_item: bool,
+}
+
+impl Conflict {
+    /// Résolution effective : un élément n'est jamais remplacé par lui-même.
+    fn resolve(&self, resolution: Resolution) -> Resolution {
+        if self.same_item && resolution == Resolution::Overwrite {
+            Resolution::Skip
+        } else {
+            resolution
+        }
+    }
+}
+
+/// Recense les conflits du collage de `src` vers `dest`. Deux dossiers de
+/// même nom sont fusionnés : seuls leurs contenus peuvent être en conflit.
+/// Un élément collé sur lui-même est un seul conflit, sans fusion.
+pub fn find_conflicts(src: &Path, dest: &Path, conflicts: &mut Vec<Conflict>) {
+    let (Ok(src_meta), Ok(dest_meta)) = (fs::symlink_metadata(src), fs::symlink_metadata(dest))
+    else {
+        return;
+    };
+    let same_item = is_same_item(src, dest);
+    if src_meta.is_dir() && dest_meta.is_dir() && !same_item {
+        if let Ok(entries) = fs::read_dir(src) {
+            for entry in entries.flatten() {
+                find_conflicts(&entry.path(), &dest.join(entry.file_name()), conflicts);
+            }
+        }
+        return;
+    }
+    conflicts.push(Conflict {
+        src: src.to_path_buf(),
+        dest: dest.to_path_buf(),
+        source: EntryInfo::new(src, &src_meta),
+        existing: EntryInfo::new(dest, &dest_meta),
+        same_item,
+    });
+}
+
+/// `src` et `dest` désignent-ils le même élément sur le disque (même
+/// périphérique et même inode, liens non suivis) ?
+#[cfg(unix)]
+pub fn is_same_item(src: &Path, dest: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+
+    match (fs::symlink_metadata(src), fs::symlink_metadata(dest)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+pub fn is_same_item(src: &Path, dest: &Path) -> bool {
+    let canonical = |path: &Path| {
+        let parent = fs::canonicalize(path.parent()?).ok()?;
+        Some(parent.join(path.file_name()?))
+    };
+    canonical(src).is_some_and(|src| Some(src) == canonical(dest))
+}
+
+/// Premier nom libre : "nom (2).ext", "nom (3).ext"… (dossiers : "nom (2)").
+pub fn unique_name(dest: &Path, is_dir: bool) -> PathBuf {
+    let name = dest
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let (stem, ext) = match (is_dir, name.rfind('.')) {
+        (false, Some(dot)) if dot > 0 => (name[..dot].to_string(), name[dot..].to_string()),
+        _ => (name.clone(), String::new()),
+    };
+    (2u32..)
+        .map(|n| dest.with_file_name(format!("{stem} ({n}){ext}")))
+        .find(|candidate| fs::symlink_metadata(candidate).is_err())
+        .unwrap_or_else(|| dest.to_path_buf())
+}
+
+/// Conflits d'une opération et choix déjà faits (un par conflit, dans
+/// l'ordre).
+pub struct ConflictPrompt {
+    conflicts: Vec<Conflict>,
+    decisions: Vec<Resolution>,
+    compare: bool,
+    apply_to_all: bool,
+}
+
+/// Issue de la fenêtre de conflits pour cette frame.
+pub enum PromptAction {
+    None,
+    /// Tous les conflits ont une résolution (par chemin de destination).
+    Decided(HashMap<PathBuf, Resolution>),
+    CancelJob,
+}
+
+impl ConflictPrompt {
+    pub fn new(conflicts: Vec<Conflict>) -> Self {
+        Self {
+            conflicts,
+            decisions: Vec::new(),
+            compare: false,
+            apply_to_all: false,
+        }
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.conflicts.len() - self.decisions.len()
+    }
+
+    fn decide(&mut self, resolution: Resolution) -> PromptAction {
+        let count = if self.apply_to_all {
+            self.remaining()
+        } else {
+            1
+        };
+        let start = self.decisions.len();
+        self.decisions.extend(
+            self.conflicts[start..start + count]
+                .iter()
+                .map(|c| c.resolve(resolution)),
+        );
+        if self.remaining() > 0 {
+            return PromptAction::None;
+        }
+        PromptAction::Decided(
+            self.conflicts
+                .iter()
+                .map(|c| c.dest.clone())
+                .zip(self.decisions.iter().copied())
+                .collect(),
+        )
+    }
+}
+
+/// Fenêtre de résolution du conflit courant. `operation` : libellé de
+/// l'opération concernée ("Copie", "Déplacement").
+pub fn draw_conflict_window(
+    ctx: &egui::Context,
+    operation: &str,
+    prompt: &mut ConflictPrompt,
+) -> PromptAction {
+    let index = prompt.decisions.len();
+    let Some(conflict) = prompt.conflicts.get(index).cloned() else {
+        return PromptAction::None;
+    };
+    let mut action = PromptAction::None;
+
+    egui::Window::new("Conflit de collage")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+        .show(ctx, |ui| {
+            ui.label(format!(
+                "{operation} : conflit {} sur {}",
+                index + 1,
+                prompt.conflicts.len()
+            ));
+            ui.add_space(4.0);
+            let name = conflict
+                .dest
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            ui.label(format!("« {name} » existe déjà dans :"));
+            if let Some(parent) = conflict.dest.parent() {
+                ui.monospace(parent.to_string_lossy());
+            }
+            ui.small(format!("Source : {}", conflict.src.to_string_lossy()));
+            if conflict.same_item {
+                ui.colored_label(
+                    egui::Color32::YELLOW,
+                    "La source et la destination sont le même élément : il ne peut pas être remplacé.",
+                );
+            } else if conflict.source.is_dir != conflict.existing.is_dir {
+                let fate = if trash::SUPPORTED {
+                    "le met à la corbeille"
+                } else {
+                    "le supprime définitivement"
+                };
+                ui.colored_label(
+                    egui::Color32::YELLOW,
+                    format!(
+                        "Un dossier et un fichier portent ce nom : remplacer l'élément existant ({}) {fate}.",
+                        conflict.existing.size_label()
+                    ),
+                );
+            }
+
+            if prompt.compare {
+                ui.add_space(6.0);
+                draw_comparison(ui, &conflict);
+            }
+            ui.add_space(8.0);
+
+            ui.horizontal(|ui| {
+                if !conflict.same_item && ui.button("Remplacer").clicked() {
+                    action = prompt.decide(Resolution::Overwrite);
+                }
+                if ui.button("Ignorer").clicked() {
+                    action = prompt.decide(Resolution::Skip);
+                }
+                if ui
+                    .button("Conserver les deux")
+                    .on_hover_text("Colle la source sous le nom « nom (2) »")
+                    .clicked()
+                {
+                    action = prompt.decide(Resolution::KeepBoth);
+                }
+                ui.toggle_value(&mut prompt.compare, "Comparer");
+            });
+
+            let remaining = prompt.remaining();
+            if remaining > 1 {
+                ui.checkbox(
+                    &mut prompt.apply_to_all,
+                    format!("Appliquer à tous les conflits restants ({remaining})"),
+                );
+            }
+            ui.add_space(4.0);
+            if ui.button("Annuler l'opération").clicked() {
+                action = PromptAction::CancelJob;
+            }
+        });
+
+    action
+}
+
+/// Tailles et dates de la source et de l'élément existant, côte à côte.
+fn draw_comparison(ui: &mut egui::Ui, conflict: &Conflict) {
+    let (source, existing) = (&conflict.source, &conflict.existing);
+    let hint = |mine: bool| if mine { " ◀" } else { "" };
+
+    egui::Grid::new("conflict_comparison")
+        .num_columns(3)
+        .striped(true)
+        .spacing([16.0, 4.0])
+        .show(ui, |ui| {
+            ui.label("");
+            ui.strong("Source");
+            ui.strong("Destination");
+            ui.end_row();
+
+            let both_files = !source.is_dir && !existing.is_dir;
+            ui.label("Taille");
+            ui.label(format!(
+                "{}{}",
+                source.size_label(),
+                hint(both_files && source.size > existing.size)
+            ));
+            ui.label(format!(
+                "{}{}",
+                existing.size_label(),
+                hint(both_files && existing.size > source.size)
+            ));
+            ui.end_row();
+
+            ui.label("Modifié le");
+            ui.label(format!(
+                "{}{}",
+                format_modified(source.modified),
+                hint(source.modified > existing.modified)
+            ));
+            ui.label(format!(
+                "{}{}",
+                format_modified(existing.modified),
+                hint(existing.modified > source.modified)
+            ));
+            ui.end_row();
+        });
+    ui.small("◀ : plus gros / plus récent.");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Dossier de test vide, propre à chaque test.
+    fn scratch(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("treesize_conflicts_{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn pasting_an_item_onto_itself_is_never_a_replacement() {
+        let root = scratch("self");
+        let dir = root.join("dossier");
+        fs::create_dir(&dir).unwrap();
+        fs::write(dir.join("fichier"), b"contenu").unwrap();
+        std::os::unix::fs::symlink("fichier", dir.join("lien")).unwrap();
+        std::os::unix::fs::symlink("dossier", root.join("lien")).unwrap();
+
+        // Dossier et lien collés dans leur propre dossier : un conflit
+        // chacun, sans descendre dans le dossier
+        let mut conflicts = Vec::new();
+        for src in [dir.clone(), root.join("lien")] {
+            find_conflicts(&src, &root.join(src.file_name().unwrap()), &mut conflicts);
+        }
+        assert_eq!(conflicts.len(), 2);
+        assert!(conflicts.iter().all(|c| c.same_item));
+
+        // "Remplacer" appliqué à tous : ignorés
+        let mut prompt = ConflictPrompt::new(conflicts);
+        prompt.apply_to_all = true;
+        let PromptAction::Decided(plan) = prompt.decide(Resolution::Overwrite) else {
+            panic!("conflits non résolus");
+        };
+        assert!(plan.values().all(|r| *r == Resolution::Skip));
+
+        // Un élément distinct du même nom reste un conflit ordinaire
+        let other = scratch("self_other");
+        fs::create_dir(other.join("dossier")).unwrap();
+        fs::write(other.join("dossier").join("fichier"), b"autre").unwrap();
+        let mut conflicts = Vec::new();
+        find_conflicts(&dir, &other.join("dossier"), &mut conflicts);
+        assert_eq!(conflicts.len(), 1);
+        assert!(!conflicts[0].same_item);
+
+        fs::remove_dir_all(&root).unwrap();
+        fs::remove_dir_all(&other).unwrap();
+    }
+}
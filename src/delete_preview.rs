@@ -0,0 +1,263 @@
+//! Aperçu de la suppression : espace libéré (ou taille envoyée à la
+//! corbeille), nombre de fichiers et de dossiers, plus gros éléments, et
+//! avertissements (dépôts git, fichiers récents, fichiers d'autres
+//! utilisateurs), calculés depuis l'arbre scanné.
+
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use eframe::egui;
+
+use crate::owners::user_name;
+use crate::tree_table::format_modified;
+use crate::{find_node_by_path, format_bytes, top_level_paths, Node};
+
+/// Nombre de plus gros éléments listés.
+const LARGEST_COUNT: usize = 8;
+/// Nombre maximal de dépôts git ou de fichiers récents cités.
+const MAX_LISTED: usize = 5;
+/// Fenêtre des fichiers considérés comme récents.
+const RECENT: Duration = Duration::from_secs(24 * 3600);
+
+/// Un élément cité dans l'aperçu.
+struct PreviewItem {
+    path: PathBuf,
+    is_dir: bool,
+    size: u64,
+    modified: Option<SystemTime>,
+}
+
+/// Résumé du contenu des éléments à supprimer.
+pub struct DeletePreview {
+    /// Eléments pour lesquels l'aperçu a été calculé.
+    pub paths: Vec<PathBuf>,
+    bytes: u64,
+    allocated: u64,
+    files: u64,
+    dirs: u64,
+    /// Eléments absents de l'arbre scanné (créés depuis le scan…).
+    unknown: usize,
+    /// Plus gros éléments du contenu (enfants directs des dossiers, ou les
+    /// fichiers eux-mêmes), par taille décroissante.
+    largest: Vec<PreviewItem>,
+    /// Dossiers contenant un `.git` (dépôts ou sous-modules).
+    git_repos: Vec<PathBuf>,
+    recent_count: u64,
+    /// Fichiers modifiés dans les dernières 24 h, les plus récents d'abord.
+    recent: Vec<PreviewItem>,
+    /// Eléments appartenant à d'autres utilisateurs : nombre par uid.
+    foreign: BTreeMap<u32, u64>,
+}
+
+impl DeletePreview {
+    pub fn compute(root: &Node, paths: &[PathBuf]) -> Self {
+        let mut preview = DeletePreview {
+            paths: paths.to_vec(),
+            bytes: 0,
+            allocated: 0,
+            files: 0,
+            dirs: 0,
+            unknown: 0,
+            largest: Vec::new(),
+            git_repos: Vec::new(),
+            recent_count: 0,
+            recent: Vec::new(),
+            foreign: BTreeMap::new(),
+        };
+        let since = SystemTime::now().checked_sub(RECENT);
+        let me = current_uid();
+
+        // Les éléments contenus dans un dossier du lot sont comptés avec lui
+        for path in top_level_paths(paths) {
+            let Some(node) = find_node_by_path(root, &path) else {
+                preview.unknown += 1;
+                continue;
+            };
+            preview.bytes += node.size;
+            preview.allocated += node.allocated;
+            if node.is_dir {
+                preview
+                    .largest
+                    .extend(node.children.iter().map(PreviewItem::of));
+            } else {
+                preview.largest.push(PreviewItem::of(node));
+            }
+            preview.walk(node, since, me);
+        }
+
+        preview.largest.sort_by_key(|item| Reverse(item.size));
+        preview.largest.truncate(LARGEST_COUNT);
+        preview.recent.sort_by_key(|item| Reverse(item.modified));
+        preview.recent.truncate(MAX_LISTED);
+        preview
+    }
+
+    fn walk(&mut self, node: &Node, since: Option<SystemTime>, me: Option<u32>) {
+        if let (Some((uid, _)), Some(me)) = (node.owner, me) {
+            if uid != me {
+                *self.foreign.entry(uid).or_default() += 1;
+            }
+        }
+        if !node.is_dir {
+            self.files += 1;
+            if since.is_some() && node.modified >= since {
+                self.recent_count += 1;
+                self.recent.push(PreviewItem::of(node));
+                // Seuls les plus récents sont gardés
+                if self.recent.len() > 4 * MAX_LISTED {
+                    self.recent.sort_by_key(|item| Reverse(item.modified));
+                    self.recent.truncate(MAX_LISTED);
+                }
+            }
+            return;
+        }
+        self.dirs += 1;
+        // `.git` est un fichier pour les worktrees et sous-modules
+        if node.children.iter().any(|c| c.name == ".git") {
+            self.git_repos.push(node.path.clone());
+        }
+        for child in &node.children {
+            self.walk(child, since, me);
+        }
+    }
+
+    fn has_warnings(&self) -> bool {
+        !self.git_repos.is_empty() || self.recent_count > 0 || !self.foreign.is_empty()
+    }
+}
+
+impl PreviewItem {
+    fn of(node: &Node) -> Self {
+        Self {
+            path: node.path.clone(),
+            is_dir: node.is_dir,
+            size: node.size,
+            modified: node.modified,
+        }
+    }
+}
+
+#[cfg(unix)]
+fn current_uid() -> Option<u32> {
+    // SAFETY: getuid ne peut pas échouer.
+    Some(unsafe { libc::getuid() })
+}
+
+#[cfg(not(unix))]
+fn current_uid() -> Option<u32> {
+    None
+}
+
+/// Résumé affiché dans la fenêtre de confirmation de suppression.
+/// `permanent` : la place n'est libérée que si l'on ne passe pas par la
+/// corbeille.
+pub fn draw_delete_preview(ui: &mut egui::Ui, preview: &DeletePreview, permanent: bool) {
+    let label = if permanent {
+        "Espace libéré"
+    } else {
+        "Taille déplacée vers la corbeille"
+    };
+    ui.label(format!(
+        "{label} : {} ({} sur le disque)",
+        format_bytes(preview.bytes),
+        format_bytes(preview.allocated)
+    ));
+    ui.label(format!(
+        "{} fichier(s), {} dossier(s)",
+        preview.files, preview.dirs
+    ));
+    if preview.unknown > 0 {
+        ui.small(format!(
+            "{} élément(s) absent(s) du dernier scan, non comptés.",
+            preview.unknown
+        ));
+    }
+
+    if preview.largest.len() > 1 {
+        ui.add_space(4.0);
+        ui.collapsing("Plus gros éléments", |ui| {
+            egui::Grid::new("delete_preview_largest")
+                .num_columns(2)
+                .striped(true)
+                .show(ui, |ui| {
+                    for item in &preview.largest {
+                        let icon = if item.is_dir { "📁" } else { "📄" };
+                        ui.label(format!("{icon} {}", file_name(&item.path)))
+                            .on_hover_text(item.path.to_string_lossy());
+                        ui.label(format_bytes(item.size));
+                        ui.end_row();
+                    }
+                });
+        });
+    }
+
+    if !preview.has_warnings() {
+        return;
+    }
+    ui.add_space(4.0);
+    let warn = egui::Color32::from_rgb(230, 140, 20);
+
+    if !preview.git_repos.is_empty() {
+        ui.colored_label(
+            warn,
+            format!(
+                "⚠ {} dépôt(s) git (historique local et modifications non poussées) :",
+                preview.git_repos.len()
+            ),
+        );
+        for repo in preview.git_repos.iter().take(MAX_LISTED) {
+            ui.monospace(repo.to_string_lossy());
+        }
+        more(ui, preview.git_repos.len(), MAX_LISTED);
+    }
+
+    if preview.recent_count > 0 {
+        ui.colored_label(
+            warn,
+            format!(
+                "⚠ {} fichier(s) modifié(s) dans les dernières 24 h :",
+                preview.recent_count
+            ),
+        );
+        for item in &preview.recent {
+            ui.horizontal(|ui| {
+                ui.label(format_modified(item.modified));
+                ui.monospace(file_name(&item.path))
+                    .on_hover_text(item.path.to_string_lossy());
+            });
+        }
+        more(ui, preview.recent_count as usize, preview.recent.len());
+    }
+
+    if !preview.foreign.is_empty() {
+        let owners: Vec<String> = preview
+            .foreign
+            .iter()
+            .map(|(&uid, count)| {
+                let name = user_name(uid).unwrap_or_else(|| format!("uid {uid}"));
+                format!("{name} ({count})")
+            })
+            .collect();
+        ui.colored_label(
+            warn,
+            format!(
+                "⚠ Eléments appartenant à d'autres utilisateurs : {}",
+                owners.join(", ")
+            ),
+        );
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string_lossy().to_string())
+}
+
+fn more(ui: &mut egui::Ui, total: usize, shown: usize) {
+    if total > shown {
+        ui.small(format!("… et {} autre(s)", total - shown));
+    }
+}
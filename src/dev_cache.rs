@@ -0,0 +1,462 @@
+//! Détection des caches de développement et artefacts de build
+//! (`target/` Rust, `node_modules`, `__pycache__`, `.gradle`, caches
+//! utilisateur…), regroupés par catégorie et par projet, avec nettoyage.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use eframe::egui;
+use egui_extras::{Column, TableBuilder};
+
+use crate::tree_table::{format_modified, right_aligned};
+use crate::{format_bytes, node_context_menu, Node, NodeActions};
+
+const ROW_HEIGHT: f32 = 20.0;
+/// Nombre maximal de projets affichés dans le récapitulatif.
+const MAX_PROJECTS: usize = 50;
+
+/// Fichiers ou dossiers qui signalent la racine d'un projet.
+const PROJECT_MARKERS: [&str; 9] = [
+    "Cargo.toml",
+    "package.json",
+    "pyproject.toml",
+    "setup.py",
+    "build.gradle",
+    "build.gradle.kts",
+    "settings.gradle",
+    "settings.gradle.kts",
+    ".git",
+];
+
+/// Fichiers de build qui signalent un projet Gradle (à côté de `.gradle`).
+const GRADLE_MARKERS: [&str; 4] = [
+    "build.gradle",
+    "build.gradle.kts",
+    "settings.gradle",
+    "settings.gradle.kts",
+];
+
+/// Type d'artefact reconnu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CacheCategory {
+    RustTarget,
+    NodeModules,
+    PythonCache,
+    Gradle,
+    CargoRegistry,
+    GradleCaches,
+    UserCache,
+}
+
+impl CacheCategory {
+    pub const ALL: [CacheCategory; 7] = [
+        CacheCategory::RustTarget,
+        CacheCategory::NodeModules,
+        CacheCategory::PythonCache,
+        CacheCategory::Gradle,
+        CacheCategory::CargoRegistry,
+        CacheCategory::GradleCaches,
+        CacheCategory::UserCache,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CacheCategory::RustTarget => "Rust (target/)",
+            CacheCategory::NodeModules => "node_modules",
+            CacheCategory::PythonCache => "Caches Python",
+            CacheCategory::Gradle => "Gradle (.gradle)",
+            CacheCategory::CargoRegistry => "Registre Cargo (~/.cargo/registry)",
+            CacheCategory::GradleCaches => "Caches Gradle (~/.gradle/caches)",
+            CacheCategory::UserCache => "Cache utilisateur (~/.cache)",
+        }
+    }
+}
+
+/// Chemins globaux reconnus quel que soit le contenu.
+struct GlobalRules {
+    cargo_registry: Option<PathBuf>,
+    /// `~/.gradle` : jamais pris en entier (configuration, identifiants),
+    /// seul son sous-dossier `caches` est un cache.
+    gradle_home: Option<PathBuf>,
+    user_cache: Option<PathBuf>,
+}
+
+impl GlobalRules {
+    fn from_home() -> Self {
+        let home = dirs::home_dir();
+        Self {
+            cargo_registry: home.as_ref().map(|h| h.join(".cargo").join("registry")),
+            gradle_home: home.as_ref().map(|h| h.join(".gradle")),
+            user_cache: home.as_ref().map(|h| h.join(".cache")),
+        }
+    }
+}
+
+/// Catégorie d'un dossier, d'après son nom, son chemin et le contenu de
+/// son dossier parent.
+fn classify(node: &Node, parent: &Node, globals: &GlobalRules) -> Option<CacheCategory> {
+    if globals.cargo_registry.as_deref() == Some(node.path.as_path()) {
+        return Some(CacheCategory::CargoRegistry);
+    }
+    if globals.user_cache.as_deref() == Some(node.path.as_path()) {
+        return Some(CacheCategory::UserCache);
+    }
+    if let Some(gradle_home) = &globals.gradle_home {
+        if node.path == gradle_home.join("caches") {
+            return Some(CacheCategory::GradleCaches);
+        }
+        if node.path == *gradle_home {
+            return None;
+        }
+    }
+
+    let has_sibling = |name: &str| parent.children.iter().any(|c| c.name == name);
+    match node.name.as_str() {
+        "target" if has_sibling("Cargo.toml") => Some(CacheCategory::RustTarget),
+        "node_modules" => Some(CacheCategory::NodeModules),
+        "__pycache__" | ".pytest_cache" | ".mypy_cache" | ".ruff_cache" => {
+            Some(CacheCategory::PythonCache)
+        }
+        ".gradle" if GRADLE_MARKERS.iter().any(|m| has_sibling(m)) => Some(CacheCategory::Gradle),
+        _ => None,
+    }
+}
+
+fn is_project_root(node: &Node) -> bool {
+    node.children
+        .iter()
+        .any(|c| PROJECT_MARKERS.contains(&c.name.as_str()))
+}
+
+/// Un dossier reconnu comme cache ou artefact.
+struct Artefact {
+    path: PathBuf,
+    category: CacheCategory,
+    /// Projet englobant (None pour les caches globaux ou hors projet).
+    project: Option<PathBuf>,
+    size: u64,
+    file_count: u64,
+    modified: Option<SystemTime>,
+}
+
+/// Total par catégorie ou par projet.
+#[derive(Debug, Clone, Copy, Default)]
+struct Totals {
+    count: u64,
+    bytes: u64,
+}
+
+/// Parcourt l'arbre et renvoie les artefacts (les plus hauts : le contenu
+/// d'un artefact n'est pas inspecté), du plus gros au plus petit.
+fn detect(root: &Node, globals: &GlobalRules) -> Vec<Artefact> {
+    let mut found = Vec::new();
+    let root_project = is_project_root(root).then(|| root.path.clone());
+
+    // (dossier, projet englobant)
+    let mut stack: Vec<(&Node, Option<PathBuf>)> = vec![(root, root_project)];
+    while let Some((dir, project)) = stack.pop() {
+        for child in dir.children.iter().filter(|c| c.is_dir) {
+            if let Some(category) = classify(child, dir, globals) {
+                let project = match category {
+                    CacheCategory::CargoRegistry
+                    | CacheCategory::GradleCaches
+                    | CacheCategory::UserCache => None,
+                    _ => project.clone(),
+                };
+                found.push(Artefact {
+                    path: child.path.clone(),
+                    category,
+                    project,
+                    size: child.size,
+                    file_count: child.file_count,
+                    modified: child.modified,
+                });
+                continue;
+            }
+
+            let child_project = if is_project_root(child) {
+                Some(child.path.clone())
+            } else {
+                project.clone()
+            };
+            stack.push((child, child_project));
+        }
+    }
+
+    found.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.path.cmp(&b.path)));
+    found
+}
+
+/// Etat du panneau : artefacts en cache, filtre de catégorie, cases cochées.
+#[derive(Default)]
+pub struct DevCacheState {
+    computed: bool,
+    artefacts: Vec<Artefact>,
+    by_category: HashMap<CacheCategory, Totals>,
+    /// Projets triés par octets récupérables décroissants.
+    by_project: Vec<(PathBuf, Totals)>,
+    category_filter: Option<CacheCategory>,
+    checked: HashSet<PathBuf>,
+}
+
+impl DevCacheState {
+    /// A appeler quand l'arbre change (nouveau scan).
+    pub fn invalidate(&mut self) {
+        self.computed = false;
+        self.artefacts.clear();
+        self.by_category.clear();
+        self.by_project.clear();
+        self.checked.clear();
+    }
+
+    fn refresh(&mut self, root: &Node) {
+        if self.computed {
+            return;
+        }
+        self.artefacts = detect(root, &GlobalRules::from_home());
+
+        let mut by_project: HashMap<PathBuf, Totals> = HashMap::new();
+        for artefact in &self.artefacts {
+            let totals = self.by_category.entry(artefact.category).or_default();
+            totals.count += 1;
+            totals.bytes += artefact.size;
+            if let Some(project) = &artefact.project {
+                let totals = by_project.entry(project.clone()).or_default();
+                totals.count += 1;
+                totals.bytes += artefact.size;
+            }
+        }
+        self.by_project = by_project.into_iter().collect();
+        self.by_project
+            .sort_by(|a, b| b.1.bytes.cmp(&a.1.bytes).then_with(|| a.0.cmp(&b.0)));
+        self.computed = true;
+    }
+
+    fn visible(&self) -> impl Iterator<Item = &Artefact> {
+        self.artefacts
+            .iter()
+            .filter(|a| self.category_filter.is_none_or(|c| a.category == c))
+    }
+}
+
+fn project_label(project: Option<&Path>) -> String {
+    match project {
+        Some(p) => p
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| p.to_string_lossy().to_string()),
+        None => "—".to_string(),
+    }
+}
+
+/// Dessin du panneau : totaux par catégorie et par projet, liste des
+/// artefacts à cocher et nettoyage via la confirmation de suppression.
+pub fn draw_dev_cache(
+    ui: &mut egui::Ui,
+    root: &Node,
+    state: &mut DevCacheState,
+    actions: &mut NodeActions,
+) {
+    state.refresh(root);
+
+    if state.artefacts.is_empty() {
+        ui.weak("Aucun cache de développement ni artefact de build détecté.");
+        return;
+    }
+
+    let total: u64 = state.artefacts.iter().map(|a| a.size).sum();
+    ui.label(format!(
+        "{} récupérables dans {} dossiers ({:.1}% de la racine).",
+        format_bytes(total),
+        state.artefacts.len(),
+        total as f64 / root.size.max(1) as f64 * 100.0
+    ));
+    ui.add_space(4.0);
+
+    ui.columns(2, |columns| {
+        let ui = &mut columns[0];
+        ui.strong("Par catégorie");
+        egui::Grid::new("dev_cache_categories")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                if ui
+                    .selectable_label(state.category_filter.is_none(), "Toutes")
+                    .clicked()
+                {
+                    state.category_filter = None;
+                }
+                right_aligned(ui, state.artefacts.len().to_string());
+                right_aligned(ui, format_bytes(total));
+                ui.end_row();
+
+                for category in CacheCategory::ALL {
+                    let Some(totals) = state.by_category.get(&category) else {
+                        continue;
+                    };
+                    if ui
+                        .selectable_label(state.category_filter == Some(category), category.label())
+                        .clicked()
+                    {
+                        state.category_filter = Some(category);
+                    }
+                    right_aligned(ui, totals.count.to_string());
+                    right_aligned(ui, format_bytes(totals.bytes));
+                    ui.end_row();
+                }
+            });
+
+        let ui = &mut columns[1];
+        ui.strong("Par projet");
+        egui::ScrollArea::vertical()
+            .id_source("dev_cache_projects")
+            .max_height(160.0)
+            .show(ui, |ui| {
+                egui::Grid::new("dev_cache_projects_grid")
+                    .num_columns(3)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (project, totals) in state.by_project.iter().take(MAX_PROJECTS) {
+                            ui.label(project_label(Some(project)))
+                                .on_hover_text(project.to_string_lossy());
+                            right_aligned(ui, totals.count.to_string());
+                            right_aligned(ui, format_bytes(totals.bytes));
+                            ui.end_row();
+                        }
+                    });
+            });
+    });
+
+    ui.add_space(6.0);
+    let checked_bytes: u64 = state
+        .artefacts
+        .iter()
+        .filter(|a| state.checked.contains(&a.path))
+        .map(|a| a.size)
+        .sum();
+    ui.horizontal(|ui| {
+        if ui.button("Tout cocher").clicked() {
+            let paths: Vec<PathBuf> = state.visible().map(|a| a.path.clone()).collect();
+            state.checked.extend(paths);
+        }
+        if ui.button("Tout décocher").clicked() {
+            state.checked.clear();
+        }
+        ui.separator();
+        let clean = ui.add_enabled(
+            !state.checked.is_empty(),
+            egui::Button::new(
+                egui::RichText::new(format!(
+                    "Nettoyer la sélection ({}, {})…",
+                    state.checked.len(),
+                    format_bytes(checked_bytes)
+                ))
+                .color(egui::Color32::RED),
+            ),
+        );
+        if clean.clicked() {
+            let mut paths: Vec<PathBuf> = state.checked.iter().cloned().collect();
+            paths.sort();
+            actions.pending_delete = paths;
+        }
+    });
+    ui.add_space(4.0);
+
+    let category_filter = state.category_filter;
+    let rows: Vec<&Artefact> = state
+        .artefacts
+        .iter()
+        .filter(|a| category_filter.is_none_or(|c| a.category == c))
+        .collect();
+    let checked = &mut state.checked;
+
+    TableBuilder::new(ui)
+        .striped(true)
+        .resizable(true)
+        .sense(egui::Sense::click())
+        .auto_shrink([false, false])
+        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+        .column(Column::exact(24.0))
+        .column(Column::initial(150.0).at_least(80.0).clip(true))
+        .column(Column::initial(90.0).at_least(60.0))
+        .column(Column::initial(70.0).at_least(50.0))
+        .column(Column::initial(140.0).at_least(80.0).clip(true))
+        .column(Column::initial(120.0).at_least(100.0))
+        .column(Column::remainder().at_least(150.0).clip(true))
+        .header(ROW_HEIGHT + 4.0, |mut header| {
+            for title in [
+                "",
+                "Catégorie",
+                "Taille",
+                "Fichiers",
+                "Projet",
+                "Modifié le",
+                "Chemin complet",
+            ] {
+                header.col(|ui| {
+                    ui.strong(title);
+                });
+            }
+        })
+        .body(|body| {
+            body.rows(ROW_HEIGHT, rows.len(), |mut row| {
+                let artefact = rows[row.index()];
+                row.set_selected(actions.selected_node_path.as_ref() == Some(&artefact.path));
+
+                row.col(|ui| {
+                    let mut is_checked = checked.contains(&artefact.path);
+                    if ui.checkbox(&mut is_checked, "").changed() {
+                        if is_checked {
+                            checked.insert(artefact.path.clone());
+                        } else {
+                            checked.remove(&artefact.path);
+                        }
+                    }
+                });
+                row.col(|ui| {
+                    ui.add(
+                        egui::Label::new(artefact.category.label())
+                            .selectable(false)
+                            .truncate(true),
+                    );
+                });
+                row.col(|ui| {
+                    right_aligned(ui, format_bytes(artefact.size));
+                });
+                row.col(|ui| {
+                    right_aligned(ui, artefact.file_count.to_string());
+                });
+                row.col(|ui| {
+                    ui.add(
+                        egui::Label::new(project_label(artefact.project.as_deref()))
+                            .selectable(false)
+                            .truncate(true),
+                    );
+                });
+                row.col(|ui| {
+                    ui.label(format_modified(artefact.modified));
+                });
+                row.col(|ui| {
+                    ui.add(
+                        egui::Label::new(
+                            egui::RichText::new(artefact.path.to_string_lossy()).monospace(),
+                        )
+                        .selectable(false)
+                        .truncate(true),
+                    );
+                });
+
+                let resp = row.response();
+                if resp.clicked() {
+                    actions.selected_node_path = Some(artefact.path.clone());
+                }
+                if resp.double_clicked() {
+                    actions.pending_reveal = Some(artefact.path.clone());
+                }
+                resp.context_menu(|ui| {
+                    node_context_menu(ui, &artefact.path, true, actions);
+                });
+            });
+        });
+}
@@ -0,0 +1,655 @@
+//! Recherche de doublons sur l'arbre scanné : regroupement par taille, puis
+//! par empreinte partielle, puis par empreinte complète du contenu (BLAKE3).
+//! Le calcul est parallèle (rayon) et annulable comme le scan.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use crossbeam_channel::{unbounded, Receiver};
+use eframe::egui;
+use egui_extras::{Column, TableBuilder};
+use rayon::prelude::*;
+
+use crate::jobs::JobKind;
+use crate::tree_table::right_aligned;
+use crate::{format_bytes, node_context_menu, trash, Node, NodeActions};
+
+/// Octets lus au début de chaque fichier pour l'empreinte partielle.
+const PARTIAL_HASH_BYTES: usize = 16 * 1024;
+const ROW_HEIGHT: f32 = 22.0;
+
+pub const PHASE_SIZE: u8 = 0;
+pub const PHASE_PARTIAL: u8 = 1;
+pub const PHASE_FULL: u8 = 2;
+
+/// Progression partagée de la recherche (phase + fichiers traités).
+#[derive(Default)]
+pub struct DuplicateProgress {
+    pub phase: AtomicU8,
+    pub files_total: AtomicU64,
+    pub files_done: AtomicU64,
+}
+
+/// Groupe de fichiers au contenu identique.
+#[derive(Debug, Clone)]
+pub struct DuplicateGroup {
+    pub size: u64,
+    pub files: Vec<PathBuf>,
+    /// Index du fichier conservé lors des actions sur le groupe.
+    pub keep: usize,
+}
+
+impl DuplicateGroup {
+    /// Espace libéré en ne gardant qu'une copie.
+    pub fn reclaimable(&self) -> u64 {
+        self.size * (self.files.len().saturating_sub(1)) as u64
+    }
+}
+
+/// Fichier candidat (même taille qu'au moins un autre).
+struct Candidate {
+    path: PathBuf,
+    size: u64,
+}
+
+/// Identité physique d'un fichier (périphérique, inode) : deux liens physiques
+/// vers le même inode ne sont pas des doublons.
+#[cfg(unix)]
+fn file_identity(path: &Path) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    fs::metadata(path).ok().map(|m| (m.dev(), m.ino()))
+}
+
+#[cfg(not(unix))]
+fn file_identity(_path: &Path) -> Option<(u64, u64)> {
+    None
+}
+
+/// Empreinte des `limit` premiers octets (tout le fichier si `None`).
+pub fn hash_file(path: &Path, limit: Option<usize>, cancel: &AtomicBool) -> io::Result<[u8; 32]> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = blake3::Hasher::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    let mut remaining = limit.unwrap_or(usize::MAX);
+
+    while remaining > 0 {
+        if cancel.load(Ordering::Relaxed) {
+            return Err(io::Error::new(io::ErrorKind::Interrupted, "Annulé"));
+        }
+        let want = buffer.len().min(remaining);
+        let read = file.read(&mut buffer[..want])?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        remaining -= read;
+    }
+
+    Ok(*hasher.finalize().as_bytes())
+}
+
+/// Empreinte complète actuelle de `path` s'il est toujours un fichier de
+/// `size` octets.
+fn current_hash(path: &Path, size: u64) -> io::Result<[u8; 32]> {
+    let meta = fs::symlink_metadata(path)?;
+    if !meta.is_file() || meta.len() != size {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "taille modifiée depuis la recherche",
+        ));
+    }
+    hash_file(path, None, &AtomicBool::new(false))
+}
+
+/// Revérifie, juste avant d'agir, que `file` est toujours une copie exacte de
+/// `keep` (même taille qu'à la recherche, même empreinte complète).
+pub fn still_duplicate(keep: &Path, file: &Path, size: u64) -> io::Result<()> {
+    let kept = current_hash(keep, size).map_err(|e| {
+        io::Error::new(
+            e.kind(),
+            format!("copie conservée {} : {e}", keep.to_string_lossy()),
+        )
+    })?;
+    if current_hash(file, size)? != kept {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "contenu différent de la copie conservée",
+        ));
+    }
+    Ok(())
+}
+
+/// Regroupe les candidats par clé calculée en parallèle et ne garde que les
+/// groupes d'au moins deux fichiers.
+fn regroup<K, F>(
+    groups: Vec<Vec<Candidate>>,
+    progress: &DuplicateProgress,
+    key: F,
+) -> Vec<Vec<Candidate>>
+where
+    K: Eq + std::hash::Hash + Send,
+    F: Fn(&Candidate) -> Option<K> + Sync,
+{
+    groups
+        .into_par_iter()
+        .flat_map_iter(|group| {
+            let mut by_key: HashMap<K, Vec<Candidate>> = HashMap::new();
+            for candidate in group {
+                let k = key(&candidate);
+                progress.files_done.fetch_add(1, Ordering::Relaxed);
+                if let Some(k) = k {
+                    by_key.entry(k).or_default().push(candidate);
+                }
+            }
+            by_key.into_values().filter(|g| g.len() > 1)
+        })
+        .collect()
+}
+
+/// Recherche complète des doublons parmi les fichiers de l'arbre d'au moins `min_size` octets.
+pub fn find_duplicates(
+    root: &Node,
+    min_size: u64,
+    cancel: &AtomicBool,
+    progress: &DuplicateProgress,
+) -> Result<Vec<DuplicateGroup>, String> {
+    // Phase 1 : regroupement par taille
+    progress.phase.store(PHASE_SIZE, Ordering::Relaxed);
+    let mut by_size: HashMap<u64, Vec<Candidate>> = HashMap::new();
+    let mut stack: Vec<&Node> = vec![root];
+    while let Some(node) = stack.pop() {
+        if node.is_dir {
+            stack.extend(node.children.iter());
+        } else if node.size >= min_size.max(1) {
+            by_size.entry(node.size).or_default().push(Candidate {
+                path: node.path.clone(),
+                size: node.size,
+            });
+        }
+    }
+
+    // Un seul représentant par inode (les liens physiques partagent déjà les données)
+    let groups: Vec<Vec<Candidate>> = by_size
+        .into_values()
+        .filter(|g| g.len() > 1)
+        .par_bridge()
+        .map(|group| {
+            let mut seen = std::collections::HashSet::new();
+            group
+                .into_iter()
+                .filter(|c| match file_identity(&c.path) {
+                    Some(id) => seen.insert(id),
+                    None => true,
+                })
+                .collect::<Vec<_>>()
+        })
+        .filter(|g| g.len() > 1)
+        .collect();
+
+    if cancel.load(Ordering::Relaxed) {
+        return Err("Recherche de doublons annulée".to_string());
+    }
+
+    // Phase 2 : empreinte partielle (début du fichier)
+    progress.phase.store(PHASE_PARTIAL, Ordering::Relaxed);
+    progress.files_done.store(0, Ordering::Relaxed);
+    progress.files_total.store(
+        groups.iter().map(|g| g.len() as u64).sum(),
+        Ordering::Relaxed,
+    );
+
+    let groups = regroup(groups, progress, |c| {
+        hash_file(&c.path, Some(PARTIAL_HASH_BYTES), cancel).ok()
+    });
+
+    if cancel.load(Ordering::Relaxed) {
+        return Err("Recherche de doublons annulée".to_string());
+    }
+
+    // Phase 3 : empreinte complète (inutile si le fichier tient dans l'empreinte partielle)
+    progress.phase.store(PHASE_FULL, Ordering::Relaxed);
+    progress.files_done.store(0, Ordering::Relaxed);
+    progress.files_total.store(
+        groups.iter().map(|g| g.len() as u64).sum(),
+        Ordering::Relaxed,
+    );
+
+    let groups = regroup(groups, progress, |c| {
+        if c.size <= PARTIAL_HASH_BYTES as u64 {
+            Some([0u8; 32])
+        } else {
+            hash_file(&c.path, None, cancel).ok()
+        }
+    });
+
+    if cancel.load(Ordering::Relaxed) {
+        return Err("Recherche de doublons annulée".to_string());
+    }
+
+    let mut result: Vec<DuplicateGroup> = groups
+        .into_iter()
+        .map(|group| {
+            let size = group[0].size;
+            let mut files: Vec<PathBuf> = group.into_iter().map(|c| c.path).collect();
+            files.sort();
+            DuplicateGroup {
+                size,
+                files,
+                keep: 0,
+            }
+        })
+        .collect();
+    result.sort_by_key(|g| std::cmp::Reverse(g.reclaimable()));
+
+    Ok(result)
+}
+
+/// Remplace `copy` par un lien physique vers `keep` (via un fichier temporaire
+/// renommé par-dessus la copie, pour ne jamais perdre la copie en cas d'échec).
+pub fn replace_with_hard_link(keep: &Path, copy: &Path) -> io::Result<()> {
+    let file_name = copy
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let tmp = copy.with_file_name(format!(".{file_name}.treesize-link"));
+
+    fs::hard_link(keep, &tmp)?;
+    if let Err(e) = fs::rename(&tmp, copy) {
+        let _ = fs::remove_file(&tmp);
+        return Err(e);
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GroupAction {
+    Delete,
+    HardLink,
+}
+
+/// Copies d'un groupe à mettre à la corbeille, supprimer ou remplacer par
+/// des liens physiques (`kind`) par la file des opérations, revérifiées
+/// juste avant d'agir.
+#[derive(Debug)]
+pub struct DuplicateJob {
+    pub keep: PathBuf,
+    pub size: u64,
+    pub copies: Vec<PathBuf>,
+    pub kind: JobKind,
+}
+
+/// Ligne affichée : en-tête de groupe ou fichier d'un groupe.
+#[derive(Clone, Copy)]
+enum DupRow {
+    Group(usize),
+    File(usize, usize),
+}
+
+/// Etat de la vue doublons : paramètres, recherche en cours, résultats.
+pub struct DuplicatesState {
+    /// Taille minimale des fichiers considérés (Ko).
+    pub min_size_kb: u64,
+    receiver: Option<Receiver<Result<Vec<DuplicateGroup>, String>>>,
+    cancel_flag: Option<Arc<AtomicBool>>,
+    progress: Option<Arc<DuplicateProgress>>,
+    groups: Option<Vec<DuplicateGroup>>,
+    pending_action: Option<(usize, GroupAction)>,
+    /// Suppression définitive cochée (corbeille par défaut).
+    permanent_delete: bool,
+    message: Option<String>,
+}
+
+impl Default for DuplicatesState {
+    fn default() -> Self {
+        Self {
+            min_size_kb: 1,
+            receiver: None,
+            cancel_flag: None,
+            progress: None,
+            groups: None,
+            pending_action: None,
+            permanent_delete: false,
+            message: None,
+        }
+    }
+}
+
+impl DuplicatesState {
+    pub fn is_running(&self) -> bool {
+        self.receiver.is_some()
+    }
+
+    /// Suit le renommage d'un fichier ou d'un dossier (sans nouvelle recherche).
+    pub fn rename_paths(&mut self, old: &Path, new: &Path) {
+        for group in self.groups.iter_mut().flatten() {
+            for file in &mut group.files {
+                if let Some(renamed) = crate::rename::reprefix(file, old, new) {
+                    *file = renamed;
+                }
+            }
+        }
+    }
+
+    /// Lance la recherche en arrière-plan sur l'arbre courant.
+    pub fn start(&mut self, root: Arc<Node>) {
+        let (tx, rx) = unbounded();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let cancel_clone = cancel.clone();
+        let progress = Arc::new(DuplicateProgress::default());
+        let progress_clone = progress.clone();
+        let min_size = self.min_size_kb * 1024;
+
+        self.receiver = Some(rx);
+        self.cancel_flag = Some(cancel);
+        self.progress = Some(progress);
+        self.groups = None;
+        self.message = None;
+
+        thread::spawn(move || {
+            let result = find_duplicates(&root, min_size, &cancel_clone, &progress_clone);
+            let _ = tx.send(result);
+        });
+    }
+
+    pub fn cancel(&mut self) {
+        if let Some(flag) = &self.cancel_flag {
+            flag.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Récupère le résultat s'il est disponible (à appeler à chaque frame).
+    pub fn poll(&mut self) {
+        let Some(rx) = &self.receiver else {
+            return;
+        };
+        if let Ok(result) = rx.try_recv() {
+            self.receiver = None;
+            self.cancel_flag = None;
+            self.progress = None;
+            match result {
+                Ok(groups) => {
+                    self.message = Some(format!(
+                        "{} groupes de doublons, {} récupérables.",
+                        groups.len(),
+                        format_bytes(groups.iter().map(|g| g.reclaimable()).sum())
+                    ));
+                    self.groups = Some(groups);
+                }
+                Err(e) => self.message = Some(e),
+            }
+        }
+    }
+
+    /// Retire un groupe de la liste et renvoie l'action sur ses copies,
+    /// confiée à la file des opérations (progression, annulation, chemins
+    /// protégés, journal d'audit, revérification de chaque copie).
+    fn queue_job(&mut self, index: usize, action: GroupAction) -> Option<DuplicateJob> {
+        let groups = self.groups.as_mut()?;
+        if index >= groups.len() {
+            return None;
+        }
+        let group = groups.remove(index);
+        let keep = group.files[group.keep].clone();
+        let copies: Vec<PathBuf> = group.files.into_iter().filter(|f| *f != keep).collect();
+        let kind = match action {
+            GroupAction::Delete if self.permanent_delete || !trash::SUPPORTED => JobKind::Delete,
+            GroupAction::Delete => JobKind::Trash,
+            GroupAction::HardLink => JobKind::HardLink,
+        };
+        self.message = Some(format!(
+            "{} copie(s) ajoutée(s) à la file des opérations ({}).",
+            copies.len(),
+            kind.label().to_lowercase()
+        ));
+        self.permanent_delete = false;
+        Some(DuplicateJob {
+            keep,
+            size: group.size,
+            copies,
+            kind,
+        })
+    }
+}
+
+/// Dessin de la vue doublons (paramètres, progression, groupes et actions).
+pub fn draw_duplicates(
+    ui: &mut egui::Ui,
+    root: &Arc<Node>,
+    state: &mut DuplicatesState,
+    actions: &mut NodeActions,
+) {
+    ui.horizontal(|ui| {
+        ui.label("Taille minimale :");
+        ui.add_enabled(
+            !state.is_running(),
+            egui::DragValue::new(&mut state.min_size_kb)
+                .clamp_range(0..=10_000_000)
+                .suffix(" Ko"),
+        );
+
+        if state.is_running() {
+            if ui
+                .button(egui::RichText::new("Arrêter").color(egui::Color32::RED))
+                .clicked()
+            {
+                state.cancel();
+            }
+        } else if ui.button("Rechercher les doublons").clicked() {
+            state.start(root.clone());
+        }
+    });
+
+    if let Some(progress) = &state.progress {
+        let phase = match progress.phase.load(Ordering::Relaxed) {
+            PHASE_SIZE => "Regroupement par taille…",
+            PHASE_PARTIAL => "Empreinte partielle…",
+            _ => "Empreinte complète du contenu…",
+        };
+        let total = progress.files_total.load(Ordering::Relaxed);
+        let done = progress.files_done.load(Ordering::Relaxed);
+        ui.horizontal(|ui| {
+            ui.spinner();
+            ui.label(phase);
+            if total > 0 {
+                ui.add(
+                    egui::ProgressBar::new((done as f32 / total as f32).min(1.0))
+                        .desired_width(200.0)
+                        .show_percentage(),
+                );
+                ui.small(format!("{done} / {total} fichiers"));
+            }
+        });
+    }
+
+    if let Some(message) = &state.message {
+        ui.small(message);
+    }
+
+    let Some(groups) = &mut state.groups else {
+        if !state.is_running() {
+            ui.add_space(6.0);
+            ui.weak(
+                "Les fichiers sont comparés par taille, puis par empreinte \
+                 partielle et enfin par empreinte complète de leur contenu.",
+            );
+        }
+        return;
+    };
+
+    ui.add_space(4.0);
+
+    let mut rows: Vec<DupRow> = Vec::new();
+    for (g, group) in groups.iter().enumerate() {
+        rows.push(DupRow::Group(g));
+        rows.extend((0..group.files.len()).map(|f| DupRow::File(g, f)));
+    }
+
+    let mut requested: Option<(usize, GroupAction)> = None;
+
+    TableBuilder::new(ui)
+        .striped(false)
+        .sense(egui::Sense::click())
+        .auto_shrink([false, false])
+        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+        .column(Column::remainder().at_least(300.0).clip(true))
+        .column(Column::initial(110.0).at_least(80.0))
+        .body(|body| {
+            body.rows(ROW_HEIGHT, rows.len(), |mut row| match rows[row.index()] {
+                DupRow::Group(g) => {
+                    let group = &groups[g];
+                    row.col(|ui| {
+                        ui.strong(format!(
+                            "{} copies × {} — récupérable : {}",
+                            group.files.len(),
+                            format_bytes(group.size),
+                            format_bytes(group.reclaimable())
+                        ));
+                        if ui.small_button("Supprimer les copies…").clicked() {
+                            requested = Some((g, GroupAction::Delete));
+                        }
+                        if ui.small_button("Liens physiques…").clicked() {
+                            requested = Some((g, GroupAction::HardLink));
+                        }
+                    });
+                    row.col(|_| {});
+                }
+                DupRow::File(g, f) => {
+                    let group = &mut groups[g];
+                    let path = group.files[f].clone();
+                    row.set_selected(actions.selected_node_path.as_ref() == Some(&path));
+                    row.col(|ui| {
+                        ui.add_space(16.0);
+                        ui.radio_value(&mut group.keep, f, "")
+                            .on_hover_text("Copie conservée");
+                        ui.add(
+                            egui::Label::new(
+                                egui::RichText::new(path.to_string_lossy()).monospace(),
+                            )
+                            .selectable(false)
+                            .truncate(true),
+                        );
+                    });
+                    row.col(|ui| {
+                        if group.keep == f {
+                            right_aligned(ui, "conservé".to_string());
+                        }
+                    });
+
+                    let resp = row.response();
+                    if resp.clicked() {
+                        actions.selected_node_path = Some(path.clone());
+                    }
+                    if resp.double_clicked() {
+                        actions.pending_reveal = Some(path.clone());
+                    }
+                    resp.context_menu(|ui| {
+                        node_context_menu(ui, &path, false, actions);
+                    });
+                }
+            });
+        });
+
+    if requested.is_some() {
+        state.pending_action = requested;
+    }
+
+    draw_action_window(ui.ctx(), state, actions);
+}
+
+/// Fenêtre de confirmation d'une action sur un groupe de doublons.
+fn draw_action_window(ctx: &egui::Context, state: &mut DuplicatesState, actions: &mut NodeActions) {
+    let Some((index, action)) = state.pending_action else {
+        return;
+    };
+    let Some(group) = state.groups.as_ref().and_then(|g| g.get(index)) else {
+        state.pending_action = None;
+        return;
+    };
+
+    let keep = group.files[group.keep].clone();
+    let copies: Vec<PathBuf> = group
+        .files
+        .iter()
+        .filter(|f| **f != keep)
+        .cloned()
+        .collect();
+
+    let title = match action {
+        GroupAction::Delete => "Supprimer les copies",
+        GroupAction::HardLink => "Remplacer par des liens physiques",
+    };
+
+    let mut confirmed = false;
+    let mut cancelled = false;
+
+    egui::Window::new(title)
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+        .show(ctx, |ui| {
+            ui.label("Copie conservée :");
+            ui.monospace(keep.to_string_lossy());
+            ui.add_space(6.0);
+            ui.label(match action {
+                GroupAction::Delete => "Copies supprimées :",
+                GroupAction::HardLink => "Copies remplacées par un lien physique :",
+            });
+            for copy in &copies {
+                ui.monospace(copy.to_string_lossy());
+            }
+            ui.add_space(8.0);
+            match action {
+                GroupAction::Delete => {
+                    if trash::SUPPORTED {
+                        ui.label("Les copies pourront être restaurées depuis la corbeille.");
+                        ui.checkbox(
+                            &mut state.permanent_delete,
+                            "Supprimer définitivement (sans passer par la corbeille)",
+                        );
+                    }
+                    if state.permanent_delete || !trash::SUPPORTED {
+                        ui.colored_label(
+                            egui::Color32::RED,
+                            "Attention : la suppression est définitive.",
+                        );
+                    }
+                }
+                GroupAction::HardLink => {
+                    ui.small(
+                        "Les fichiers doivent être sur le même système de fichiers. \
+                         Modifier l'un des liens modifiera aussi les autres.",
+                    );
+                }
+            }
+            ui.small(
+                "Chaque copie est revérifiée juste avant d'agir ; \
+                 une copie modifiée depuis la recherche est ignorée.",
+            );
+            ui.add_space(12.0);
+
+            ui.horizontal(|ui| {
+                if ui.button("Annuler").clicked() {
+                    cancelled = true;
+                }
+                if ui
+                    .button(egui::RichText::new(title).color(egui::Color32::RED))
+                    .clicked()
+                {
+                    confirmed = true;
+                }
+            });
+        });
+
+    if confirmed {
+        actions.pending_duplicate_job = state.queue_job(index, action);
+        state.pending_action = None;
+    } else if cancelled {
+        state.permanent_delete = false;
+        state.pending_action = None;
+    }
+}
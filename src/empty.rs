@@ -0,0 +1,296 @@
+//! Dossiers vides (récursivement : aucun fichier dedans) et fichiers de
+//! 0 octet, avec suppression par lot via la fenêtre de confirmation commune.
+//! L'arbre scanné ne voit pas tout (liens cassés, FIFO, sockets, entrées
+//! illisibles) : le vide est confirmé sur le disque avant de lister et à
+//! nouveau avant de supprimer.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use eframe::egui;
+use egui_extras::{Column, TableBuilder};
+
+use crate::tree_table::{format_modified, right_aligned};
+use crate::{node_context_menu, Node, NodeActions};
+
+const ROW_HEIGHT: f32 = 20.0;
+
+/// Liste affichée.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EmptyKind {
+    Dirs,
+    Files,
+}
+
+/// Un dossier vide ou un fichier de 0 octet.
+struct EmptyEntry {
+    path: PathBuf,
+    name: String,
+    is_dir: bool,
+    /// Sous-dossiers (tous vides) d'un dossier vide.
+    subdirs: u64,
+    modified: Option<SystemTime>,
+}
+
+/// Etat de la vue : listes en cache et éléments cochés.
+pub struct EmptyState {
+    kind: EmptyKind,
+    computed: bool,
+    dirs: Vec<EmptyEntry>,
+    files: Vec<EmptyEntry>,
+    checked: HashSet<PathBuf>,
+}
+
+impl Default for EmptyState {
+    fn default() -> Self {
+        Self {
+            kind: EmptyKind::Dirs,
+            computed: false,
+            dirs: Vec::new(),
+            files: Vec::new(),
+            checked: HashSet::new(),
+        }
+    }
+}
+
+impl EmptyState {
+    /// A appeler quand l'arbre change (nouveau scan).
+    pub fn invalidate(&mut self) {
+        self.computed = false;
+        self.dirs.clear();
+        self.files.clear();
+        self.checked.clear();
+    }
+
+    fn refresh(&mut self, root: &Node) {
+        if self.computed {
+            return;
+        }
+        let (dirs, files) = find_empty(root);
+        self.dirs = dirs;
+        self.files = files;
+        self.computed = true;
+    }
+
+    fn entries(&self) -> &[EmptyEntry] {
+        match self.kind {
+            EmptyKind::Dirs => &self.dirs,
+            EmptyKind::Files => &self.files,
+        }
+    }
+}
+
+fn count_subdirs(node: &Node) -> u64 {
+    node.children
+        .iter()
+        .filter(|c| c.is_dir)
+        .map(|c| 1 + count_subdirs(c))
+        .sum()
+}
+
+/// Dossier vide sur le disque : aucune entrée, sinon des sous-dossiers
+/// eux-mêmes vides (un lien, même vers un dossier, compte comme une entrée).
+fn is_empty_dir(path: &Path) -> io::Result<bool> {
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() || !is_empty_dir(&entry.path())? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Elément toujours vide sur le disque (dossier vide ou fichier de
+/// 0 octet) ; faux s'il est illisible ou a disparu.
+pub fn still_empty(path: &Path) -> bool {
+    match fs::symlink_metadata(path) {
+        Ok(meta) if meta.is_dir() => is_empty_dir(path).unwrap_or(false),
+        Ok(meta) => meta.is_file() && meta.len() == 0,
+        Err(_) => false,
+    }
+}
+
+/// Dossiers sans aucun fichier (seul le plus haut d'une arborescence vide est
+/// listé, la racine exclue) et fichiers de 0 octet, triés par chemin.
+fn find_empty(root: &Node) -> (Vec<EmptyEntry>, Vec<EmptyEntry>) {
+    let mut dirs = Vec::new();
+    let mut files = Vec::new();
+
+    let mut stack: Vec<&Node> = root.children.iter().collect();
+    while let Some(node) = stack.pop() {
+        let entry = |subdirs| EmptyEntry {
+            path: node.path.clone(),
+            name: node.name.clone(),
+            is_dir: node.is_dir,
+            subdirs,
+            modified: node.modified,
+        };
+        if node.is_dir {
+            // Un dossier sans fichier d'après le scan peut contenir des
+            // entrées non comptées : ses sous-dossiers sont alors examinés
+            if node.file_count == 0 && is_empty_dir(&node.path).unwrap_or(false) {
+                dirs.push(entry(count_subdirs(node)));
+            } else {
+                stack.extend(node.children.iter());
+            }
+        } else if node.size == 0 {
+            files.push(entry(0));
+        }
+    }
+
+    dirs.sort_by(|a, b| a.path.cmp(&b.path));
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    (dirs, files)
+}
+
+/// Dessin de la vue : onglets dossiers / fichiers, cases à cocher et
+/// suppression des éléments cochés.
+pub fn draw_empty(
+    ui: &mut egui::Ui,
+    root: &Node,
+    state: &mut EmptyState,
+    actions: &mut NodeActions,
+) {
+    state.refresh(root);
+
+    let nested: u64 = state.dirs.iter().map(|d| d.subdirs).sum();
+    ui.horizontal(|ui| {
+        ui.selectable_value(
+            &mut state.kind,
+            EmptyKind::Dirs,
+            format!("Dossiers vides ({})", state.dirs.len()),
+        );
+        ui.selectable_value(
+            &mut state.kind,
+            EmptyKind::Files,
+            format!("Fichiers de 0 octet ({})", state.files.len()),
+        );
+    });
+    match state.kind {
+        EmptyKind::Dirs => ui.small(format!(
+            "{} dossiers sans aucun fichier, {} dossiers au total en comptant leurs sous-dossiers.",
+            state.dirs.len(),
+            state.dirs.len() as u64 + nested
+        )),
+        EmptyKind::Files => ui.small(format!("{} fichiers de 0 octet.", state.files.len())),
+    };
+
+    let checked_here = state
+        .entries()
+        .iter()
+        .filter(|e| state.checked.contains(&e.path))
+        .count();
+    ui.horizontal(|ui| {
+        if ui.button("Tout cocher").clicked() {
+            let paths: Vec<PathBuf> = state.entries().iter().map(|e| e.path.clone()).collect();
+            state.checked.extend(paths);
+        }
+        if ui.button("Tout décocher").clicked() {
+            state.checked.clear();
+        }
+        ui.separator();
+        let delete = ui.add_enabled(
+            !state.checked.is_empty(),
+            egui::Button::new(
+                egui::RichText::new(format!("Supprimer la sélection ({})…", state.checked.len()))
+                    .color(egui::Color32::RED),
+            ),
+        );
+        if delete.clicked() {
+            let mut paths: Vec<PathBuf> = state.checked.iter().cloned().collect();
+            paths.sort();
+            actions.pending_delete = paths;
+            actions.delete_only_empty = true;
+        }
+        if checked_here != state.checked.len() {
+            ui.weak(format!("dont {checked_here} dans cette liste"));
+        }
+    });
+    ui.add_space(4.0);
+
+    let kind = state.kind;
+    let entries = match kind {
+        EmptyKind::Dirs => &state.dirs,
+        EmptyKind::Files => &state.files,
+    };
+    let checked = &mut state.checked;
+
+    TableBuilder::new(ui)
+        .striped(true)
+        .resizable(true)
+        .sense(egui::Sense::click())
+        .auto_shrink([false, false])
+        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+        .column(Column::exact(24.0))
+        .column(Column::initial(220.0).at_least(100.0).clip(true))
+        .column(Column::initial(90.0).at_least(60.0))
+        .column(Column::initial(120.0).at_least(100.0))
+        .column(Column::remainder().at_least(150.0).clip(true))
+        .header(ROW_HEIGHT + 4.0, |mut header| {
+            let count_title = match kind {
+                EmptyKind::Dirs => "Sous-dossiers",
+                EmptyKind::Files => "",
+            };
+            for title in ["", "Nom", count_title, "Modifié le", "Chemin complet"] {
+                header.col(|ui| {
+                    ui.strong(title);
+                });
+            }
+        })
+        .body(|body| {
+            body.rows(ROW_HEIGHT, entries.len(), |mut row| {
+                let entry = &entries[row.index()];
+                row.set_selected(actions.selected_node_path.as_ref() == Some(&entry.path));
+
+                row.col(|ui| {
+                    let mut is_checked = checked.contains(&entry.path);
+                    if ui.checkbox(&mut is_checked, "").changed() {
+                        if is_checked {
+                            checked.insert(entry.path.clone());
+                        } else {
+                            checked.remove(&entry.path);
+                        }
+                    }
+                });
+                row.col(|ui| {
+                    let text = if entry.is_dir {
+                        format!("📁 {}", entry.name)
+                    } else {
+                        entry.name.clone()
+                    };
+                    ui.add(egui::Label::new(text).selectable(false).truncate(true));
+                });
+                row.col(|ui| {
+                    if entry.is_dir {
+                        right_aligned(ui, entry.subdirs.to_string());
+                    }
+                });
+                row.col(|ui| {
+                    ui.label(format_modified(entry.modified));
+                });
+                row.col(|ui| {
+                    ui.add(
+                        egui::Label::new(
+                            egui::RichText::new(entry.path.to_string_lossy()).monospace(),
+                        )
+                        .selectable(false)
+                        .truncate(true),
+                    );
+                });
+
+                let resp = row.response();
+                if resp.clicked() {
+                    actions.selected_node_path = Some(entry.path.clone());
+                }
+                if resp.double_clicked() {
+                    actions.pending_reveal = Some(entry.path.clone());
+                }
+                resp.context_menu(|ui| {
+                    node_context_menu(ui, &entry.path, entry.is_dir, actions);
+                });
+            });
+        });
+}
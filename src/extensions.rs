@@ -0,0 +1,434 @@
+//! Statistiques par extension, calculées en arrière-plan à la fin du scan,
+//! avec la liste détaillée des fichiers de l'extension sélectionnée.
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::thread;
+
+use crossbeam_channel::{unbounded, Receiver};
+use eframe::egui;
+use egui_extras::{Column, TableBuilder};
+use rayon::prelude::*;
+
+use crate::sorting::extension_of;
+use crate::top_n::TopEntry;
+use crate::tree_table::{format_modified, right_aligned};
+use crate::{format_bytes, node_context_menu, Node, NodeActions};
+
+const ROW_HEIGHT: f32 = 20.0;
+
+/// Agrégat pour une extension (clé vide = fichiers sans extension).
+#[derive(Debug, Clone, Default)]
+pub struct ExtensionStat {
+    pub extension: String,
+    pub total_bytes: u64,
+    pub file_count: u64,
+    pub largest_size: u64,
+    pub largest_path: PathBuf,
+}
+
+impl ExtensionStat {
+    pub fn average(&self) -> u64 {
+        self.total_bytes.checked_div(self.file_count).unwrap_or(0)
+    }
+
+    pub fn display_name(&self) -> String {
+        display_extension(&self.extension)
+    }
+
+    fn add_file(&mut self, node: &Node) {
+        self.total_bytes += node.size;
+        self.file_count += 1;
+        if node.size >= self.largest_size {
+            self.largest_size = node.size;
+            self.largest_path = node.path.clone();
+        }
+    }
+
+    fn merge(&mut self, other: ExtensionStat) {
+        self.total_bytes += other.total_bytes;
+        self.file_count += other.file_count;
+        if other.largest_size >= self.largest_size {
+            self.largest_size = other.largest_size;
+            self.largest_path = other.largest_path;
+        }
+    }
+}
+
+/// Libellé affiché pour une extension.
+pub fn display_extension(extension: &str) -> String {
+    if extension.is_empty() {
+        "(sans extension)".to_string()
+    } else {
+        format!(".{extension}")
+    }
+}
+
+fn accumulate(node: &Node, stats: &mut HashMap<String, ExtensionStat>) {
+    if node.is_dir {
+        for child in &node.children {
+            accumulate(child, stats);
+        }
+        return;
+    }
+
+    let extension = extension_of(node);
+    stats
+        .entry(extension.clone())
+        .or_insert_with(|| ExtensionStat {
+            extension,
+            ..Default::default()
+        })
+        .add_file(node);
+}
+
+/// Agrège l'arbre par extension (en parallèle sur les enfants de la racine).
+pub fn compute_extension_stats(root: &Node) -> Vec<ExtensionStat> {
+    let merged = root
+        .children
+        .par_iter()
+        .fold(HashMap::new, |mut stats, child| {
+            accumulate(child, &mut stats);
+            stats
+        })
+        .reduce(HashMap::new, |mut a, b| {
+            for (extension, stat) in b {
+                match a.get_mut(&extension) {
+                    Some(existing) => existing.merge(stat),
+                    None => {
+                        a.insert(extension, stat);
+                    }
+                }
+            }
+            a
+        });
+
+    let mut stats: Vec<ExtensionStat> = merged.into_values().collect();
+    stats.sort_by_key(|s| Reverse(s.total_bytes));
+    stats
+}
+
+/// Fichiers d'une extension donnée, du plus gros au plus petit.
+fn files_with_extension(root: &Node, extension: &str) -> Vec<TopEntry> {
+    let mut files = Vec::new();
+    let mut stack: Vec<&Node> = vec![root];
+    while let Some(node) = stack.pop() {
+        if node.is_dir {
+            stack.extend(node.children.iter());
+        } else if extension_of(node) == extension {
+            files.push(TopEntry {
+                path: node.path.clone(),
+                name: node.name.clone(),
+                size: node.size,
+                is_dir: false,
+                modified: node.modified,
+            });
+        }
+    }
+    files.sort_by_key(|f| Reverse(f.size));
+    files
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExtColumn {
+    Extension,
+    Total,
+    Count,
+    Average,
+    Largest,
+}
+
+/// Etat de la vue : calcul en cours, résultats, tri et extension sélectionnée.
+pub struct ExtensionsState {
+    receiver: Option<Receiver<Vec<ExtensionStat>>>,
+    stats: Option<Vec<ExtensionStat>>,
+    column: ExtColumn,
+    descending: bool,
+    selected: Option<String>,
+    files: Vec<TopEntry>,
+}
+
+impl Default for ExtensionsState {
+    fn default() -> Self {
+        Self {
+            receiver: None,
+            stats: None,
+            column: ExtColumn::Total,
+            descending: true,
+            selected: None,
+            files: Vec::new(),
+        }
+    }
+}
+
+impl ExtensionsState {
+    /// Lance le calcul en arrière-plan pour un nouvel arbre.
+    pub fn start(&mut self, root: Arc<Node>) {
+        self.clear();
+
+        let (tx, rx) = unbounded();
+        self.receiver = Some(rx);
+        thread::spawn(move || {
+            let _ = tx.send(compute_extension_stats(&root));
+        });
+    }
+
+    pub fn clear(&mut self) {
+        self.receiver = None;
+        self.stats = None;
+        self.selected = None;
+        self.files.clear();
+    }
+
+    pub fn is_computing(&self) -> bool {
+        self.receiver.is_some()
+    }
+
+    /// Récupère le résultat s'il est disponible (à appeler à chaque frame).
+    pub fn poll(&mut self) {
+        if let Some(rx) = &self.receiver {
+            if let Ok(stats) = rx.try_recv() {
+                self.receiver = None;
+                self.stats = Some(stats);
+                self.sort_stats();
+            }
+        }
+    }
+
+    fn click_header(&mut self, column: ExtColumn) {
+        if self.column == column {
+            self.descending = !self.descending;
+        } else {
+            self.column = column;
+            self.descending = column != ExtColumn::Extension;
+        }
+        self.sort_stats();
+    }
+
+    fn sort_stats(&mut self) {
+        let Some(stats) = &mut self.stats else {
+            return;
+        };
+        let column = self.column;
+        let descending = self.descending;
+        stats.sort_by(|a, b| {
+            let ord = match column {
+                ExtColumn::Extension => a.extension.cmp(&b.extension),
+                ExtColumn::Total => a.total_bytes.cmp(&b.total_bytes),
+                ExtColumn::Count => a.file_count.cmp(&b.file_count),
+                ExtColumn::Average => a.average().cmp(&b.average()),
+                ExtColumn::Largest => a.largest_size.cmp(&b.largest_size),
+            };
+            if descending {
+                ord.reverse()
+            } else {
+                ord
+            }
+        });
+    }
+}
+
+/// Dessin de la vue extensions : tableau agrégé + détail de l'extension choisie.
+pub fn draw_extensions(
+    ui: &mut egui::Ui,
+    root: &Node,
+    state: &mut ExtensionsState,
+    actions: &mut NodeActions,
+) {
+    let Some(stats) = &state.stats else {
+        ui.horizontal(|ui| {
+            if state.is_computing() {
+                ui.spinner();
+                ui.label("Calcul des statistiques par extension…");
+            } else {
+                ui.weak("Aucune statistique disponible.");
+            }
+        });
+        return;
+    };
+
+    let root_size = root.size.max(1);
+    ui.small(format!("{} extensions", stats.len()));
+
+    let mut clicked_header: Option<ExtColumn> = None;
+    let mut clicked_extension: Option<String> = None;
+    let header_text = |title: &str, column: ExtColumn| {
+        if state.column == column {
+            let arrow = if state.descending { "⬇" } else { "⬆" };
+            format!("{title} {arrow}")
+        } else {
+            title.to_string()
+        }
+    };
+
+    let table_height = ui.available_height() * 0.5;
+    ui.push_id("extensions_table", |ui| {
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(true)
+            .sense(egui::Sense::click())
+            .auto_shrink([false, true])
+            .max_scroll_height(table_height)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(Column::initial(130.0).at_least(80.0).clip(true))
+            .column(Column::initial(90.0).at_least(60.0))
+            .column(Column::initial(80.0).at_least(50.0))
+            .column(Column::initial(90.0).at_least(60.0))
+            .column(Column::initial(90.0).at_least(60.0))
+            .column(Column::remainder().at_least(60.0))
+            .header(ROW_HEIGHT + 4.0, |mut header| {
+                for (title, column) in [
+                    ("Extension", Some(ExtColumn::Extension)),
+                    ("Total", Some(ExtColumn::Total)),
+                    ("Fichiers", Some(ExtColumn::Count)),
+                    ("Moyenne", Some(ExtColumn::Average)),
+                    ("Plus gros", Some(ExtColumn::Largest)),
+                    ("% racine", None),
+                ] {
+                    header.col(|ui| match column {
+                        Some(column) => {
+                            if ui
+                                .add(
+                                    egui::Label::new(
+                                        egui::RichText::new(header_text(title, column)).strong(),
+                                    )
+                                    .selectable(false)
+                                    .sense(egui::Sense::click()),
+                                )
+                                .on_hover_text("Cliquer pour trier")
+                                .clicked()
+                            {
+                                clicked_header = Some(column);
+                            }
+                        }
+                        None => {
+                            ui.strong(title);
+                        }
+                    });
+                }
+            })
+            .body(|body| {
+                body.rows(ROW_HEIGHT, stats.len(), |mut row| {
+                    let stat = &stats[row.index()];
+                    row.set_selected(state.selected.as_ref() == Some(&stat.extension));
+
+                    row.col(|ui| {
+                        ui.add(egui::Label::new(stat.display_name()).selectable(false));
+                    });
+                    row.col(|ui| {
+                        right_aligned(ui, format_bytes(stat.total_bytes));
+                    });
+                    row.col(|ui| {
+                        right_aligned(ui, stat.file_count.to_string());
+                    });
+                    row.col(|ui| {
+                        right_aligned(ui, format_bytes(stat.average()));
+                    });
+                    row.col(|ui| {
+                        right_aligned(ui, format_bytes(stat.largest_size))
+                            .on_hover_text(stat.largest_path.to_string_lossy());
+                    });
+                    row.col(|ui| {
+                        right_aligned(
+                            ui,
+                            format!("{:.2}%", stat.total_bytes as f64 / root_size as f64 * 100.0),
+                        );
+                    });
+
+                    if row.response().clicked() {
+                        clicked_extension = Some(stat.extension.clone());
+                    }
+                });
+            });
+    });
+
+    if let Some(column) = clicked_header {
+        state.click_header(column);
+    }
+    if let Some(extension) = clicked_extension {
+        if state.selected.as_ref() != Some(&extension) {
+            state.files = files_with_extension(root, &extension);
+            state.selected = Some(extension);
+        }
+    }
+
+    let Some(selected) = &state.selected else {
+        ui.add_space(6.0);
+        ui.weak("Cliquer sur une extension pour lister ses fichiers.");
+        return;
+    };
+
+    ui.add_space(6.0);
+    ui.separator();
+    ui.label(
+        egui::RichText::new(format!(
+            "Fichiers {} ({})",
+            display_extension(selected),
+            state.files.len()
+        ))
+        .strong(),
+    );
+
+    let files = &state.files;
+    ui.push_id("extension_files_table", |ui| {
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(true)
+            .sense(egui::Sense::click())
+            .auto_shrink([false, false])
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(Column::initial(220.0).at_least(100.0).clip(true))
+            .column(Column::initial(90.0).at_least(60.0))
+            .column(Column::initial(120.0).at_least(100.0))
+            .column(Column::remainder().at_least(150.0).clip(true))
+            .header(ROW_HEIGHT + 4.0, |mut header| {
+                for title in ["Nom", "Taille", "Modifié le", "Chemin complet"] {
+                    header.col(|ui| {
+                        ui.strong(title);
+                    });
+                }
+            })
+            .body(|body| {
+                body.rows(ROW_HEIGHT, files.len(), |mut row| {
+                    let entry = &files[row.index()];
+                    row.set_selected(actions.selected_node_path.as_ref() == Some(&entry.path));
+
+                    row.col(|ui| {
+                        ui.add(
+                            egui::Label::new(&entry.name)
+                                .selectable(false)
+                                .truncate(true),
+                        );
+                    });
+                    row.col(|ui| {
+                        right_aligned(ui, format_bytes(entry.size));
+                    });
+                    row.col(|ui| {
+                        ui.label(format_modified(entry.modified));
+                    });
+                    row.col(|ui| {
+                        ui.add(
+                            egui::Label::new(
+                                egui::RichText::new(entry.path.to_string_lossy()).monospace(),
+                            )
+                            .selectable(false)
+                            .truncate(true),
+                        );
+                    });
+
+                    let resp = row.response();
+                    if resp.clicked() {
+                        actions.selected_node_path = Some(entry.path.clone());
+                    }
+                    if resp.double_clicked() {
+                        actions.pending_reveal = Some(entry.path.clone());
+                    }
+                    resp.context_menu(|ui| {
+                        node_context_menu(ui, &entry.path, false, actions);
+                    });
+                });
+            });
+    });
+}
@@ -0,0 +1,358 @@
+//! Filtre de l'arborescence : copie élaguée de l'arbre ne gardant que les
+//! éléments retenus et leurs dossiers parents (tailles recalculées).
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use chrono::{Local, NaiveDate, NaiveTime, TimeZone};
+use regex::{Regex, RegexBuilder};
+
+use crate::age::AgeBasis;
+use crate::{format_bytes, Node};
+
+/// Interprétation du texte recherché dans les noms.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NameMode {
+    /// Sous-chaîne, sans tenir compte de la casse.
+    #[default]
+    Substring,
+    /// Motif `*`, `?`, `[abc]` sur le nom entier, sans tenir compte de la casse.
+    Glob,
+    Regex,
+}
+
+impl NameMode {
+    pub const ALL: [NameMode; 3] = [NameMode::Substring, NameMode::Glob, NameMode::Regex];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            NameMode::Substring => "Texte",
+            NameMode::Glob => "Glob",
+            NameMode::Regex => "Regex",
+        }
+    }
+}
+
+/// Type d'éléments recherchés.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum KindFilter {
+    #[default]
+    All,
+    Files,
+    Dirs,
+}
+
+impl KindFilter {
+    pub const ALL: [KindFilter; 3] = [KindFilter::All, KindFilter::Files, KindFilter::Dirs];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            KindFilter::All => "Tous",
+            KindFilter::Files => "Fichiers",
+            KindFilter::Dirs => "Dossiers",
+        }
+    }
+}
+
+/// Critères du filtre (tous combinés en ET). Vide = pas de filtre.
+///
+/// Un fichier est retenu s'il vérifie tous les critères. Un dossier peut
+/// aussi être retenu pour lui-même (recherche par nom, ou type "Dossiers") :
+/// il est alors affiché avec tout son contenu.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TreeFilter {
+    /// Texte recherché dans le nom (vide = tous les noms).
+    pub name: String,
+    pub name_mode: NameMode,
+    pub kind: KindFilter,
+    /// Taille minimale en octets (0 = pas de minimum).
+    pub min_size: u64,
+    /// Taille maximale en octets, exclue.
+    pub max_size: Option<u64>,
+    /// Modifié à partir de ce jour (inclus).
+    pub modified_from: Option<NaiveDate>,
+    /// Modifié jusqu'à ce jour (inclus).
+    pub modified_to: Option<NaiveDate>,
+    /// Ne garder que les fichiers dont la date (modification ou accès)
+    /// remonte à plus de N jours.
+    pub older_than: Option<(u64, AgeBasis)>,
+    /// Ne retenir que des éléments situés dans ce dossier.
+    pub within: Option<PathBuf>,
+}
+
+impl TreeFilter {
+    pub fn is_active(&self) -> bool {
+        *self != TreeFilter::default()
+    }
+
+    /// Résumé lisible des critères actifs.
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.name.is_empty() {
+            parts.push(match self.name_mode {
+                NameMode::Substring => format!("nom contenant « {} »", self.name),
+                NameMode::Glob => format!("nom correspondant à « {} »", self.name),
+                NameMode::Regex => format!("regex « {} »", self.name),
+            });
+        }
+        match self.kind {
+            KindFilter::All => {}
+            KindFilter::Files => parts.push("fichiers uniquement".to_string()),
+            KindFilter::Dirs => parts.push("dossiers uniquement".to_string()),
+        }
+        if self.min_size > 0 {
+            parts.push(format!("≥ {}", format_bytes(self.min_size)));
+        }
+        if let Some(max) = self.max_size {
+            parts.push(format!("< {}", format_bytes(max)));
+        }
+        if let Some(from) = self.modified_from {
+            parts.push(format!("modifié depuis le {}", from.format("%d/%m/%Y")));
+        }
+        if let Some(to) = self.modified_to {
+            parts.push(format!("modifié jusqu'au {}", to.format("%d/%m/%Y")));
+        }
+        if let Some((days, basis)) = self.older_than {
+            parts.push(format!("{} : plus de {days} jours", basis.label()));
+        }
+        if let Some(within) = &self.within {
+            parts.push(format!("dans {}", within.to_string_lossy()));
+        }
+        parts.join(", ")
+    }
+
+    /// Vérifie les critères (motif ou expression régulière invalide…).
+    pub fn validate(&self) -> Result<(), String> {
+        self.compile().map(|_| ())
+    }
+
+    /// Prépare les critères pour un parcours (motif compilé, dates calculées
+    /// une seule fois).
+    fn compile(&self) -> Result<Matcher, String> {
+        let name = if self.name.is_empty() {
+            NameMatcher::Any
+        } else {
+            match self.name_mode {
+                NameMode::Substring => NameMatcher::Substring(self.name.to_lowercase()),
+                NameMode::Glob => NameMatcher::Regex(
+                    RegexBuilder::new(&glob_to_regex(&self.name))
+                        .case_insensitive(true)
+                        .build()
+                        .map_err(|e| format!("Motif glob invalide : {e}"))?,
+                ),
+                NameMode::Regex => NameMatcher::Regex(
+                    Regex::new(&self.name)
+                        .map_err(|e| format!("Expression régulière invalide : {e}"))?,
+                ),
+            }
+        };
+
+        if let (Some(from), Some(to)) = (self.modified_from, self.modified_to) {
+            if from > to {
+                return Err("La date de début est postérieure à la date de fin.".to_string());
+            }
+        }
+
+        let now = SystemTime::now();
+        Ok(Matcher {
+            dirs_match: self.kind == KindFilter::Dirs
+                || (self.kind == KindFilter::All && !self.name.is_empty()),
+            files_match: self.kind != KindFilter::Dirs,
+            name,
+            min_size: self.min_size,
+            max_size: self.max_size,
+            modified_from: self.modified_from.and_then(start_of_day),
+            modified_to: self
+                .modified_to
+                .and_then(|d| d.succ_opt())
+                .and_then(start_of_day),
+            older_than: self.older_than.map(|(days, basis)| {
+                let cutoff = now
+                    .checked_sub(Duration::from_secs(days * 86_400))
+                    .unwrap_or(SystemTime::UNIX_EPOCH);
+                (cutoff, basis)
+            }),
+            within: self.within.clone(),
+        })
+    }
+}
+
+/// Minuit (heure locale) au début d'une journée.
+fn start_of_day(date: NaiveDate) -> Option<SystemTime> {
+    Local
+        .from_local_datetime(&date.and_time(NaiveTime::MIN))
+        .earliest()
+        .map(SystemTime::from)
+}
+
+/// Traduit un motif glob (`*`, `?`, `[...]`, `[!...]`) en expression régulière ancrée.
+fn glob_to_regex(glob: &str) -> String {
+    let mut regex = String::from("^");
+    let mut in_class = false;
+    for c in glob.chars() {
+        match c {
+            '*' if !in_class => regex.push_str(".*"),
+            '?' if !in_class => regex.push('.'),
+            '[' if !in_class => {
+                in_class = true;
+                regex.push('[');
+            }
+            ']' if in_class => {
+                in_class = false;
+                regex.push(']');
+            }
+            '!' if in_class && regex.ends_with('[') => regex.push('^'),
+            '\\' | '^' | '[' if in_class => {
+                regex.push('\\');
+                regex.push(c);
+            }
+            _ if in_class => regex.push(c),
+            _ => regex.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+        }
+    }
+    if in_class {
+        // Crochet non fermé : motif pris littéralement
+        return format!("^{}$", regex::escape(glob));
+    }
+    regex.push('$');
+    regex
+}
+
+enum NameMatcher {
+    Any,
+    Substring(String),
+    Regex(Regex),
+}
+
+struct Matcher {
+    /// Un dossier peut-il être retenu pour lui-même ?
+    dirs_match: bool,
+    files_match: bool,
+    name: NameMatcher,
+    min_size: u64,
+    max_size: Option<u64>,
+    modified_from: Option<SystemTime>,
+    /// Borne exclue (lendemain du dernier jour, à minuit).
+    modified_to: Option<SystemTime>,
+    older_than: Option<(SystemTime, AgeBasis)>,
+    within: Option<PathBuf>,
+}
+
+impl Matcher {
+    /// Le nœud est-il dans le dossier ciblé ? Sinon, peut-il en contenir une
+    /// partie (ancêtre) ?
+    fn scope(&self, path: &Path) -> (bool, bool) {
+        match &self.within {
+            None => (true, true),
+            Some(within) => (path.starts_with(within), within.starts_with(path)),
+        }
+    }
+
+    fn matches(&self, node: &Node) -> bool {
+        let kind_ok = if node.is_dir {
+            self.dirs_match
+        } else {
+            self.files_match
+        };
+        if !kind_ok
+            || node.size < self.min_size
+            || self.max_size.is_some_and(|max| node.size >= max)
+        {
+            return false;
+        }
+
+        let name_ok = match &self.name {
+            NameMatcher::Any => true,
+            NameMatcher::Substring(needle) => node.name.to_lowercase().contains(needle),
+            NameMatcher::Regex(regex) => regex.is_match(&node.name),
+        };
+        if !name_ok {
+            return false;
+        }
+
+        if self.modified_from.is_some() || self.modified_to.is_some() {
+            let Some(modified) = node.modified else {
+                return false;
+            };
+            if self.modified_from.is_some_and(|from| modified < from)
+                || self.modified_to.is_some_and(|to| modified >= to)
+            {
+                return false;
+            }
+        }
+
+        if let Some((cutoff, basis)) = self.older_than {
+            match basis.time_of(node) {
+                Some(t) if t < cutoff => {}
+                _ => return false,
+            }
+        }
+        true
+    }
+}
+
+/// Résultat du filtrage : arbre élagué et éléments retenus.
+pub struct FilterOutcome {
+    /// None si rien ne correspond.
+    pub root: Option<Node>,
+    /// Eléments retenus (les plus hauts : le contenu d'un dossier retenu
+    /// n'est pas listé).
+    pub matches: Vec<PathBuf>,
+}
+
+/// Copie de l'arbre réduite aux éléments retenus par le filtre.
+/// L'ordre des enfants est conservé.
+pub fn filter_tree(root: &Node, filter: &TreeFilter) -> Result<FilterOutcome, String> {
+    let matcher = filter.compile()?;
+    let mut matches = Vec::new();
+
+    // La racine sert uniquement de conteneur
+    let children: Vec<Node> = root
+        .children
+        .iter()
+        .filter_map(|c| prune(c, &matcher, &mut matches))
+        .collect();
+    let root = (!children.is_empty()).then(|| pruned_dir(root, children));
+
+    Ok(FilterOutcome { root, matches })
+}
+
+fn prune(node: &Node, matcher: &Matcher, matches: &mut Vec<PathBuf>) -> Option<Node> {
+    let (inside, contains_scope) = matcher.scope(&node.path);
+    if inside && matcher.matches(node) {
+        matches.push(node.path.clone());
+        return Some(node.clone());
+    }
+    if !node.is_dir || !(inside || contains_scope) {
+        return None;
+    }
+
+    let children: Vec<Node> = node
+        .children
+        .iter()
+        .filter_map(|c| prune(c, matcher, matches))
+        .collect();
+    (!children.is_empty()).then(|| pruned_dir(node, children))
+}
+
+/// Copie d'un dossier avec ses seuls enfants retenus.
+fn pruned_dir(node: &Node, children: Vec<Node>) -> Node {
+    let mut dir = Node::new_dir(node.name.clone(), node.path.clone(), None, children);
+    dir.owner = node.owner;
+    dir
+}
+
+/// Remet les éléments retenus dans l'ordre d'affichage de l'arbre filtré
+/// (après un tri).
+pub fn order_matches(filtered: &Node, matches: &mut Vec<PathBuf>) {
+    let wanted: HashSet<PathBuf> = matches.drain(..).collect();
+    let mut stack: Vec<&Node> = vec![filtered];
+    while let Some(node) = stack.pop() {
+        if wanted.contains(&node.path) {
+            matches.push(node.path.clone());
+            continue;
+        }
+        stack.extend(node.children.iter().rev());
+    }
+}
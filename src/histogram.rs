@@ -0,0 +1,226 @@
+//! Histogramme des tailles de fichiers (tranches logarithmiques de 0 octet
+//! à plus de 1 To), en nombre de fichiers et en octets.
+
+use std::path::PathBuf;
+
+use eframe::egui;
+
+use crate::filter::{KindFilter, TreeFilter};
+use crate::tree_table::right_aligned;
+use crate::{find_node_by_path, format_bytes, Node};
+
+/// Première borne au-delà de 1 o ; les suivantes sont multipliées par 4.
+const FIRST_BOUND: u64 = 1024;
+const LAST_BOUND: u64 = 1 << 40;
+
+/// Bornes inférieures des tranches : 0, 1 o, 1 Ko, 4 Ko, 16 Ko… 1 To.
+fn bucket_bounds() -> Vec<u64> {
+    let mut bounds = vec![0, 1];
+    let mut bound = FIRST_BOUND;
+    while bound <= LAST_BOUND {
+        bounds.push(bound);
+        bound *= 4;
+    }
+    bounds
+}
+
+fn bucket_index(bounds: &[u64], size: u64) -> usize {
+    bounds.partition_point(|&b| b <= size) - 1
+}
+
+/// Borne lisible sans décimale (1 Ko, 256 Mo…).
+fn bound_label(bound: u64) -> String {
+    const UNITS: [&str; 5] = ["o", "Ko", "Mo", "Go", "To"];
+    let mut value = bound;
+    let mut unit = 0;
+    while value >= 1024 && unit < UNITS.len() - 1 {
+        value /= 1024;
+        unit += 1;
+    }
+    format!("{value} {}", UNITS[unit])
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Bucket {
+    files: u64,
+    bytes: u64,
+}
+
+/// Etat de la vue : portée choisie et histogramme en cache.
+#[derive(Default)]
+pub struct HistogramState {
+    /// Dossier analysé (None = racine du scan).
+    scope: Option<PathBuf>,
+    /// Portée du calcul en cache (Some(None) = racine).
+    computed_for: Option<Option<PathBuf>>,
+    bounds: Vec<u64>,
+    buckets: Vec<Bucket>,
+}
+
+impl HistogramState {
+    /// A appeler quand l'arbre change (nouveau scan).
+    pub fn invalidate(&mut self) {
+        self.scope = None;
+        self.computed_for = None;
+        self.buckets.clear();
+    }
+
+    fn refresh(&mut self, node: &Node) {
+        if self.computed_for.as_ref() == Some(&self.scope) {
+            return;
+        }
+        self.bounds = bucket_bounds();
+        self.buckets = vec![Bucket::default(); self.bounds.len()];
+
+        let mut stack: Vec<&Node> = vec![node];
+        while let Some(node) = stack.pop() {
+            if node.is_dir {
+                stack.extend(node.children.iter());
+            } else {
+                let bucket = &mut self.buckets[bucket_index(&self.bounds, node.size)];
+                bucket.files += 1;
+                bucket.bytes += node.size;
+            }
+        }
+        self.computed_for = Some(self.scope.clone());
+    }
+
+    fn range_label(&self, index: usize) -> String {
+        match (index, self.bounds.get(index + 1)) {
+            (0, _) => "0 o".to_string(),
+            (_, Some(&upper)) => format!(
+                "{} – {}",
+                bound_label(self.bounds[index]),
+                bound_label(upper)
+            ),
+            (_, None) => format!("≥ {}", bound_label(self.bounds[index])),
+        }
+    }
+}
+
+/// Dessin de l'histogramme. `selected_dir` : dossier sélectionné, proposé
+/// comme portée. Renvoie le filtre "fichiers de cette tranche" si
+/// l'utilisateur clique sur une tranche.
+pub fn draw_histogram(
+    ui: &mut egui::Ui,
+    root: &Node,
+    selected_dir: Option<&Node>,
+    state: &mut HistogramState,
+) -> Option<TreeFilter> {
+    ui.horizontal(|ui| {
+        ui.label("Portée :");
+        if ui
+            .selectable_label(state.scope.is_none(), "Racine du scan")
+            .clicked()
+        {
+            state.scope = None;
+        }
+        match selected_dir {
+            Some(dir) if dir.path != root.path => {
+                let is_current = state.scope.as_ref() == Some(&dir.path);
+                if ui
+                    .selectable_label(is_current, format!("📁 {}", dir.name))
+                    .on_hover_text("Dossier sélectionné")
+                    .clicked()
+                {
+                    state.scope = Some(dir.path.clone());
+                }
+            }
+            _ => {
+                ui.weak("(sélectionner un dossier pour l'analyser seul)");
+            }
+        }
+    });
+
+    let scoped = state
+        .scope
+        .as_ref()
+        .and_then(|p| find_node_by_path(root, p));
+    if scoped.is_none() {
+        state.scope = None;
+    }
+    let node = scoped.unwrap_or(root);
+    state.refresh(node);
+
+    ui.monospace(node.path.to_string_lossy());
+    ui.small(format!(
+        "{} fichiers, {}. Clic sur une tranche : n'afficher que ces fichiers dans l'arborescence.",
+        node.file_count,
+        format_bytes(node.size)
+    ));
+    ui.add_space(6.0);
+
+    let max_files = state
+        .buckets
+        .iter()
+        .map(|b| b.files)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let max_bytes = state
+        .buckets
+        .iter()
+        .map(|b| b.bytes)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let total_files = node.file_count.max(1);
+    let total_bytes = node.size.max(1);
+    let mut clicked: Option<usize> = None;
+
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        egui::Grid::new("size_histogram")
+            .num_columns(5)
+            .striped(true)
+            .spacing([12.0, 4.0])
+            .show(ui, |ui| {
+                ui.strong("Tranche");
+                ui.strong("Fichiers");
+                ui.strong("");
+                ui.strong("Taille");
+                ui.strong("");
+                ui.end_row();
+
+                for (index, bucket) in state.buckets.iter().enumerate() {
+                    if ui
+                        .add_enabled(
+                            bucket.files > 0,
+                            egui::SelectableLabel::new(false, state.range_label(index)),
+                        )
+                        .on_hover_text("Filtrer l'arborescence sur cette tranche")
+                        .clicked()
+                    {
+                        clicked = Some(index);
+                    }
+                    right_aligned(ui, bucket.files.to_string());
+                    ui.add(
+                        egui::ProgressBar::new(bucket.files as f32 / max_files as f32)
+                            .desired_width(180.0)
+                            .fill(egui::Color32::from_rgb(220, 140, 60))
+                            .text(format!(
+                                "{:.1}%",
+                                bucket.files as f64 / total_files as f64 * 100.0
+                            )),
+                    );
+                    right_aligned(ui, format_bytes(bucket.bytes));
+                    ui.add(
+                        egui::ProgressBar::new(bucket.bytes as f32 / max_bytes as f32)
+                            .desired_width(180.0)
+                            .text(format!(
+                                "{:.1}%",
+                                bucket.bytes as f64 / total_bytes as f64 * 100.0
+                            )),
+                    );
+                    ui.end_row();
+                }
+            });
+    });
+
+    clicked.map(|index| TreeFilter {
+        kind: KindFilter::Files,
+        min_size: state.bounds[index],
+        max_size: state.bounds.get(index + 1).copied(),
+        within: state.scope.clone(),
+        ..TreeFilter::default()
+    })
+}
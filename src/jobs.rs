@@ -0,0 +1,1446 @@
+//! File d'attente des opérations sur les fichiers (copie, déplacement,
+//! corbeille, suppression, compression, annulation d'une opération du
+//! journal) exécutées
+//! une par une dans un thread dédié, avec progression, débit, temps restant,
+//! pause et annulation.
+
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crossbeam_channel::{bounded, unbounded, Receiver, RecvTimeoutError, Sender};
+use eframe::egui;
+
+use crate::archive;
+use crate::audit::{self, AuditOp};
+use crate::conflicts::{
+    draw_conflict_window, find_conflicts, is_same_item, unique_name, Conflict, ConflictPrompt,
+    PromptAction, Resolution,
+};
+use crate::duplicates::{hash_file, replace_with_hard_link, still_duplicate};
+use crate::journal::{undo_item, JournalEntry, JournalItem, OperationKind};
+use crate::{delete_path, format_bytes, preserve, protect, trash};
+
+/// Taille des blocs copiés entre deux vérifications pause/annulation.
+const COPY_BUFFER: usize = 1024 * 1024;
+const PAUSE_POLL: Duration = Duration::from_millis(100);
+/// Avertissements conservés par opération (les suivants sont seulement comptés).
+const MAX_WARNINGS: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobKind {
+    Copy,
+    Move,
+    Trash,
+    Delete,
+    /// Remplacement de copies d'un doublon par des liens physiques vers la
+    /// copie conservée.
+    HardLink,
+    /// Création et vérification d'une archive (`dest` : nom demandé, un nom
+    /// libre est choisi au lancement).
+    Compress,
+    /// Annulation d'une opération du journal.
+    Undo,
+}
+
+impl JobKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            JobKind::Copy => "Copie",
+            JobKind::Move => "Déplacement",
+            JobKind::Trash => "Mise à la corbeille",
+            JobKind::Delete => "Suppression définitive",
+            JobKind::HardLink => "Remplacement par des liens physiques",
+            JobKind::Compress => "Compression",
+            JobKind::Undo => "Annulation",
+        }
+    }
+
+    /// Participe passé pour les messages ("3 éléments copiés").
+    fn done_label(self) -> &'static str {
+        match self {
+            JobKind::Copy => "copiés",
+            JobKind::Move => "déplacés",
+            JobKind::Trash => "mis à la corbeille",
+            JobKind::Delete => "supprimés",
+            JobKind::HardLink => "remplacés par un lien physique",
+            JobKind::Compress => "compressés",
+            JobKind::Undo => "rétablis",
+        }
+    }
+
+    /// Type d'opération enregistré dans le journal (None : non annulable).
+    pub fn journal_kind(self) -> Option<OperationKind> {
+        match self {
+            JobKind::Copy => Some(OperationKind::Copy),
+            JobKind::Move => Some(OperationKind::Move),
+            JobKind::Trash => Some(OperationKind::Trash),
+            JobKind::Delete | JobKind::HardLink | JobKind::Compress | JobKind::Undo => None,
+        }
+    }
+}
+
+/// Progression partagée entre le thread de travail et l'interface.
+#[derive(Default)]
+pub struct JobProgress {
+    pub bytes_total: AtomicU64,
+    pub bytes_done: AtomicU64,
+    pub files_total: AtomicU64,
+    pub files_done: AtomicU64,
+    current: Mutex<PathBuf>,
+    paused: AtomicBool,
+    cancel: AtomicBool,
+    /// Début effectif du travail (après la file d'attente).
+    started: OnceLock<Instant>,
+    /// Temps passé en pause, exclu du calcul du débit.
+    paused_ms: AtomicU64,
+    /// Déplacement entre systèmes de fichiers : relecture de la copie.
+    verifying: AtomicBool,
+}
+
+impl JobProgress {
+    fn set_current(&self, path: &Path) {
+        if let Ok(mut current) = self.current.lock() {
+            current.clear();
+            current.push(path);
+        }
+    }
+
+    fn current(&self) -> PathBuf {
+        self.current.lock().map(|c| c.clone()).unwrap_or_default()
+    }
+
+    /// Attend tant que l'opération est en pause ; erreur si elle est annulée.
+    fn checkpoint(&self) -> io::Result<()> {
+        while self.paused.load(Ordering::Relaxed) && !self.cancel.load(Ordering::Relaxed) {
+            thread::sleep(PAUSE_POLL);
+            self.paused_ms
+                .fetch_add(PAUSE_POLL.as_millis() as u64, Ordering::Relaxed);
+        }
+        if self.cancel.load(Ordering::Relaxed) {
+            return Err(io::Error::new(
+                io::ErrorKind::Interrupted,
+                "Opération annulée",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Temps de travail effectif (pauses exclues).
+    fn active_time(&self) -> Option<Duration> {
+        let started = self.started.get()?;
+        let paused = Duration::from_millis(self.paused_ms.load(Ordering::Relaxed));
+        Some(started.elapsed().saturating_sub(paused))
+    }
+}
+
+/// Bilan d'une opération terminée.
+#[derive(Debug, Clone, Default)]
+pub struct JobOutcome {
+    /// Eléments sources traités avec succès.
+    pub done: usize,
+    /// Conflits résolus par "Ignorer".
+    pub skipped: usize,
+    pub errors: Vec<String>,
+    /// Ce qui n'a pas pu être reproduit à l'identique (propriétaire,
+    /// attributs, sockets…), limité à `MAX_WARNINGS` éléments.
+    pub warnings: Vec<String>,
+    pub warning_count: usize,
+    pub cancelled: bool,
+    /// Eléments placés (copiés, déplacés, mis à la corbeille), pour le journal.
+    pub items: Vec<JournalItem>,
+    /// Annulation : éléments non rétablis (erreur ou arrêt), gardés au journal.
+    pub not_undone: Vec<JournalItem>,
+    /// Archive vérifiée (chemin retenu, tailles, éléments non archivés).
+    pub archived: Option<archive::Verified>,
+}
+
+#[derive(Debug, Clone)]
+enum JobStatus {
+    Pending,
+    Running,
+    Finished(JobOutcome),
+}
+
+/// Opération terminée, renvoyée par [`JobQueue::poll`].
+#[derive(Debug, Clone)]
+pub struct JobReport {
+    pub kind: JobKind,
+    pub sources: Vec<PathBuf>,
+    pub dest: Option<PathBuf>,
+    pub outcome: JobOutcome,
+    /// Opération du journal annulée par ce travail.
+    pub undo_of: Option<u64>,
+}
+
+impl JobReport {
+    /// Message de statut résumant l'opération.
+    pub fn summary(&self) -> String {
+        let total = self.sources.len();
+        let done = self.outcome.done;
+        let error_label = match self.kind {
+            JobKind::Copy | JobKind::Move => "Erreur copie/déplacement",
+            JobKind::Trash | JobKind::Delete => "Erreur suppression",
+            JobKind::HardLink => "Erreur lien physique",
+            JobKind::Compress => "Erreur compression",
+            JobKind::Undo => "Erreur annulation",
+        };
+        if self.outcome.cancelled {
+            return format!(
+                "{} annulée ({done} éléments traités sur {total}).",
+                self.kind.label()
+            );
+        }
+        let summary = match (self.outcome.errors.last(), total) {
+            (None, 1) => match (self.kind, &self.dest) {
+                (JobKind::Copy, Some(dest)) => format!("Copié vers : {}", dest.to_string_lossy()),
+                (JobKind::Move, Some(dest)) => {
+                    format!("Déplacé vers : {}", dest.to_string_lossy())
+                }
+                (JobKind::Trash, _) => {
+                    format!("Mis à la corbeille : {}", self.sources[0].to_string_lossy())
+                }
+                (JobKind::Compress, Some(dest)) => match &self.outcome.archived {
+                    Some(archived) => format!(
+                        "Archive vérifiée : {} — {}",
+                        archived.path.to_string_lossy(),
+                        archive::ratio_label(archived.original_size, archived.archive_size)
+                    ),
+                    None => format!("Archive créée : {}", dest.to_string_lossy()),
+                },
+                (JobKind::Undo, _) => {
+                    format!("Opération annulée : {}", self.sources[0].to_string_lossy())
+                }
+                _ => format!("Supprimé : {}", self.sources[0].to_string_lossy()),
+            },
+            (None, _) => match &self.dest {
+                Some(dest) => format!(
+                    "{done} éléments {} vers : {}",
+                    self.kind.done_label(),
+                    dest.to_string_lossy()
+                ),
+                None => format!("{done} éléments {}.", self.kind.done_label()),
+            },
+            (Some(e), 1) => format!("{error_label} : {e}"),
+            (Some(e), _) => format!(
+                "{done} éléments {} sur {total}. {error_label} : {e}",
+                self.kind.done_label()
+            ),
+        };
+        let summary = match self.outcome.skipped {
+            0 => summary,
+            skipped => format!("{summary} ({skipped} conflits ignorés)"),
+        };
+        match self.outcome.warning_count {
+            0 => summary,
+            count => {
+                format!("{summary} — {count} éléments reproduits partiellement (voir Opérations)")
+            }
+        }
+    }
+}
+
+struct Job {
+    id: u64,
+    kind: JobKind,
+    sources: Vec<PathBuf>,
+    dest: Option<PathBuf>,
+    progress: Arc<JobProgress>,
+    status: JobStatus,
+    /// Conflits en attente de décision de l'utilisateur.
+    prompt: Option<ConflictPrompt>,
+    decisions: Sender<HashMap<PathBuf, Resolution>>,
+    undo_of: Option<u64>,
+}
+
+/// Travail transmis au thread d'exécution.
+struct WorkItem {
+    id: u64,
+    kind: JobKind,
+    sources: Vec<PathBuf>,
+    dest: Option<PathBuf>,
+    progress: Arc<JobProgress>,
+    decisions: Receiver<HashMap<PathBuf, Resolution>>,
+    /// Opération du journal à défaire (`JobKind::Undo`).
+    undo: Option<(OperationKind, Vec<JournalItem>)>,
+    /// Copies de doublons : copie conservée et taille, revérifiées juste
+    /// avant chaque élément.
+    duplicate_of: Option<(PathBuf, u64)>,
+}
+
+/// Messages du thread de travail vers l'interface.
+enum JobEvent {
+    /// Collage en attente : conflits à résoudre avant toute écriture.
+    Conflicts(u64, Vec<Conflict>),
+    Finished(u64, JobOutcome),
+}
+
+/// File des opérations : un seul thread les exécute dans l'ordre d'ajout.
+#[derive(Default)]
+pub struct JobQueue {
+    jobs: Vec<Job>,
+    next_id: u64,
+    sender: Option<Sender<WorkItem>>,
+    receiver: Option<Receiver<JobEvent>>,
+}
+
+impl JobQueue {
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+
+    /// Une opération est-elle en attente ou en cours ?
+    pub fn is_busy(&self) -> bool {
+        self.jobs
+            .iter()
+            .any(|j| !matches!(j.status, JobStatus::Finished(_)))
+    }
+
+    /// Une annulation est-elle en attente ou en cours ?
+    pub fn is_undoing(&self) -> bool {
+        self.jobs
+            .iter()
+            .any(|j| j.kind == JobKind::Undo && !matches!(j.status, JobStatus::Finished(_)))
+    }
+
+    /// Ajoute une opération à la file. `dest` : dossier de destination
+    /// (copie et déplacement).
+    pub fn enqueue(&mut self, kind: JobKind, sources: Vec<PathBuf>, dest: Option<PathBuf>) {
+        self.push(kind, sources, dest, None, None);
+    }
+
+    /// Ajoute la mise à la corbeille, la suppression ou le remplacement par
+    /// des liens physiques de copies de `keep` ; une copie qui n'est plus
+    /// identique à `keep` est ignorée.
+    pub fn enqueue_duplicates(
+        &mut self,
+        kind: JobKind,
+        copies: Vec<PathBuf>,
+        keep: PathBuf,
+        size: u64,
+    ) {
+        self.push(kind, copies, None, None, Some((keep, size)));
+    }
+
+    /// Ajoute l'annulation d'une opération du journal (éléments traités du
+    /// dernier au premier).
+    pub fn enqueue_undo(&mut self, entry: &JournalEntry) {
+        let sources = entry.items.iter().map(|i| i.to.clone()).collect();
+        self.push(
+            JobKind::Undo,
+            sources,
+            None,
+            Some((entry.id, entry.kind, entry.items.clone())),
+            None,
+        );
+    }
+
+    fn push(
+        &mut self,
+        kind: JobKind,
+        sources: Vec<PathBuf>,
+        dest: Option<PathBuf>,
+        undo: Option<(u64, OperationKind, Vec<JournalItem>)>,
+        duplicate_of: Option<(PathBuf, u64)>,
+    ) {
+        let sender = self.sender.get_or_insert_with(|| {
+            let (work_tx, work_rx) = unbounded::<WorkItem>();
+            let (done_tx, done_rx) = unbounded();
+            self.receiver = Some(done_rx);
+            thread::spawn(move || {
+                for item in work_rx {
+                    let outcome = run_job(&item, &done_tx);
+                    if done_tx.send(JobEvent::Finished(item.id, outcome)).is_err() {
+                        break;
+                    }
+                }
+            });
+            work_tx
+        });
+
+        let id = self.next_id;
+        self.next_id += 1;
+        let progress = Arc::new(JobProgress::default());
+        let (decisions_tx, decisions_rx) = bounded(1);
+        let _ = sender.send(WorkItem {
+            id,
+            kind,
+            sources: sources.clone(),
+            dest: dest.clone(),
+            progress: progress.clone(),
+            decisions: decisions_rx,
+            undo: undo.as_ref().map(|(_, kind, items)| (*kind, items.clone())),
+            duplicate_of,
+        });
+        self.jobs.push(Job {
+            id,
+            kind,
+            sources,
+            dest,
+            progress,
+            status: JobStatus::Pending,
+            prompt: None,
+            decisions: decisions_tx,
+            undo_of: undo.map(|(id, ..)| id),
+        });
+    }
+
+    /// Met à jour les états et renvoie les opérations terminées depuis
+    /// le dernier appel (à appeler à chaque frame).
+    pub fn poll(&mut self) -> Vec<JobReport> {
+        let mut reports = Vec::new();
+        if let Some(rx) = &self.receiver {
+            while let Ok(event) = rx.try_recv() {
+                match event {
+                    JobEvent::Conflicts(id, conflicts) => {
+                        if let Some(job) = self.jobs.iter_mut().find(|j| j.id == id) {
+                            job.prompt = Some(ConflictPrompt::new(conflicts));
+                        }
+                    }
+                    JobEvent::Finished(id, outcome) => {
+                        if let Some(job) = self.jobs.iter_mut().find(|j| j.id == id) {
+                            job.status = JobStatus::Finished(outcome.clone());
+                            job.prompt = None;
+                            reports.push(JobReport {
+                                kind: job.kind,
+                                sources: job.sources.clone(),
+                                dest: job.dest.clone(),
+                                outcome,
+                                undo_of: job.undo_of,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        for job in &mut self.jobs {
+            if matches!(job.status, JobStatus::Pending) && job.progress.started.get().is_some() {
+                job.status = JobStatus::Running;
+            }
+        }
+        reports
+    }
+
+    /// Fenêtre de résolution des conflits de la première opération qui en
+    /// attend une.
+    pub fn draw_conflicts(&mut self, ctx: &egui::Context) {
+        let Some(job) = self.jobs.iter_mut().find(|j| j.prompt.is_some()) else {
+            return;
+        };
+        let Some(prompt) = &mut job.prompt else {
+            return;
+        };
+        match draw_conflict_window(ctx, job.kind.label(), prompt) {
+            PromptAction::None => {}
+            PromptAction::Decided(plan) => {
+                let _ = job.decisions.send(plan);
+                job.prompt = None;
+            }
+            PromptAction::CancelJob => {
+                job.progress.cancel.store(true, Ordering::Relaxed);
+                job.prompt = None;
+            }
+        }
+    }
+
+    fn clear_finished(&mut self) {
+        self.jobs
+            .retain(|j| !matches!(j.status, JobStatus::Finished(_)));
+    }
+}
+
+/// Exécute une opération complète dans le thread de travail.
+fn run_job(item: &WorkItem, events: &Sender<JobEvent>) -> JobOutcome {
+    let progress = &item.progress;
+    let _ = progress.started.set(Instant::now());
+    let mut outcome = JobOutcome::default();
+
+    if let Some((kind, items)) = &item.undo {
+        run_undo(*kind, items, progress, &mut outcome);
+        return outcome;
+    }
+
+    // Volume total : nombre d'éléments pour la corbeille (simple renommage),
+    // fichiers et octets sinon
+    if item.kind == JobKind::Trash {
+        progress
+            .files_total
+            .store(item.sources.len() as u64, Ordering::Relaxed);
+    } else {
+        let sizes: Vec<(u64, u64)> = item.sources.iter().map(|s| measure(s)).collect();
+        progress
+            .files_total
+            .store(sizes.iter().map(|s| s.0).sum(), Ordering::Relaxed);
+        progress
+            .bytes_total
+            .store(sizes.iter().map(|s| s.1).sum(), Ordering::Relaxed);
+    }
+
+    // Collage : conflits recensés et résolus avant toute écriture
+    let mut plan = HashMap::new();
+    if let (JobKind::Copy | JobKind::Move, Some(dest_dir)) = (item.kind, &item.dest) {
+        let mut conflicts = Vec::new();
+        for src in &item.sources {
+            if let Some(name) = src.file_name() {
+                if !dest_dir.starts_with(src) {
+                    find_conflicts(src, &dest_dir.join(name), &mut conflicts);
+                }
+            }
+        }
+        if !conflicts.is_empty() {
+            if events
+                .send(JobEvent::Conflicts(item.id, conflicts))
+                .is_err()
+            {
+                outcome.cancelled = true;
+                return outcome;
+            }
+            match wait_for_decisions(item) {
+                Some(decisions) => plan = decisions,
+                None => {
+                    outcome.cancelled = true;
+                    return outcome;
+                }
+            }
+        }
+    }
+
+    for src in &item.sources {
+        if progress.checkpoint().is_err() {
+            outcome.cancelled = true;
+            break;
+        }
+        progress.set_current(src);
+        let bytes_before = progress.bytes_done.load(Ordering::Relaxed);
+        let bytes_since = || {
+            progress
+                .bytes_done
+                .load(Ordering::Relaxed)
+                .saturating_sub(bytes_before)
+        };
+
+        let result = match (item.kind, &item.dest) {
+            (JobKind::Copy | JobKind::Move, Some(dest)) => {
+                let mut transfer = Transfer {
+                    is_cut: item.kind == JobKind::Move,
+                    plan: &plan,
+                    progress,
+                    skipped: 0,
+                    warnings: Vec::new(),
+                    warning_count: 0,
+                    copied: None,
+                    placing: 0,
+                    placed: Vec::new(),
+                };
+                let result = transfer.paste(src, dest);
+                if transfer.is_cut {
+                    // Nom choisi à l'arrivée ("Garder les deux") ou dossier fusionné
+                    let moved_to = transfer
+                        .placed
+                        .first()
+                        .map(|i| i.to.clone())
+                        .or_else(|| src.file_name().map(|name| dest.join(name)));
+                    audit::record(
+                        AuditOp::Move,
+                        src,
+                        moved_to.as_deref(),
+                        bytes_since(),
+                        &result,
+                    );
+                }
+                outcome.items.append(&mut transfer.placed);
+                outcome.skipped += transfer.skipped;
+                outcome.warning_count += transfer.warning_count;
+                let room = MAX_WARNINGS.saturating_sub(outcome.warnings.len());
+                outcome
+                    .warnings
+                    .extend(transfer.warnings.into_iter().take(room));
+                result
+            }
+            (JobKind::Trash, _) => {
+                let bytes = measure(src).1;
+                let trashed = protect::check(src)
+                    .and_then(|()| still_same(item, src))
+                    .and_then(|()| trash::move_to_trash(src));
+                audit::record(
+                    AuditOp::Trash,
+                    src,
+                    trashed.as_deref().ok(),
+                    bytes,
+                    &trashed,
+                );
+                trashed.map(|trashed| {
+                    progress.files_done.fetch_add(1, Ordering::Relaxed);
+                    outcome.items.push(JournalItem {
+                        from: src.clone(),
+                        to: trashed,
+                        replaced: false,
+                    });
+                })
+            }
+            (JobKind::Delete, _) => {
+                let result = protect::check(src)
+                    .and_then(|()| still_same(item, src))
+                    .and_then(|()| remove_tree(src, progress));
+                audit::record(AuditOp::Delete, src, None, bytes_since(), &result);
+                result
+            }
+            (JobKind::HardLink, _) => match &item.duplicate_of {
+                Some((keep, size)) => {
+                    let linked = protect::check(src)
+                        .and_then(|()| still_same(item, src))
+                        .and_then(|()| replace_with_hard_link(keep, src));
+                    audit::record(AuditOp::Overwrite, keep, Some(src), *size, &linked);
+                    linked.map(|()| {
+                        progress.files_done.fetch_add(1, Ordering::Relaxed);
+                        progress.bytes_done.fetch_add(*size, Ordering::Relaxed);
+                    })
+                }
+                None => Err(invalid("Copie conservée inconnue".to_string())),
+            },
+            (JobKind::Compress, Some(dest)) => compress(src, dest, progress, &mut outcome),
+            (JobKind::Undo, _) | (_, None) => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Destination manquante",
+            )),
+        };
+
+        match result {
+            Ok(()) => outcome.done += 1,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {
+                outcome.cancelled = true;
+                break;
+            }
+            Err(e) => outcome
+                .errors
+                .push(format!("{} : {}", src.to_string_lossy(), e)),
+        }
+    }
+    outcome
+}
+
+/// Copie de doublon toujours identique à la copie conservée (toujours vrai
+/// hors suppression de doublons).
+fn still_same(item: &WorkItem, src: &Path) -> io::Result<()> {
+    match &item.duplicate_of {
+        Some((keep, size)) => still_duplicate(keep, src, *size),
+        None => Ok(()),
+    }
+}
+
+/// Défait une opération du journal, du dernier élément au premier.
+fn run_undo(
+    kind: OperationKind,
+    items: &[JournalItem],
+    progress: &JobProgress,
+    outcome: &mut JobOutcome,
+) {
+    progress
+        .files_total
+        .store(items.len() as u64, Ordering::Relaxed);
+    let plan = HashMap::new();
+    let mut failed = Vec::new();
+    // Eléments pas encore traités en cas d'arrêt (du premier à `stopped`)
+    let mut stopped = None;
+    for (index, item) in items.iter().enumerate().rev() {
+        if progress.checkpoint().is_err() {
+            outcome.cancelled = true;
+            stopped = Some(index);
+            break;
+        }
+        progress.set_current(&item.to);
+        let bytes = measure(&item.to).1;
+
+        // Retour à l'emplacement d'origine, vérifié entre systèmes de fichiers
+        let result = undo_item(kind, item, |from, to| {
+            match preserve::rename_no_replace(from, to) {
+                Ok(()) => return Ok(()),
+                // Occupé depuis la vérification : jamais remplacé
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                    return Err(io::Error::new(
+                        e.kind(),
+                        format!("emplacement d'origine occupé : {}", to.to_string_lossy()),
+                    ))
+                }
+                Err(_) => {}
+            }
+            let mut transfer = Transfer {
+                is_cut: true,
+                plan: &plan,
+                progress,
+                skipped: 0,
+                warnings: Vec::new(),
+                warning_count: 0,
+                copied: None,
+                placing: 0,
+                placed: Vec::new(),
+            };
+            let result = transfer.move_by_copy(from, to, &fs::symlink_metadata(from)?);
+            outcome.warning_count += transfer.warning_count;
+            let room = MAX_WARNINGS.saturating_sub(outcome.warnings.len());
+            outcome
+                .warnings
+                .extend(transfer.warnings.into_iter().take(room));
+            result
+        });
+        progress.files_done.fetch_add(1, Ordering::Relaxed);
+        match kind {
+            OperationKind::Move => {
+                audit::record(AuditOp::Move, &item.to, Some(&item.from), bytes, &result)
+            }
+            // Copie annulée : mise à la corbeille (ou suppression) de la copie
+            OperationKind::Copy if trash::SUPPORTED => {
+                audit::record(AuditOp::Trash, &item.to, None, bytes, &result)
+            }
+            OperationKind::Copy => audit::record(AuditOp::Delete, &item.to, None, bytes, &result),
+            OperationKind::Trash => {}
+        }
+
+        match result {
+            Ok(()) => outcome.done += 1,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {
+                outcome.cancelled = true;
+                stopped = Some(index);
+                break;
+            }
+            Err(e) => {
+                outcome
+                    .errors
+                    .push(format!("{} : {}", item.from.to_string_lossy(), e));
+                failed.push(item.clone());
+            }
+        }
+    }
+
+    // Dans l'ordre d'origine : non traités, puis échecs
+    if let Some(index) = stopped {
+        outcome.not_undone.extend_from_slice(&items[..=index]);
+    }
+    outcome.not_undone.extend(failed.into_iter().rev());
+}
+
+impl archive::Progress for JobProgress {
+    fn entry(&self, path: &Path) -> io::Result<()> {
+        self.checkpoint()?;
+        self.set_current(path);
+        Ok(())
+    }
+
+    fn bytes(&self, count: u64) -> io::Result<()> {
+        self.bytes_done.fetch_add(count, Ordering::Relaxed);
+        self.checkpoint()
+    }
+}
+
+/// Crée l'archive sous un nom libre puis la relit entièrement ; une archive
+/// incomplète ou non conforme est supprimée (jamais une archive existante).
+fn compress(
+    src: &Path,
+    dest: &Path,
+    progress: &JobProgress,
+    outcome: &mut JobOutcome,
+) -> io::Result<()> {
+    let created = archive::create(src, dest, progress)?;
+    outcome.warning_count += created.warnings.len();
+    let room = MAX_WARNINGS.saturating_sub(outcome.warnings.len());
+    outcome
+        .warnings
+        .extend(created.warnings.iter().take(room).cloned());
+    progress.files_done.store(
+        progress.files_total.load(Ordering::Relaxed),
+        Ordering::Relaxed,
+    );
+
+    // Relecture : la progression repart de zéro
+    progress.verifying.store(true, Ordering::Relaxed);
+    progress.bytes_done.store(0, Ordering::Relaxed);
+    let verified = archive::verify(src, &created.path, &created.names, progress)
+        .and_then(|()| fs::metadata(&created.path));
+    progress.verifying.store(false, Ordering::Relaxed);
+    match verified {
+        Ok(meta) => {
+            outcome.archived = Some(archive::Verified {
+                path: created.path,
+                original_size: progress.bytes_total.load(Ordering::Relaxed),
+                archive_size: meta.len(),
+                skipped: created.warnings,
+            });
+            Ok(())
+        }
+        Err(e) => {
+            let _ = fs::remove_file(&created.path);
+            Err(e)
+        }
+    }
+}
+
+/// Nombre de fichiers et octets sous `path` (sans suivre les liens).
+pub fn measure(path: &Path) -> (u64, u64) {
+    let Ok(meta) = fs::symlink_metadata(path) else {
+        return (0, 0);
+    };
+    if !meta.is_dir() {
+        return (1, meta.len());
+    }
+    let mut total = (0, 0);
+    if let Ok(entries) = fs::read_dir(path) {
+        for entry in entries.flatten() {
+            let (files, bytes) = measure(&entry.path());
+            total.0 += files;
+            total.1 += bytes;
+        }
+    }
+    total
+}
+
+#[cfg(target_os = "windows")]
+fn is_path_too_long(path: &Path) -> bool {
+    // Windows classique ~260 caractères (MAX_PATH)
+    path.to_string_lossy().len() > 260
+}
+
+#[cfg(not(target_os = "windows"))]
+fn is_path_too_long(_path: &Path) -> bool {
+    false
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+/// Attend les choix de l'utilisateur sur les conflits (temps exclu du
+/// débit comme une pause). None si l'opération est annulée.
+fn wait_for_decisions(item: &WorkItem) -> Option<HashMap<PathBuf, Resolution>> {
+    loop {
+        match item.decisions.recv_timeout(PAUSE_POLL) {
+            Ok(plan) => return Some(plan),
+            Err(RecvTimeoutError::Timeout) => {
+                if item.progress.cancel.load(Ordering::Relaxed) {
+                    return None;
+                }
+                item.progress
+                    .paused_ms
+                    .fetch_add(PAUSE_POLL.as_millis() as u64, Ordering::Relaxed);
+            }
+            Err(RecvTimeoutError::Disconnected) => return None,
+        }
+    }
+}
+
+/// Collage (copie ou déplacement) suivant les résolutions de conflits,
+/// indexées par chemin de destination.
+struct Transfer<'a> {
+    is_cut: bool,
+    plan: &'a HashMap<PathBuf, Resolution>,
+    progress: &'a JobProgress,
+    skipped: usize,
+    warnings: Vec<String>,
+    warning_count: usize,
+    /// Eléments créés, dans l'ordre (relevés pour vérifier un déplacement).
+    copied: Option<Vec<CopiedItem>>,
+    /// Profondeur dans un élément en cours de placement : seuls les éléments
+    /// placés au premier niveau (hors fusion de dossiers) sont journalisés.
+    placing: usize,
+    /// Eléments placés, pour le journal des opérations.
+    placed: Vec<JournalItem>,
+}
+
+/// Elément copié : source, destination et type.
+struct CopiedItem {
+    src: PathBuf,
+    dest: PathBuf,
+    file_type: fs::FileType,
+}
+
+impl Transfer<'_> {
+    fn warn(&mut self, warning: String) {
+        self.warning_count += 1;
+        if self.warnings.len() < MAX_WARNINGS {
+            self.warnings.push(warning);
+        }
+    }
+
+    /// Colle `src` dans le dossier `dest_dir`.
+    fn paste(&mut self, src: &Path, dest_dir: &Path) -> io::Result<()> {
+        if !dest_dir.is_dir() {
+            return Err(invalid(format!(
+                "Destination invalide : {}",
+                dest_dir.to_string_lossy()
+            )));
+        }
+        if fs::symlink_metadata(src).is_err() {
+            return Err(invalid(format!(
+                "Source introuvable : {}",
+                src.to_string_lossy()
+            )));
+        }
+        if self.is_cut {
+            protect::check(src)?;
+        }
+        if dest_dir.starts_with(src) {
+            return Err(invalid(
+                "Impossible de copier ou déplacer un dossier dans lui-même ou un sous-dossier."
+                    .to_string(),
+            ));
+        }
+
+        let file_name = src.file_name().unwrap_or_else(|| OsStr::new("unnamed"));
+        let dest_path = dest_dir.join(file_name);
+
+        if is_path_too_long(&dest_path) {
+            return Err(invalid(format!(
+                "Chemin de destination trop long pour le système ({} caractères).\n{}",
+                dest_path.to_string_lossy().len(),
+                dest_path.to_string_lossy()
+            )));
+        }
+
+        self.transfer(src, &dest_path)
+    }
+
+    /// Copie ou déplace `src` vers `dest` ; deux dossiers de même nom sont
+    /// fusionnés.
+    fn transfer(&mut self, src: &Path, dest: &Path) -> io::Result<()> {
+        self.progress.checkpoint()?;
+        let src_meta = fs::symlink_metadata(src)?;
+
+        // Jamais de suppression de la source collée sur elle-même
+        let resolution = match self.plan.get(dest).copied() {
+            Some(Resolution::Overwrite) if is_same_item(src, dest) => Some(Resolution::Skip),
+            resolution => resolution,
+        };
+        // Taille de l'élément écrasé, pour le journal d'audit
+        let mut overwritten = None;
+        let dest = match resolution {
+            Some(Resolution::Skip) => {
+                let (files, bytes) = measure(src);
+                self.progress.files_done.fetch_add(files, Ordering::Relaxed);
+                self.progress.bytes_done.fetch_add(bytes, Ordering::Relaxed);
+                self.skipped += 1;
+                return Ok(());
+            }
+            Some(Resolution::KeepBoth) => unique_name(dest, src_meta.is_dir()),
+            Some(Resolution::Overwrite) => {
+                let bytes = measure(dest).1;
+                overwritten = Some(bytes);
+                // Un fichier ordinaire écrase un fichier ordinaire ; tout autre
+                // élément existant (dossier, lien, fichier spécial) est d'abord
+                // mis à la corbeille, ou supprimé si elle n'est pas disponible
+                if let Ok(existing) = fs::symlink_metadata(dest) {
+                    if !(existing.is_file() && src_meta.is_file()) {
+                        let removed = if trash::SUPPORTED {
+                            protect::check(dest)
+                                .and_then(|()| trash::move_to_trash(dest))
+                                .map(|_| ())
+                        } else {
+                            delete_path(dest)
+                        };
+                        if removed.is_err() {
+                            audit::record(AuditOp::Overwrite, src, Some(dest), bytes, &removed);
+                        }
+                        removed?;
+                    }
+                }
+                dest.to_path_buf()
+            }
+            None => dest.to_path_buf(),
+        };
+
+        let existing = fs::symlink_metadata(&dest).ok();
+        let merge = src_meta.is_dir() && existing.as_ref().is_some_and(|m| m.is_dir());
+        if existing.is_some() && !merge && resolution != Some(Resolution::Overwrite) {
+            // Apparu depuis la détection des conflits : jamais écrasé sans accord
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("Existe déjà : {}", dest.to_string_lossy()),
+            ));
+        }
+
+        let journaled = !merge && self.placing == 0;
+        self.placing += usize::from(journaled);
+        let result = self.place(src, &dest, &src_meta, merge);
+        self.placing -= usize::from(journaled);
+        if let Some(bytes) = overwritten {
+            audit::record(AuditOp::Overwrite, src, Some(&dest), bytes, &result);
+        }
+        if journaled && result.is_ok() {
+            self.placed.push(JournalItem {
+                from: src.to_path_buf(),
+                to: dest,
+                replaced: resolution == Some(Resolution::Overwrite),
+            });
+        }
+        result
+    }
+
+    /// Copie ou déplace `src` vers `dest`, libre ou dossier à fusionner.
+    fn place(
+        &mut self,
+        src: &Path,
+        dest: &Path,
+        src_meta: &fs::Metadata,
+        merge: bool,
+    ) -> io::Result<()> {
+        // Même système de fichiers : simple renommage (sauf fusion de dossiers)
+        if self.is_cut && !merge {
+            let (files, bytes) = if src_meta.is_dir() {
+                measure(src)
+            } else {
+                (1, src_meta.len())
+            };
+            if fs::rename(src, dest).is_ok() {
+                self.progress.files_done.fetch_add(files, Ordering::Relaxed);
+                self.progress.bytes_done.fetch_add(bytes, Ordering::Relaxed);
+                return Ok(());
+            }
+            return self.move_by_copy(src, dest, src_meta);
+        }
+
+        self.copy(src, dest, src_meta, merge)
+    }
+
+    /// Copie (ou fusion, pour un déplacement dans un dossier existant) de
+    /// `src` vers `dest`, dont les conflits sont déjà résolus.
+    fn copy(
+        &mut self,
+        src: &Path,
+        dest: &Path,
+        src_meta: &fs::Metadata,
+        merge: bool,
+    ) -> io::Result<()> {
+        let dest = dest.to_path_buf();
+        if src_meta.is_dir() {
+            fs::create_dir_all(&dest)?;
+            self.record(src, &dest, src_meta);
+            for entry in fs::read_dir(src)? {
+                let entry = entry?;
+                self.transfer(&entry.path(), &dest.join(entry.file_name()))?;
+            }
+            // Métadonnées d'un dossier fusionné : celles de l'existant sont gardées
+            if !merge {
+                for warning in preserve::apply_metadata(src, src_meta, &dest) {
+                    self.warn(warning);
+                }
+            }
+            if self.is_cut {
+                // Reste en place s'il contient des éléments ignorés
+                let _ = fs::remove_dir(src);
+            }
+            return Ok(());
+        }
+
+        self.progress.set_current(src);
+        let file_type = src_meta.file_type();
+        let copied = if file_type.is_symlink() {
+            preserve::copy_symlink(src, &dest)
+        } else if file_type.is_file() {
+            copy_file(src, &dest, self.progress)
+        } else {
+            preserve::copy_special(src_meta, &dest)
+        };
+        match copied {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::Unsupported => {
+                // Non reproductible (socket…) : signalé, la source reste en place
+                self.warn(format!("{} : {e}", src.to_string_lossy()));
+                self.progress.files_done.fetch_add(1, Ordering::Relaxed);
+                return Ok(());
+            }
+            Err(e) => return Err(e),
+        }
+        self.record(src, &dest, src_meta);
+        for warning in preserve::apply_metadata(src, src_meta, &dest) {
+            self.warn(warning);
+        }
+        self.progress.files_done.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn record(&mut self, src: &Path, dest: &Path, meta: &fs::Metadata) {
+        if let Some(copied) = &mut self.copied {
+            copied.push(CopiedItem {
+                src: src.to_path_buf(),
+                dest: dest.to_path_buf(),
+                file_type: meta.file_type(),
+            });
+        }
+    }
+
+    /// Déplacement vers un autre système de fichiers : copie complète,
+    /// vérification de chaque fichier (taille et empreinte), et seulement
+    /// ensuite suppression de la source. En cas d'échec, la source est
+    /// conservée et la copie partielle signalée.
+    fn move_by_copy(&mut self, src: &Path, dest: &Path, src_meta: &fs::Metadata) -> io::Result<()> {
+        let mut copy = Transfer {
+            is_cut: false,
+            plan: self.plan,
+            progress: self.progress,
+            skipped: 0,
+            warnings: Vec::new(),
+            warning_count: 0,
+            copied: Some(Vec::new()),
+            placing: 1,
+            placed: Vec::new(),
+        };
+        let result = copy
+            .copy(src, dest, src_meta, false)
+            .and_then(|()| copy.verify());
+        self.skipped += copy.skipped;
+        self.warning_count += copy.warning_count;
+        let room = MAX_WARNINGS.saturating_sub(self.warnings.len());
+        self.warnings.extend(copy.warnings.into_iter().take(room));
+
+        if let Err(e) = result {
+            if e.kind() == io::ErrorKind::Interrupted {
+                return Err(e);
+            }
+            return Err(io::Error::new(
+                e.kind(),
+                format!(
+                    "{e}. Déplacement interrompu : source conservée, copie partielle dans {}",
+                    dest.to_string_lossy()
+                ),
+            ));
+        }
+
+        // Enfants avant parents ; un dossier contenant un élément non copié reste
+        for item in copy.copied.unwrap_or_default().iter().rev() {
+            if item.file_type.is_dir() {
+                let _ = fs::remove_dir(&item.src);
+            } else {
+                fs::remove_file(&item.src)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Relit chaque fichier copié et le compare à sa source.
+    fn verify(&self) -> io::Result<()> {
+        let Some(copied) = &self.copied else {
+            return Ok(());
+        };
+        self.progress.verifying.store(true, Ordering::Relaxed);
+        let result = copied.iter().try_for_each(|item| {
+            self.progress.checkpoint()?;
+            self.progress.set_current(&item.src);
+            let mismatch = |what: &str| {
+                Err(io::Error::other(format!(
+                    "Vérification échouée ({what}) : {}",
+                    item.dest.to_string_lossy()
+                )))
+            };
+            if item.file_type.is_symlink() {
+                if fs::read_link(&item.src)? != fs::read_link(&item.dest)? {
+                    return mismatch("cible du lien différente");
+                }
+            } else if item.file_type.is_file() {
+                if fs::symlink_metadata(&item.src)?.len() != fs::symlink_metadata(&item.dest)?.len()
+                {
+                    return mismatch("taille différente");
+                }
+                let cancel = &self.progress.cancel;
+                if hash_file(&item.src, None, cancel)? != hash_file(&item.dest, None, cancel)? {
+                    return mismatch("contenu différent");
+                }
+            }
+            Ok(())
+        });
+        self.progress.verifying.store(false, Ordering::Relaxed);
+        result
+    }
+}
+
+/// Copie du contenu d'un fichier par blocs (pause et annulation entre deux
+/// blocs). Les métadonnées sont reportées ensuite par `preserve`.
+///
+/// La copie est écrite dans un fichier temporaire du dossier de destination
+/// puis renommée sur `dest` : un fichier écrasé reste intact jusqu'au
+/// remplacement, et seul le fichier temporaire est supprimé en cas d'échec
+/// (jamais de fichier tronqué à l'arrivée).
+fn copy_file(src: &Path, dest: &Path, progress: &JobProgress) -> io::Result<()> {
+    let mut reader = fs::File::open(src)?;
+    let replacing = fs::symlink_metadata(dest).is_ok();
+    let (tmp, mut writer) = create_temp_beside(dest)?;
+    let result = (|| {
+        let mut buffer = vec![0u8; COPY_BUFFER];
+        loop {
+            progress.checkpoint()?;
+            let read = reader.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            writer.write_all(&buffer[..read])?;
+            progress
+                .bytes_done
+                .fetch_add(read as u64, Ordering::Relaxed);
+        }
+        // Sur le disque avant de remplacer l'original
+        if replacing {
+            writer.sync_all()?;
+        }
+        drop(writer);
+        fs::rename(&tmp, dest)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    result
+}
+
+/// Fichier temporaire caché, nouveau, à côté de `dest`. Créé lisible du
+/// seul propriétaire : les droits de la source ne sont reportés qu'après le
+/// renommage, et une copie interrompue ne doit rien exposer entre-temps.
+fn create_temp_beside(dest: &Path) -> io::Result<(PathBuf, fs::File)> {
+    let file_name = dest
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    for n in 1u32.. {
+        let tmp = match n {
+            1 => dest.with_file_name(format!(".{file_name}.treesize-copy")),
+            n => dest.with_file_name(format!(".{file_name}.treesize-copy-{n}")),
+        };
+        match options.open(&tmp) {
+            Ok(file) => return Ok((tmp, file)),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Err(io::Error::from(io::ErrorKind::AlreadyExists))
+}
+
+/// Suppression récursive avec progression (fichier par fichier).
+fn remove_tree(path: &Path, progress: &JobProgress) -> io::Result<()> {
+    progress.checkpoint()?;
+    let meta = fs::symlink_metadata(path)?;
+    if meta.is_dir() {
+        for entry in fs::read_dir(path)? {
+            remove_tree(&entry?.path(), progress)?;
+        }
+        return fs::remove_dir(path);
+    }
+    progress.set_current(path);
+    fs::remove_file(path)?;
+    progress.files_done.fetch_add(1, Ordering::Relaxed);
+    progress.bytes_done.fetch_add(meta.len(), Ordering::Relaxed);
+    Ok(())
+}
+
+/// Durée lisible : "2 h 05 min", "3 min 20 s", "12 s".
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match (secs / 3600, secs % 3600 / 60, secs % 60) {
+        (0, 0, s) => format!("{s} s"),
+        (0, m, s) => format!("{m} min {s:02} s"),
+        (h, m, _) => format!("{h} h {m:02} min"),
+    }
+}
+
+/// Progression, débit et temps restant d'une opération en cours.
+fn draw_running(ui: &mut egui::Ui, progress: &JobProgress) {
+    let bytes_total = progress.bytes_total.load(Ordering::Relaxed);
+    let bytes_done = progress.bytes_done.load(Ordering::Relaxed);
+    let files_total = progress.files_total.load(Ordering::Relaxed);
+    let files_done = progress.files_done.load(Ordering::Relaxed);
+
+    let fraction = if bytes_total > 0 {
+        bytes_done as f32 / bytes_total as f32
+    } else if files_total > 0 {
+        files_done as f32 / files_total as f32
+    } else {
+        0.0
+    };
+    ui.add(egui::ProgressBar::new(fraction.min(1.0)).show_percentage());
+
+    if bytes_total > 0 {
+        ui.small(format!(
+            "{} / {} — {files_done} / {files_total} fichiers",
+            format_bytes(bytes_done),
+            format_bytes(bytes_total)
+        ));
+    } else {
+        ui.small(format!("{files_done} / {files_total} éléments"));
+    }
+
+    let current = progress.current();
+    if let Some(name) = current.file_name() {
+        let label = if progress.verifying.load(Ordering::Relaxed) {
+            "Vérification"
+        } else {
+            "Fichier"
+        };
+        ui.small(format!("{label} : {}", name.to_string_lossy()))
+            .on_hover_text(current.to_string_lossy());
+    }
+
+    if progress.paused.load(Ordering::Relaxed) {
+        ui.small("En pause.");
+    } else if let Some(active) = progress.active_time() {
+        let secs = active.as_secs_f64();
+        if secs >= 1.0 && bytes_done > 0 {
+            let throughput = bytes_done as f64 / secs;
+            let remaining = bytes_total.saturating_sub(bytes_done) as f64 / throughput;
+            ui.small(format!(
+                "Débit : {}/s — reste ~ {}",
+                format_bytes(throughput as u64),
+                format_duration(Duration::from_secs_f64(remaining))
+            ));
+        }
+    }
+}
+
+/// Panneau des opérations : en attente, en cours et terminées.
+pub fn draw_jobs(ui: &mut egui::Ui, queue: &mut JobQueue) {
+    for job in &queue.jobs {
+        ui.separator();
+        let title = match job.sources.as_slice() {
+            [src] => src
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| src.to_string_lossy().to_string()),
+            sources => format!("{} éléments", sources.len()),
+        };
+        ui.label(egui::RichText::new(format!("{} : {title}", job.kind.label())).strong());
+        if let Some(dest) = &job.dest {
+            ui.small(format!("→ {}", dest.to_string_lossy()));
+        }
+
+        match &job.status {
+            JobStatus::Pending => {
+                ui.horizontal(|ui| {
+                    ui.weak("En attente");
+                    if ui.small_button("Annuler").clicked() {
+                        job.progress.cancel.store(true, Ordering::Relaxed);
+                    }
+                });
+            }
+            JobStatus::Running if job.prompt.is_some() => {
+                let remaining = job.prompt.as_ref().map_or(0, ConflictPrompt::remaining);
+                ui.colored_label(
+                    egui::Color32::YELLOW,
+                    format!("En attente de votre choix ({remaining} conflits)."),
+                );
+            }
+            JobStatus::Running => {
+                draw_running(ui, &job.progress);
+                ui.horizontal(|ui| {
+                    let paused = job.progress.paused.load(Ordering::Relaxed);
+                    let label = if paused { "Reprendre" } else { "Pause" };
+                    if ui.small_button(label).clicked() {
+                        job.progress.paused.store(!paused, Ordering::Relaxed);
+                    }
+                    if ui.small_button("Annuler").clicked() {
+                        job.progress.cancel.store(true, Ordering::Relaxed);
+                    }
+                });
+            }
+            JobStatus::Finished(outcome) => {
+                if outcome.cancelled {
+                    ui.weak(format!(
+                        "Annulée ({} / {} éléments).",
+                        outcome.done,
+                        job.sources.len()
+                    ));
+                } else if let Some(error) = outcome.errors.last() {
+                    ui.colored_label(
+                        egui::Color32::RED,
+                        format!("{} erreur(s) : {error}", outcome.errors.len()),
+                    );
+                } else {
+                    ui.weak("Terminée.");
+                }
+                if outcome.warning_count > 0 {
+                    egui::CollapsingHeader::new(format!(
+                        "{} éléments reproduits partiellement",
+                        outcome.warning_count
+                    ))
+                    .id_source(("job_warnings", job.id))
+                    .show(ui, |ui| {
+                        egui::ScrollArea::vertical()
+                            .max_height(150.0)
+                            .show(ui, |ui| {
+                                for warning in &outcome.warnings {
+                                    ui.small(warning);
+                                }
+                                let hidden = outcome.warning_count - outcome.warnings.len();
+                                if hidden > 0 {
+                                    ui.weak(format!("… et {hidden} autres."));
+                                }
+                            });
+                    });
+                }
+            }
+        }
+    }
+
+    if queue
+        .jobs
+        .iter()
+        .any(|j| matches!(j.status, JobStatus::Finished(_)))
+    {
+        ui.add_space(4.0);
+        if ui.button("Effacer les opérations terminées").clicked() {
+            queue.clear_finished();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(unix)]
+    fn replacing_an_item_by_itself_keeps_it() {
+        let root = std::env::temp_dir().join(format!("treesize_jobs_self_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        fs::create_dir(root.join("dossier")).unwrap();
+        std::os::unix::fs::symlink("cible", root.join("dossier").join("lien")).unwrap();
+        std::os::unix::fs::symlink("dossier", root.join("lien")).unwrap();
+
+        // Plan forcé à "Remplacer" sur les éléments eux-mêmes
+        let plan: HashMap<PathBuf, Resolution> = [root.join("lien"), root.join("dossier")]
+            .into_iter()
+            .map(|p| (p, Resolution::Overwrite))
+            .collect();
+        let progress = JobProgress::default();
+        for is_cut in [false, true] {
+            let mut transfer = Transfer {
+                is_cut,
+                plan: &plan,
+                progress: &progress,
+                skipped: 0,
+                warnings: Vec::new(),
+                warning_count: 0,
+                copied: None,
+                placing: 0,
+                placed: Vec::new(),
+            };
+            for name in ["lien", "dossier"] {
+                transfer.paste(&root.join(name), &root).unwrap();
+            }
+            assert_eq!(transfer.skipped, 2);
+            assert!(transfer.placed.is_empty());
+        }
+        assert_eq!(
+            fs::read_link(root.join("lien")).unwrap(),
+            Path::new("dossier")
+        );
+        assert_eq!(
+            fs::read_link(root.join("dossier").join("lien")).unwrap(),
+            Path::new("cible")
+        );
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
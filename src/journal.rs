@@ -0,0 +1,443 @@
+//! Journal persistant des opérations annulables (copies, déplacements,
+//! mises à la corbeille) : fichier texte en ajout seul dans le dossier de
+//! données de l'utilisateur, une ligne par opération ou élément.
+//!
+//! Format (champs séparés par des tabulations, chemins encodés en `%XX`) :
+//! `op <id> <horodatage> <type>`, puis `item <remplacé 0/1> <origine> <destination>`
+//! pour chaque élément, et `undone <id>` une fois l'opération annulée.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{Local, TimeZone};
+use eframe::egui;
+
+/// Opérations conservées (les plus anciennes sont oubliées au chargement).
+const MAX_ENTRIES: usize = 500;
+/// Opérations affichées dans l'historique.
+const SHOWN_ENTRIES: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationKind {
+    Copy,
+    Move,
+    Trash,
+}
+
+impl OperationKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            OperationKind::Copy => "Copie",
+            OperationKind::Move => "Déplacement",
+            OperationKind::Trash => "Mise à la corbeille",
+        }
+    }
+
+    fn code(self) -> &'static str {
+        match self {
+            OperationKind::Copy => "copy",
+            OperationKind::Move => "move",
+            OperationKind::Trash => "trash",
+        }
+    }
+
+    fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "copy" => Some(OperationKind::Copy),
+            "move" => Some(OperationKind::Move),
+            "trash" => Some(OperationKind::Trash),
+            _ => None,
+        }
+    }
+}
+
+/// Elément traité : `from` (source ou emplacement d'origine) vers `to`
+/// (copie créée, nouvel emplacement ou emplacement dans la corbeille).
+#[derive(Debug, Clone)]
+pub struct JournalItem {
+    pub from: PathBuf,
+    pub to: PathBuf,
+    /// La copie a remplacé un élément existant (qui ne peut pas être rendu).
+    pub replaced: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct JournalEntry {
+    pub id: u64,
+    /// Horodatage Unix (secondes).
+    time: i64,
+    pub kind: OperationKind,
+    pub items: Vec<JournalItem>,
+    pub undone: bool,
+}
+
+impl JournalEntry {
+    /// Résumé : "Copie de rapport.pdf" / "Déplacement de 12 éléments".
+    fn describe(&self) -> String {
+        match self.items.as_slice() {
+            [item] => format!(
+                "{} de {}",
+                self.kind.label(),
+                item.from
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_else(|| item.from.to_string_lossy().to_string())
+            ),
+            items => format!("{} de {} éléments", self.kind.label(), items.len()),
+        }
+    }
+
+    fn time_label(&self) -> String {
+        Local
+            .timestamp_opt(self.time, 0)
+            .single()
+            .map(|t| t.format("%d/%m/%Y %H:%M").to_string())
+            .unwrap_or_default()
+    }
+}
+
+/// Historique chargé depuis le fichier journal.
+#[derive(Default)]
+pub struct Journal {
+    entries: Vec<JournalEntry>,
+    path: Option<PathBuf>,
+    next_id: u64,
+    /// Dernière erreur d'écriture du journal.
+    error: Option<String>,
+}
+
+impl Journal {
+    /// Charge le journal de l'utilisateur (vide s'il n'existe pas encore).
+    pub fn load() -> Self {
+        let path = dirs::data_dir().map(|d| d.join("treesize_rust").join("journal.log"));
+        let mut journal = Journal {
+            path,
+            ..Journal::default()
+        };
+        let Some(path) = journal.path.clone() else {
+            return journal;
+        };
+        if let Ok(text) = fs::read_to_string(&path) {
+            journal.entries = parse(&text);
+        }
+        journal.next_id = journal.entries.iter().map(|e| e.id + 1).max().unwrap_or(0);
+
+        if journal.entries.len() > MAX_ENTRIES {
+            let excess = journal.entries.len() - MAX_ENTRIES;
+            journal.entries.drain(..excess);
+            journal.rewrite();
+        }
+        journal
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Enregistre une opération terminée.
+    pub fn record(&mut self, kind: OperationKind, items: Vec<JournalItem>) {
+        if items.is_empty() {
+            return;
+        }
+        let entry = JournalEntry {
+            id: self.next_id,
+            time: Local::now().timestamp(),
+            kind,
+            items,
+            undone: false,
+        };
+        self.next_id += 1;
+        self.append(&format_entry(&entry));
+        self.entries.push(entry);
+    }
+
+    pub fn mark_undone(&mut self, id: u64) {
+        if let Some(entry) = self.entries.iter_mut().find(|e| e.id == id) {
+            entry.undone = true;
+            self.append(&format!("undone\t{id}\n"));
+        }
+    }
+
+    /// Annulation partielle : l'opération ne garde que les éléments qui
+    /// n'ont pas été rétablis (annulables à nouveau).
+    pub fn keep_items(&mut self, id: u64, items: Vec<JournalItem>) {
+        if items.is_empty() {
+            self.mark_undone(id);
+            return;
+        }
+        if let Some(entry) = self.entries.iter_mut().find(|e| e.id == id) {
+            entry.items = items;
+            self.rewrite();
+        }
+    }
+
+    pub fn entry(&self, id: u64) -> Option<&JournalEntry> {
+        self.entries.iter().find(|e| e.id == id)
+    }
+
+    /// Dernière opération pas encore annulée.
+    pub fn last_undoable(&self) -> Option<&JournalEntry> {
+        self.entries.iter().rev().find(|e| !e.undone)
+    }
+
+    fn append(&mut self, text: &str) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let result = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::OpenOptions::new().create(true).append(true).open(path))
+            .and_then(|mut file| file.write_all(text.as_bytes()));
+        self.error = result
+            .err()
+            .map(|e| format!("Journal non enregistré : {e}"));
+    }
+
+    /// Réécrit le fichier avec les seules opérations conservées.
+    fn rewrite(&mut self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let text: String = self.entries.iter().map(format_entry).collect();
+        let tmp = path.with_extension("log.tmp");
+        let result = fs::write(&tmp, text).and_then(|()| fs::rename(&tmp, path));
+        self.error = result
+            .err()
+            .map(|e| format!("Journal non enregistré : {e}"));
+    }
+}
+
+fn format_entry(entry: &JournalEntry) -> String {
+    let mut text = format!("op\t{}\t{}\t{}\n", entry.id, entry.time, entry.kind.code());
+    for item in &entry.items {
+        text.push_str(&format!(
+            "item\t{}\t{}\t{}\n",
+            u8::from(item.replaced),
+            encode_path(&item.from),
+            encode_path(&item.to)
+        ));
+    }
+    if entry.undone {
+        text.push_str(&format!("undone\t{}\n", entry.id));
+    }
+    text
+}
+
+/// Relit le journal ; les lignes illisibles sont ignorées.
+fn parse(text: &str) -> Vec<JournalEntry> {
+    let mut entries: Vec<JournalEntry> = Vec::new();
+    for line in text.lines() {
+        let fields: Vec<&str> = line.split('\t').collect();
+        match fields.as_slice() {
+            ["op", id, time, kind] => {
+                if let (Ok(id), Ok(time), Some(kind)) =
+                    (id.parse(), time.parse(), OperationKind::from_code(kind))
+                {
+                    entries.push(JournalEntry {
+                        id,
+                        time,
+                        kind,
+                        items: Vec::new(),
+                        undone: false,
+                    });
+                }
+            }
+            ["item", replaced, from, to] => {
+                if let Some(entry) = entries.last_mut() {
+                    entry.items.push(JournalItem {
+                        from: decode_path(from),
+                        to: decode_path(to),
+                        replaced: *replaced == "1",
+                    });
+                }
+            }
+            ["undone", id] => {
+                if let Some(entry) = entries.iter_mut().find(|e| id.parse() == Ok(e.id)) {
+                    entry.undone = true;
+                }
+            }
+            _ => {}
+        }
+    }
+    entries.retain(|e| !e.items.is_empty());
+    entries
+}
+
+/// Encodage `%XX` des octets hors caractères sûrs (pas de tabulation ni de
+/// retour à la ligne dans le fichier).
+pub fn encode_path(path: &Path) -> String {
+    let mut encoded = String::new();
+    for &byte in path.as_os_str().as_encoded_bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'/' | b'\\' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+pub fn decode_path(text: &str) -> PathBuf {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = (bytes[i] == b'%')
+            .then(|| text.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match hex {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    bytes_to_path(decoded)
+}
+
+#[cfg(unix)]
+fn bytes_to_path(bytes: Vec<u8>) -> PathBuf {
+    use std::os::unix::ffi::OsStringExt;
+    PathBuf::from(std::ffi::OsString::from_vec(bytes))
+}
+
+#[cfg(not(unix))]
+fn bytes_to_path(bytes: Vec<u8>) -> PathBuf {
+    PathBuf::from(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Annule un élément d'une opération (dans le thread des opérations).
+/// `move_back` déplace un élément vers un chemin exact (vérifié entre
+/// systèmes de fichiers).
+pub fn undo_item(
+    kind: OperationKind,
+    item: &JournalItem,
+    move_back: impl FnOnce(&Path, &Path) -> io::Result<()>,
+) -> io::Result<()> {
+    let missing = |what: &str| {
+        Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{what} : {}", item.to.to_string_lossy()),
+        ))
+    };
+    if fs::symlink_metadata(&item.to).is_err() {
+        return match kind {
+            OperationKind::Copy => missing("copie introuvable"),
+            OperationKind::Move => missing("élément déplacé introuvable"),
+            OperationKind::Trash => missing("n'est plus dans la corbeille"),
+        };
+    }
+
+    match kind {
+        OperationKind::Copy => {
+            if item.replaced {
+                return Err(io::Error::other(
+                    "la copie a remplacé un élément existant, qui ne peut pas être rendu",
+                ));
+            }
+            // La copie part à la corbeille plutôt que d'être effacée
+            if crate::trash::SUPPORTED {
+                crate::trash::move_to_trash(&item.to).map(|_| ())
+            } else {
+                crate::delete_path(&item.to)
+            }
+        }
+        OperationKind::Move | OperationKind::Trash => {
+            if fs::symlink_metadata(&item.from).is_ok() {
+                return Err(io::Error::new(
+                    io::ErrorKind::AlreadyExists,
+                    format!(
+                        "emplacement d'origine occupé : {}",
+                        item.from.to_string_lossy()
+                    ),
+                ));
+            }
+            if let Some(parent) = item.from.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            if kind == OperationKind::Trash {
+                crate::trash::restore(&item.to, &item.from)
+            } else {
+                move_back(&item.to, &item.from)
+            }
+        }
+    }
+}
+
+/// Historique : bouton "Annuler la dernière opération" et liste des
+/// opérations récentes. Renvoie l'opération à annuler.
+pub fn draw_journal(ui: &mut egui::Ui, journal: &Journal, undo_running: bool) -> Option<u64> {
+    let mut undo = None;
+
+    let last = journal.last_undoable();
+    let button = ui
+        .add_enabled(
+            last.is_some() && !undo_running,
+            egui::Button::new("↶ Annuler la dernière opération"),
+        )
+        .on_hover_text(last.map(JournalEntry::describe).unwrap_or_default());
+    if button.clicked() {
+        undo = last.map(|e| e.id);
+    }
+    if undo_running {
+        ui.weak("Annulation en cours…");
+    }
+    if let Some(error) = &journal.error {
+        ui.colored_label(egui::Color32::RED, error);
+    }
+
+    egui::CollapsingHeader::new(format!("Historique ({})", journal.entries.len()))
+        .id_source("journal_history")
+        .show(ui, |ui| {
+            egui::ScrollArea::vertical()
+                .id_source("journal_entries")
+                .max_height(200.0)
+                .show(ui, |ui| {
+                    for entry in journal.entries.iter().rev().take(SHOWN_ENTRIES) {
+                        ui.horizontal(|ui| {
+                            ui.small(entry.time_label());
+                            let text = entry.describe();
+                            if entry.undone {
+                                ui.label(egui::RichText::new(text).strikethrough().weak());
+                            } else {
+                                ui.label(text);
+                                if ui
+                                    .add_enabled(
+                                        !undo_running,
+                                        egui::Button::new("Annuler").small(),
+                                    )
+                                    .clicked()
+                                {
+                                    undo = Some(entry.id);
+                                }
+                            }
+                        })
+                        .response
+                        .on_hover_text(
+                            entry
+                                .items
+                                .iter()
+                                .map(|i| {
+                                    format!(
+                                        "{} → {}",
+                                        i.from.to_string_lossy(),
+                                        i.to.to_string_lossy()
+                                    )
+                                })
+                                .collect::<Vec<_>>()
+                                .join("\n"),
+                        );
+                    }
+                });
+        });
+
+    undo
+}
@@ -0,0 +1,238 @@
+//! Ouverture par les programmes du système : application par défaut
+//! (`xdg-open`), gestionnaire de fichiers avec l'élément sélectionné
+//! (interface D-Bus `org.freedesktop.FileManager1` si disponible) et
+//! terminal dans le dossier.
+//!
+//! Les programmes sont lancés dans un thread : l'interface ne se bloque pas
+//! et les échecs sont renvoyés par [`Launcher::poll`].
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+
+use crossbeam_channel::{unbounded, Receiver, Sender};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Launch {
+    /// Application associée (fichier) ou gestionnaire de fichiers (dossier).
+    Open,
+    /// Gestionnaire de fichiers sur le dossier parent, élément sélectionné.
+    ShowInFileManager,
+    /// Terminal dans le dossier (le parent pour un fichier).
+    Terminal,
+}
+
+impl Launch {
+    pub fn label(self) -> &'static str {
+        match self {
+            Launch::Open => "Ouvrir",
+            Launch::ShowInFileManager => "Afficher dans le gestionnaire de fichiers",
+            Launch::Terminal => "Ouvrir un terminal ici",
+        }
+    }
+}
+
+/// Lancements en cours ; les erreurs reviennent par un canal.
+pub struct Launcher {
+    sender: Sender<String>,
+    receiver: Receiver<String>,
+}
+
+impl Default for Launcher {
+    fn default() -> Self {
+        let (sender, receiver) = unbounded();
+        Self { sender, receiver }
+    }
+}
+
+impl Launcher {
+    /// Lance l'action sur `path`. `terminal` : commande configurée (vide :
+    /// détection automatique).
+    pub fn launch(&self, action: Launch, path: PathBuf, terminal: String) {
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            let result = match action {
+                Launch::Open => open_default(&path),
+                Launch::ShowInFileManager => show_in_file_manager(&path),
+                Launch::Terminal => {
+                    let dir = if path.is_dir() {
+                        path.as_path()
+                    } else {
+                        path.parent().unwrap_or(&path)
+                    };
+                    open_terminal(dir, &terminal)
+                }
+            };
+            if let Err(e) = result {
+                let _ = sender.send(format!("{} impossible : {e}", action.label()));
+            }
+        });
+    }
+
+    /// Erreur de lancement survenue depuis le dernier appel.
+    pub fn poll(&self) -> Option<String> {
+        self.receiver.try_iter().last()
+    }
+}
+
+/// Lance un programme et attend sa fin (les ouvreurs rendent la main
+/// aussitôt) ; erreur si son code de retour est non nul.
+fn run(command: &mut Command) -> io::Result<()> {
+    let status = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()?;
+    if !status.success() {
+        return Err(io::Error::other(format!(
+            "{} a échoué ({status})",
+            command.get_program().to_string_lossy()
+        )));
+    }
+    Ok(())
+}
+
+/// Lance un programme sans attendre sa fin (terminal, explorateur).
+fn spawn(command: &mut Command) -> io::Result<()> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    // Attendu pour ne pas laisser de processus zombie
+    thread::spawn(move || child.wait());
+    Ok(())
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn open_default(path: &Path) -> io::Result<()> {
+    run(Command::new("xdg-open").arg(path))
+}
+
+#[cfg(target_os = "macos")]
+fn open_default(path: &Path) -> io::Result<()> {
+    run(Command::new("open").arg(path))
+}
+
+#[cfg(windows)]
+fn open_default(path: &Path) -> io::Result<()> {
+    spawn(Command::new("explorer").arg(path))
+}
+
+/// FileManager1.ShowItems (Nautilus, Dolphin, Nemo, Thunar…), sinon
+/// ouverture du dossier parent.
+#[cfg(all(unix, not(target_os = "macos")))]
+fn show_in_file_manager(path: &Path) -> io::Result<()> {
+    let path = std::path::absolute(path)?;
+    let uri = format!("file://{}", crate::trash::percent_encode(&path));
+    let shown = run(Command::new("dbus-send")
+        .args([
+            "--session",
+            "--print-reply",
+            "--reply-timeout=5000",
+            "--dest=org.freedesktop.FileManager1",
+            "--type=method_call",
+            "/org/freedesktop/FileManager1",
+            "org.freedesktop.FileManager1.ShowItems",
+        ])
+        .arg(format!("array:string:{uri}"))
+        .arg("string:"));
+    if shown.is_ok() {
+        return Ok(());
+    }
+    open_default(path.parent().unwrap_or(&path))
+}
+
+#[cfg(target_os = "macos")]
+fn show_in_file_manager(path: &Path) -> io::Result<()> {
+    run(Command::new("open").arg("-R").arg(path))
+}
+
+#[cfg(windows)]
+fn show_in_file_manager(path: &Path) -> io::Result<()> {
+    let mut select = std::ffi::OsString::from("/select,");
+    select.push(path);
+    spawn(Command::new("explorer").arg(select))
+}
+
+/// Terminal dans `dir` : commande configurée (mots séparés par des
+/// espaces, "{dir}" remplacé par le dossier), sinon terminal détecté.
+fn open_terminal(dir: &Path, configured: &str) -> io::Result<()> {
+    let configured = configured.trim();
+    let command = if configured.is_empty() {
+        default_terminal()
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    "aucun terminal trouvé (à configurer dans Préférences)",
+                )
+            })?
+            .to_string()
+    } else {
+        configured.to_string()
+    };
+
+    let mut words = command.split_whitespace();
+    let program = words.next().unwrap_or_default();
+    let mut process = Command::new(program);
+    for word in words {
+        if word.contains("{dir}") {
+            process.arg(word.replace("{dir}", &dir.to_string_lossy()));
+        } else {
+            process.arg(word);
+        }
+    }
+    spawn(process.current_dir(dir))
+}
+
+/// Terminal de l'utilisateur (`$TERMINAL`) ou premier terminal courant installé.
+#[cfg(all(unix, not(target_os = "macos")))]
+fn default_terminal() -> Option<String> {
+    if let Ok(terminal) = std::env::var("TERMINAL") {
+        if !terminal.trim().is_empty() {
+            return Some(terminal);
+        }
+    }
+    const CANDIDATES: [&str; 9] = [
+        "x-terminal-emulator",
+        "gnome-terminal",
+        "konsole",
+        "xfce4-terminal",
+        "mate-terminal",
+        "alacritty",
+        "kitty",
+        "wezterm",
+        "xterm",
+    ];
+    let paths = std::env::var_os("PATH")?;
+    CANDIDATES
+        .iter()
+        .find(|name| std::env::split_paths(&paths).any(|dir| dir.join(name).is_file()))
+        .map(|name| name.to_string())
+}
+
+#[cfg(target_os = "macos")]
+fn default_terminal() -> Option<String> {
+    Some("open -a Terminal {dir}".to_string())
+}
+
+#[cfg(windows)]
+fn default_terminal() -> Option<String> {
+    Some("cmd.exe /C start cmd.exe".to_string())
+}
+
+#[cfg(not(any(unix, windows)))]
+fn open_default(_path: &Path) -> io::Result<()> {
+    Err(io::Error::from(io::ErrorKind::Unsupported))
+}
+
+#[cfg(not(any(unix, windows)))]
+fn show_in_file_manager(_path: &Path) -> io::Result<()> {
+    Err(io::Error::from(io::ErrorKind::Unsupported))
+}
+
+#[cfg(not(any(unix, windows)))]
+fn default_terminal() -> Option<String> {
+    None
+}
@@ -1,5 +1,5 @@
 use std::collections::hash_map::DefaultHasher;
-use std::ffi::OsStr;
+use std::collections::HashSet;
 use std::fs;
 use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
@@ -8,11 +8,66 @@ use std::sync::{
     Arc,
 };
 use std::thread;
+use std::time::SystemTime;
 
 use crossbeam_channel::{unbounded, Receiver};
 use eframe::{egui, NativeOptions};
 use rayon::prelude::*;
 
+mod age;
+mod archive;
+mod audit;
+mod conflicts;
+mod delete_preview;
+mod dev_cache;
+mod duplicates;
+mod empty;
+mod extensions;
+mod filter;
+mod histogram;
+mod jobs;
+mod journal;
+mod launch;
+mod owners;
+mod preserve;
+mod protect;
+mod rename;
+mod search;
+mod settings;
+mod sorting;
+mod sunburst;
+mod top_n;
+mod trash;
+mod tree_table;
+
+use age::{draw_age, AgeState};
+use archive::{
+    draw_archive_review, draw_compress_window, ArchiveReview, CompressAction, CompressDialog,
+    ReviewAction,
+};
+use audit::{draw_audit_window, AuditOp, AuditViewer};
+use delete_preview::{draw_delete_preview, DeletePreview};
+use dev_cache::{draw_dev_cache, DevCacheState};
+use duplicates::{draw_duplicates, DuplicateJob, DuplicatesState};
+use empty::{draw_empty, EmptyState};
+use extensions::{draw_extensions, ExtensionsState};
+use filter::{filter_tree, order_matches, TreeFilter};
+use histogram::{draw_histogram, HistogramState};
+use jobs::{draw_jobs, JobKind, JobQueue};
+use journal::{draw_journal, Journal};
+use launch::{Launch, Launcher};
+use owners::{draw_owners, OwnersState};
+use protect::{draw_protected_window, PromptAction, ProtectedOperation, ProtectedPrompt};
+use rename::{
+    draw_rename_window, rename_node, rename_path, reprefix, validate_name, RenameAction, RenameEdit,
+};
+use search::{draw_search, SearchRequest, SearchState};
+use settings::{draw_settings, Settings};
+use sorting::{SortColumn, SortOptions};
+use sunburst::{draw_sunburst, SunburstState};
+use top_n::{draw_top_n, TopNState};
+use tree_table::{draw_tree_table, TreeTableState};
+
 fn main() -> eframe::Result<()> {
     let native_options = NativeOptions {
         viewport: egui::ViewportBuilder::default()
@@ -33,45 +88,201 @@ struct Node {
     path: PathBuf,
     is_dir: bool,
     size: u64,
+    /// Espace réellement occupé sur le disque (blocs alloués).
+    allocated: u64,
     file_count: u64,
+    /// Dernière modification (pour un dossier : la plus récente de son contenu).
+    modified: Option<SystemTime>,
+    /// Dernier accès (pour un dossier : le plus récent de son contenu).
+    accessed: Option<SystemTime>,
+    /// Propriétaire (uid, gid) de l'entrée elle-même (None hors Unix).
+    owner: Option<(u32, u32)>,
     children: Vec<Node>,
 }
 
 impl Node {
-    fn new_dir(name: String, path: PathBuf, children: Vec<Node>) -> Self {
+    fn new_dir(
+        name: String,
+        path: PathBuf,
+        modified: Option<SystemTime>,
+        children: Vec<Node>,
+    ) -> Self {
         let mut size = 0;
+        let mut allocated = 0;
         let mut file_count = 0;
+        let mut modified = modified;
+        // L'accès du dossier lui-même est mis à jour par le scan : seul le contenu compte
+        let mut accessed = None;
 
         for child in &children {
             size += child.size;
+            allocated += child.allocated;
             file_count += child.file_count;
+            modified = modified.max(child.modified);
+            accessed = accessed.max(child.accessed);
         }
 
-        let mut children = children;
-        children.sort_by(|a, b| b.size.cmp(&a.size));
-
         Self {
             name,
             path,
             is_dir: true,
             size,
+            allocated,
             file_count,
+            modified,
+            accessed,
+            owner: None,
             children,
         }
     }
 
-    fn new_file(name: String, path: PathBuf, size: u64) -> Self {
+    fn new_file(
+        name: String,
+        path: PathBuf,
+        size: u64,
+        allocated: u64,
+        modified: Option<SystemTime>,
+        accessed: Option<SystemTime>,
+    ) -> Self {
         Self {
             name,
             path,
             is_dir: false,
             size,
+            allocated,
             file_count: 1,
+            modified,
+            accessed,
+            owner: None,
             children: Vec::new(),
         }
     }
 }
 
+/// Sélection, presse-papier interne et actions différées partagés par les vues.
+#[derive(Debug, Default)]
+struct NodeActions {
+    /// Elément courant (dernier cliqué), ancre des sélections par plage.
+    selected_node_path: Option<PathBuf>,
+    /// Sélection multiple (Ctrl/Maj + clic). Ignorée si elle ne contient pas
+    /// l'élément courant (sélection simple faite ailleurs).
+    selection: HashSet<PathBuf>,
+    /// Eléments à supprimer après confirmation (vide = aucune suppression).
+    pending_delete: Vec<PathBuf>,
+    /// Suppression demandée depuis la vue des éléments vides : chacun est
+    /// revérifié vide sur le disque à la confirmation.
+    delete_only_empty: bool,
+    /// Eléments copiés ou coupés (vide = presse-papier vide).
+    clipboard_paths: Vec<PathBuf>,
+    clipboard_is_cut: bool,
+    pending_paste_dest: Option<PathBuf>,
+    /// Elément à afficher (déplié et sélectionné) dans l'arborescence.
+    pending_reveal: Option<PathBuf>,
+    /// Renommage demandé (menu contextuel, F2) et renommage en cours.
+    pending_rename: Option<PathBuf>,
+    renaming: Option<RenameEdit>,
+    /// Compression demandée (menu contextuel).
+    pending_compress: Option<PathBuf>,
+    /// Copies de doublons à supprimer ou à lier (vue doublons).
+    pending_duplicate_job: Option<DuplicateJob>,
+    /// Ouverture par un programme du système (menu contextuel).
+    pending_launch: Option<(Launch, PathBuf)>,
+    /// Une vue a modifié le disque : rescan de la racine demandé.
+    rescan_requested: bool,
+}
+
+impl NodeActions {
+    fn has_multi_selection(&self) -> bool {
+        self.selected_node_path
+            .as_ref()
+            .is_some_and(|p| self.selection.contains(p))
+    }
+
+    fn is_selected(&self, path: &Path) -> bool {
+        if self.has_multi_selection() {
+            self.selection.contains(path)
+        } else {
+            self.selected_node_path.as_deref() == Some(path)
+        }
+    }
+
+    /// Eléments sélectionnés, triés (l'élément courant seul sans sélection
+    /// multiple).
+    fn selected_paths(&self) -> Vec<PathBuf> {
+        if self.has_multi_selection() {
+            let mut paths: Vec<PathBuf> = self.selection.iter().cloned().collect();
+            paths.sort();
+            paths
+        } else {
+            self.selected_node_path.iter().cloned().collect()
+        }
+    }
+
+    /// Clic avec modificateurs : Ctrl ajoute ou retire l'élément, Maj
+    /// sélectionne la plage `range` (éléments entre l'élément courant et
+    /// celui cliqué), sinon sélection simple.
+    fn click_select(
+        &mut self,
+        path: &Path,
+        modifiers: egui::Modifiers,
+        range: impl FnOnce() -> Vec<PathBuf>,
+    ) {
+        if modifiers.shift && self.selected_node_path.is_some() {
+            if !modifiers.command {
+                self.selection.clear();
+            } else if !self.has_multi_selection() {
+                self.selection = self.selected_node_path.iter().cloned().collect();
+            }
+            self.selection.extend(range());
+            self.selection.insert(path.to_path_buf());
+            // L'ancre reste l'élément courant
+            if let Some(anchor) = &self.selected_node_path {
+                self.selection.insert(anchor.clone());
+            }
+        } else if modifiers.command {
+            if !self.has_multi_selection() {
+                self.selection = self.selected_node_path.iter().cloned().collect();
+            }
+            if self.selection.remove(path) {
+                if self.selected_node_path.as_deref() == Some(path) {
+                    self.selected_node_path = self.selection.iter().min().cloned();
+                }
+            } else {
+                self.selection.insert(path.to_path_buf());
+                self.selected_node_path = Some(path.to_path_buf());
+            }
+        } else {
+            self.selection.clear();
+            self.selected_node_path = Some(path.to_path_buf());
+        }
+    }
+
+    fn clear_selection(&mut self) {
+        self.selection.clear();
+        self.selected_node_path = None;
+    }
+
+    /// Eléments visés par une action sur `path` : toute la sélection si elle
+    /// le contient, sinon `path` seul.
+    fn targets(&self, path: &Path) -> Vec<PathBuf> {
+        if self.is_selected(path) {
+            self.selected_paths()
+        } else {
+            vec![path.to_path_buf()]
+        }
+    }
+}
+
+/// Retire les chemins déjà couverts par un dossier parent de la liste.
+fn top_level_paths(paths: &[PathBuf]) -> Vec<PathBuf> {
+    let all: HashSet<&Path> = paths.iter().map(PathBuf::as_path).collect();
+    paths
+        .iter()
+        .filter(|p| !p.ancestors().skip(1).any(|a| all.contains(a)))
+        .cloned()
+        .collect()
+}
+
 #[derive(Debug)]
 struct ScanResult {
     root_path: PathBuf,
@@ -89,6 +300,15 @@ enum ScanMode {
 enum ViewMode {
     Tree,
     Treemap,
+    Sunburst,
+    TopN,
+    Extensions,
+    Duplicates,
+    Age,
+    Owners,
+    Empty,
+    DevCache,
+    Histogram,
 }
 
 /// Etat partagé de progression du scan (octets totaux / scannés)
@@ -108,7 +328,12 @@ impl Default for ScanProgress {
 
 struct TreeSizeApp {
     root_path: Option<PathBuf>,
-    root_node: Option<Node>,
+    /// Partagé avec les analyses en arrière-plan (lecture seule).
+    root_node: Option<Arc<Node>>,
+    /// Filtre de l'arborescence et copie élaguée correspondante
+    /// (None avec un filtre actif = aucun élément retenu).
+    tree_filter: TreeFilter,
+    filtered_root: Option<Arc<Node>>,
     is_scanning: bool,
     status: String,
     scan_receiver: Option<Receiver<ScanResult>>,
@@ -121,15 +346,43 @@ struct TreeSizeApp {
 
     // UI / sélection
     view_mode: ViewMode,
-    selected_node_path: Option<PathBuf>,
-
-    // Suppression
-    pending_delete: Option<PathBuf>,
-
-    // Clipboard interne pour copier/couper/coller
-    clipboard_path: Option<PathBuf>,
-    clipboard_is_cut: bool,
-    pending_paste_dest: Option<PathBuf>,
+    tree_table: TreeTableState,
+    sunburst: SunburstState,
+    top_n: TopNState,
+    extensions: ExtensionsState,
+    duplicates: DuplicatesState,
+    age: AgeState,
+    owners: OwnersState,
+    empty: EmptyState,
+    dev_cache: DevCacheState,
+    histogram: HistogramState,
+    search: SearchState,
+
+    // Sélection, suppression et clipboard interne pour copier/couper/coller
+    actions: NodeActions,
+    /// Fenêtre de confirmation : suppression définitive plutôt que corbeille.
+    permanent_delete: bool,
+    /// Résumé du contenu à supprimer (calculé à l'ouverture de la confirmation).
+    delete_preview: Option<DeletePreview>,
+    /// Suppression ou déplacement refusé (chemins protégés) en attente de décision.
+    protected_prompt: Option<ProtectedPrompt>,
+    /// Copies, déplacements et suppressions en arrière-plan.
+    jobs: JobQueue,
+    /// Une opération terminée a modifié le disque : rescan quand la file
+    /// sera vide.
+    rescan_after_jobs: bool,
+    /// Journal persistant des opérations annulables.
+    journal: Journal,
+    /// Choix du format d'archive, puis proposition de supprimer l'original
+    /// une fois l'archive vérifiée.
+    compress_dialog: Option<CompressDialog>,
+    archive_review: Option<ArchiveReview>,
+    /// Préférences enregistrées (commande du terminal…).
+    settings: Settings,
+    /// Programmes du système lancés depuis le menu contextuel.
+    launcher: Launcher,
+    /// Consultation du journal d'audit des opérations destructrices.
+    audit: AuditViewer,
 
     // Progression
     scan_progress: Option<Arc<ScanProgress>>,
@@ -142,6 +395,8 @@ impl Default for TreeSizeApp {
         Self {
             root_path: None,
             root_node: None,
+            tree_filter: TreeFilter::default(),
+            filtered_root: None,
             is_scanning: false,
             status: "Choisis un dossier ou un lecteur puis lance un scan."
                 .to_string(),
@@ -151,11 +406,29 @@ impl Default for TreeSizeApp {
             selected_root_index: 0,
             scan_mode: ScanMode::Folder,
             view_mode: ViewMode::Tree,
-            selected_node_path: None,
-            pending_delete: None,
-            clipboard_path: None,
-            clipboard_is_cut: false,
-            pending_paste_dest: None,
+            tree_table: TreeTableState::default(),
+            sunburst: SunburstState::default(),
+            top_n: TopNState::default(),
+            extensions: ExtensionsState::default(),
+            duplicates: DuplicatesState::default(),
+            age: AgeState::default(),
+            owners: OwnersState::default(),
+            empty: EmptyState::default(),
+            dev_cache: DevCacheState::default(),
+            histogram: HistogramState::default(),
+            search: SearchState::default(),
+            actions: NodeActions::default(),
+            permanent_delete: false,
+            delete_preview: None,
+            protected_prompt: None,
+            jobs: JobQueue::default(),
+            rescan_after_jobs: false,
+            journal: Journal::load(),
+            compress_dialog: None,
+            archive_review: None,
+            settings: Settings::load(),
+            launcher: Launcher::default(),
+            audit: AuditViewer::default(),
             scan_progress: None,
         }
     }
@@ -264,273 +537,455 @@ impl TreeSizeApp {
     }
 
     fn draw_left_panel(&mut self, ctx: &egui::Context) {
+        let mut search_request = None;
+
         egui::SidePanel::left("left_panel")
             .resizable(true)
             .default_width(340.0)
             .show(ctx, |ui| {
-                ui.vertical_centered(|ui| {
-                    ui.label(
-                        egui::RichText::new("Analyseur d’occupation disque")
-                            .small()
-                            .italics()
-                            .weak(),
-                    );
-                });
-
-                ui.add_space(8.0);
-
-                section_card(ui, "Mode de scan", |ui| {
-                    ui.horizontal_wrapped(|ui| {
-                        ui.selectable_value(
-                            &mut self.scan_mode,
-                            ScanMode::Folder,
-                            "Dossier",
-                        );
-                        ui.selectable_value(
-                            &mut self.scan_mode,
-                            ScanMode::Drive,
-                            "Lecteur",
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    ui.vertical_centered(|ui| {
+                        ui.label(
+                            egui::RichText::new("Analyseur d’occupation disque")
+                                .small()
+                                .italics()
+                                .weak(),
                         );
                     });
 
-                    ui.add_space(6.0);
-
-                    match self.scan_mode {
-                        ScanMode::Folder => {
-                            ui.label("Dossier sélectionné :");
-                            if let Some(path) = &self.root_path {
-                                ui.monospace(path.to_string_lossy());
-                            } else {
-                                ui.weak("(aucun dossier sélectionné)");
-                            }
-
-                            ui.add_space(4.0);
+                    ui.add_space(8.0);
 
-                            let choose_btn = egui::Button::new(
-                                "Choisir un dossier…",
-                            )
-                            .fill(
-                                ui.visuals().widgets.inactive.bg_fill,
+                    section_card(ui, "Mode de scan", |ui| {
+                        ui.horizontal_wrapped(|ui| {
+                            ui.selectable_value(
+                                &mut self.scan_mode,
+                                ScanMode::Folder,
+                                "Dossier",
+                            );
+                            ui.selectable_value(
+                                &mut self.scan_mode,
+                                ScanMode::Drive,
+                                "Lecteur",
                             );
+                        });
 
-                            if ui
-                                .add_enabled(!self.is_scanning, choose_btn)
-                                .clicked()
-                            {
-                                if let Some(path) = pick_directory() {
-                                    self.root_path = Some(path.clone());
-                                    self.status = format!(
-                                        "Dossier sélectionné : {}",
-                                        path.to_string_lossy()
-                                    );
+                        ui.add_space(6.0);
+
+                        match self.scan_mode {
+                            ScanMode::Folder => {
+                                ui.label("Dossier sélectionné :");
+                                if let Some(path) = &self.root_path {
+                                    ui.monospace(path.to_string_lossy());
+                                } else {
+                                    ui.weak("(aucun dossier sélectionné)");
                                 }
-                            }
-                        }
-                        ScanMode::Drive => {
-                            ui.label("Lecteur / racine à scanner :");
-                            if self.available_roots.is_empty() {
-                                ui.weak("Aucun lecteur détecté.");
-                            } else {
-                                let current_index =
-                                    self.selected_root_index.min(
-                                        self.available_roots.len()
-                                            .saturating_sub(1),
-                                    );
-                                self.selected_root_index =
-                                    current_index;
 
-                                let current_root = &self.available_roots
-                                    [self.selected_root_index];
+                                ui.add_space(4.0);
 
-                                egui::ComboBox::from_label(
-                                    "Choisir un lecteur",
+                                let choose_btn = egui::Button::new(
+                                    "Choisir un dossier…",
                                 )
-                                .selected_text(
-                                    current_root.to_string_lossy(),
-                                )
-                                .show_ui(ui, |ui| {
-                                    for (i, root) in self
-                                        .available_roots
-                                        .iter()
-                                        .enumerate()
-                                    {
-                                        let label = root
-                                            .to_string_lossy()
-                                            .to_string();
-                                        ui.selectable_value(
-                                            &mut self
-                                                .selected_root_index,
-                                            i,
-                                            label,
+                                .fill(
+                                    ui.visuals().widgets.inactive.bg_fill,
+                                );
+
+                                if ui
+                                    .add_enabled(!self.is_scanning, choose_btn)
+                                    .clicked()
+                                {
+                                    if let Some(path) = pick_directory() {
+                                        self.root_path = Some(path.clone());
+                                        self.status = format!(
+                                            "Dossier sélectionné : {}",
+                                            path.to_string_lossy()
                                         );
                                     }
-                                });
+                                }
+                            }
+                            ScanMode::Drive => {
+                                ui.label("Lecteur / racine à scanner :");
+                                if self.available_roots.is_empty() {
+                                    ui.weak("Aucun lecteur détecté.");
+                                } else {
+                                    let current_index =
+                                        self.selected_root_index.min(
+                                            self.available_roots.len()
+                                                .saturating_sub(1),
+                                        );
+                                    self.selected_root_index =
+                                        current_index;
+
+                                    let current_root = &self.available_roots
+                                        [self.selected_root_index];
+
+                                    egui::ComboBox::from_label(
+                                        "Choisir un lecteur",
+                                    )
+                                    .selected_text(
+                                        current_root.to_string_lossy(),
+                                    )
+                                    .show_ui(ui, |ui| {
+                                        for (i, root) in self
+                                            .available_roots
+                                            .iter()
+                                            .enumerate()
+                                        {
+                                            let label = root
+                                                .to_string_lossy()
+                                                .to_string();
+                                            ui.selectable_value(
+                                                &mut self
+                                                    .selected_root_index,
+                                                i,
+                                                label,
+                                            );
+                                        }
+                                    });
 
-                                self.root_path =
-                                    Some(current_root.to_path_buf());
+                                    self.root_path =
+                                        Some(current_root.to_path_buf());
+                                }
                             }
                         }
-                    }
-                });
+                    });
 
-                section_card(ui, "Actions", |ui| {
-                    ui.horizontal(|ui| {
-                        let can_scan =
-                            !self.is_scanning && self.root_path.is_some();
-                        if ui
-                            .add_enabled(
-                                can_scan,
-                                egui::Button::new("Lancer le scan")
-                                    .fill(
-                                        ui.visuals()
-                                            .selection
-                                            .bg_fill,
-                                    ),
-                            )
-                            .clicked()
-                        {
-                            if let Some(path) =
-                                self.root_path.clone()
+                    section_card(ui, "Actions", |ui| {
+                        ui.horizontal(|ui| {
+                            let can_scan =
+                                !self.is_scanning && self.root_path.is_some();
+                            if ui
+                                .add_enabled(
+                                    can_scan,
+                                    egui::Button::new("Lancer le scan")
+                                        .fill(
+                                            ui.visuals()
+                                                .selection
+                                                .bg_fill,
+                                        ),
+                                )
+                                .clicked()
                             {
-                                self.start_scan(path);
-                            } else {
-                                self.status = "Aucun dossier / lecteur sélectionné"
-                                    .to_string();
+                                if let Some(path) =
+                                    self.root_path.clone()
+                                {
+                                    self.start_scan(path);
+                                } else {
+                                    self.status = "Aucun dossier / lecteur sélectionné"
+                                        .to_string();
+                                }
                             }
-                        }
 
-                        let can_stop =
-                            self.is_scanning && self.cancel_flag.is_some();
-                        if ui
-                            .add_enabled(
-                                can_stop,
-                                egui::Button::new("Arrêter")
-                                    .fill(
-                                        egui::Color32::from_rgb(
-                                            120, 40, 40,
+                            let can_stop =
+                                self.is_scanning && self.cancel_flag.is_some();
+                            if ui
+                                .add_enabled(
+                                    can_stop,
+                                    egui::Button::new("Arrêter")
+                                        .fill(
+                                            egui::Color32::from_rgb(
+                                                120, 40, 40,
+                                            ),
                                         ),
-                                    ),
-                            )
-                            .clicked()
-                        {
-                            if let Some(flag) = &self.cancel_flag {
-                                flag.store(true, Ordering::Relaxed);
-                                self.status = "Arrêt du scan demandé…"
-                                    .to_string();
+                                )
+                                .clicked()
+                            {
+                                if let Some(flag) = &self.cancel_flag {
+                                    flag.store(true, Ordering::Relaxed);
+                                    self.status = "Arrêt du scan demandé…"
+                                        .to_string();
+                                }
                             }
-                        }
-                    });
+                        });
 
-                    if self.is_scanning {
-                        if let Some(progress) = &self.scan_progress {
-                            let total = progress
-                                .total_bytes
-                                .load(Ordering::Relaxed);
-                            let scanned = progress
-                                .scanned_bytes
-                                .load(Ordering::Relaxed);
-                            if total > 0 {
-                                let frac = (scanned as f32
-                                    / total as f32)
-                                    .min(1.0);
-                                ui.add_space(6.0);
-                                ui.add(
-                                    egui::ProgressBar::new(frac)
-                                        .show_percentage(),
-                                );
-                                ui.small(format!(
-                                    "{} / {}",
-                                    format_bytes(scanned),
-                                    format_bytes(total),
-                                ));
-                            } else {
-                                ui.add_space(6.0);
-                                ui.small(
-                                    "Pré-analyse en cours (estimation du volume total)…",
-                                );
+                        if self.is_scanning {
+                            if let Some(progress) = &self.scan_progress {
+                                let total = progress
+                                    .total_bytes
+                                    .load(Ordering::Relaxed);
+                                let scanned = progress
+                                    .scanned_bytes
+                                    .load(Ordering::Relaxed);
+                                if total > 0 {
+                                    let frac = (scanned as f32
+                                        / total as f32)
+                                        .min(1.0);
+                                    ui.add_space(6.0);
+                                    ui.add(
+                                        egui::ProgressBar::new(frac)
+                                            .show_percentage(),
+                                    );
+                                    ui.small(format!(
+                                        "{} / {}",
+                                        format_bytes(scanned),
+                                        format_bytes(total),
+                                    ));
+                                } else {
+                                    ui.add_space(6.0);
+                                    ui.small(
+                                        "Pré-analyse en cours (estimation du volume total)…",
+                                    );
+                                }
                             }
                         }
-                    }
-                });
+                    });
 
-                section_card(ui, "Vue", |ui| {
-                    ui.horizontal(|ui| {
-                        ui.selectable_value(
-                            &mut self.view_mode,
-                            ViewMode::Tree,
-                            "Arborescence",
-                        );
-                        ui.selectable_value(
-                            &mut self.view_mode,
-                            ViewMode::Treemap,
-                            "Treemap",
+                    section_card(ui, "Vue", |ui| {
+                        ui.horizontal(|ui| {
+                            ui.selectable_value(
+                                &mut self.view_mode,
+                                ViewMode::Tree,
+                                "Arborescence",
+                            );
+                            ui.selectable_value(
+                                &mut self.view_mode,
+                                ViewMode::Treemap,
+                                "Treemap",
+                            );
+                            ui.selectable_value(
+                                &mut self.view_mode,
+                                ViewMode::Sunburst,
+                                "Sunburst",
+                            );
+                            ui.selectable_value(
+                                &mut self.view_mode,
+                                ViewMode::TopN,
+                                "Top N",
+                            );
+                            ui.selectable_value(
+                                &mut self.view_mode,
+                                ViewMode::Extensions,
+                                "Extensions",
+                            );
+                            ui.selectable_value(
+                                &mut self.view_mode,
+                                ViewMode::Duplicates,
+                                "Doublons",
+                            );
+                            ui.selectable_value(
+                                &mut self.view_mode,
+                                ViewMode::Age,
+                                "Ancienneté",
+                            );
+                            ui.selectable_value(
+                                &mut self.view_mode,
+                                ViewMode::Owners,
+                                "Propriétaires",
+                            );
+                            ui.selectable_value(
+                                &mut self.view_mode,
+                                ViewMode::Empty,
+                                "Vides",
+                            );
+                            ui.selectable_value(
+                                &mut self.view_mode,
+                                ViewMode::DevCache,
+                                "Caches dev",
+                            );
+                            ui.selectable_value(
+                                &mut self.view_mode,
+                                ViewMode::Histogram,
+                                "Histogramme",
+                            );
+                        });
+
+                        ui.add_space(4.0);
+
+                        let sort = &mut self.tree_table.sort;
+                        let before = *sort;
+
+                        ui.horizontal(|ui| {
+                            egui::ComboBox::from_label("Tri")
+                                .selected_text(sort.column.label())
+                                .show_ui(ui, |ui| {
+                                    for column in SortColumn::ALL {
+                                        if ui
+                                            .selectable_label(
+                                                sort.column == column,
+                                                column.label(),
+                                            )
+                                            .clicked()
+                                            && sort.column != column
+                                        {
+                                            sort.select_column(column);
+                                        }
+                                    }
+                                });
+
+                            ui.selectable_value(
+                                &mut sort.descending,
+                                false,
+                                "⬆ Croissant",
+                            );
+                            ui.selectable_value(
+                                &mut sort.descending,
+                                true,
+                                "⬇ Décroissant",
+                            );
+                        });
+                        ui.checkbox(
+                            &mut sort.dirs_first,
+                            "Dossiers avant les fichiers",
                         );
-                    });
-                });
 
-                section_card(ui, "Élément sélectionné", |ui| {
-                    if let Some(node) = self.get_selected_node() {
-                        ui.label(format!("Nom : {}", node.name));
-                        ui.monospace(node.path.to_string_lossy());
-                        ui.label(format!(
-                            "Taille : {}",
-                            format_bytes(node.size)
-                        ));
-                        ui.label(format!(
-                            "Fichiers : {}",
-                            node.file_count
-                        ));
-                        ui.add_space(6.0);
-                        if ui
-                            .button(
-                                egui::RichText::new(
-                                    "Supprimer cet élément…",
-                                )
-                                .color(egui::Color32::RED),
-                            )
-                            .clicked()
-                        {
-                            self.pending_delete =
-                                Some(node.path.clone());
+                        if *sort != before {
+                            self.tree_table.pending_sort = true;
                         }
-                    } else {
-                        ui.weak("Aucun élément sélectionné.");
-                    }
-                });
+                    });
 
-                section_card(ui, "Presse-papier (fichiers/dossiers)", |ui| {
-                    match &self.clipboard_path {
-                        Some(p) => {
-                            ui.label(if self.clipboard_is_cut {
-                                "Mode : Couper"
-                            } else {
-                                "Mode : Copier"
+                    section_card(ui, "Recherche", |ui| {
+                        let filtered_size = self
+                            .tree_filter
+                            .is_active()
+                            .then(|| {
+                                self.filtered_root
+                                    .as_ref()
+                                    .map_or(0, |f| f.size)
                             });
-                            ui.monospace(p.to_string_lossy());
-                            ui.add_space(4.0);
+                        search_request = draw_search(
+                            ui,
+                            &mut self.search,
+                            filtered_size,
+                        );
+                    });
+
+                    section_card(ui, "Élément sélectionné", |ui| {
+                        let selected = self.actions.selected_paths();
+                        if selected.len() > 1 {
+                            self.draw_selection_summary(ui, &selected);
+                        } else if let Some(node) = self.get_selected_node() {
+                            ui.label(format!("Nom : {}", node.name));
+                            ui.monospace(node.path.to_string_lossy());
+                            ui.label(format!(
+                                "Taille : {}",
+                                format_bytes(node.size)
+                            ));
+                            ui.label(format!(
+                                "Fichiers : {}",
+                                node.file_count
+                            ));
+                            ui.add_space(6.0);
                             if ui
-                                .button("Vider le presse-papier")
+                                .button(
+                                    egui::RichText::new(
+                                        "Supprimer cet élément…",
+                                    )
+                                    .color(egui::Color32::RED),
+                                )
                                 .clicked()
                             {
-                                self.clipboard_path = None;
-                                self.clipboard_is_cut = false;
+                                self.actions.pending_delete =
+                                    vec![node.path.clone()];
                             }
+                        } else {
+                            ui.weak("Aucun élément sélectionné.");
                         }
-                        None => {
-                            ui.weak("Presse-papier vide.");
+                    });
+
+                    section_card(ui, "Presse-papier (fichiers/dossiers)", |ui| {
+                        match self.actions.clipboard_paths.as_slice() {
+                            [] => {
+                                ui.weak("Presse-papier vide.");
+                            }
+                            paths => {
+                                ui.label(if self.actions.clipboard_is_cut {
+                                    "Mode : Couper"
+                                } else {
+                                    "Mode : Copier"
+                                });
+                                if let [path] = paths {
+                                    ui.monospace(path.to_string_lossy());
+                                } else {
+                                    ui.label(format!("{} éléments", paths.len()));
+                                    egui::ScrollArea::vertical()
+                                        .id_source("clipboard_paths")
+                                        .max_height(100.0)
+                                        .show(ui, |ui| {
+                                            for path in paths {
+                                                ui.monospace(path.to_string_lossy());
+                                            }
+                                        });
+                                }
+                                ui.add_space(4.0);
+                                if ui
+                                    .button("Vider le presse-papier")
+                                    .clicked()
+                                {
+                                    self.actions.clipboard_paths.clear();
+                                    self.actions.clipboard_is_cut = false;
+                                }
+                            }
                         }
+                    });
+
+                    if !self.jobs.is_empty() {
+                        section_card(ui, "Opérations", |ui| {
+                            draw_jobs(ui, &mut self.jobs);
+                        });
                     }
-                });
 
-                section_card(ui, "Aide rapide", |ui| {
-                    ui.small(
-                        "• Clic gauche : sélection dans l’arborescence ou la treemap.\n\
-                         • Clic droit : menu contextuel (Propriétés, Copier chemin, Copier/Couper, Supprimer, Coller ici).\n\
-                         • Les erreurs d’accès (permissions, fichiers spéciaux…) sont ignorées.\n\
-                         • L’arrêt du scan est coopératif : les threads finissent proprement.",
-                    );
+                    if !self.journal.is_empty() {
+                        section_card(ui, "Historique des opérations", |ui| {
+                            let undoing = self.jobs.is_undoing();
+                            if let Some(id) = draw_journal(ui, &self.journal, undoing) {
+                                if let Some(entry) = self.journal.entry(id) {
+                                    self.jobs.enqueue_undo(entry);
+                                }
+                            }
+                        });
+                    }
+
+                    section_card(ui, "Journal d'audit", |ui| {
+                        ui.small("Suppressions, déplacements, renommages et écrasements faits par l'application.");
+                        if ui.button("Afficher le journal d'audit…").clicked() {
+                            self.audit.show();
+                        }
+                    });
+
+                    section_card(ui, "Préférences", |ui| {
+                        draw_settings(ui, &mut self.settings);
+                    });
+
+                    section_card(ui, "Aide rapide", |ui| {
+                        ui.small(
+                            "• Clic gauche : sélection dans l’arborescence ou la treemap.\n\
+                             • Ctrl + clic : ajouter/retirer un élément, Maj + clic : sélectionner une plage.\n\
+                             • Clic droit : menu contextuel (Propriétés, Ouvrir, gestionnaire de fichiers, terminal, Copier chemin, Renommer, Compresser, Copier/Couper, Supprimer, Coller ici).\n\
+                             • F2 : renommer l’élément sélectionné dans l’arborescence.\n\
+                             • Sunburst : double-clic sur un dossier pour zoomer, clic au centre pour remonter.\n\
+                             • Les erreurs d’accès (permissions, fichiers spéciaux…) sont ignorées.\n\
+                             • L’arrêt du scan est coopératif : les threads finissent proprement.",
+                        );
+                    });
                 });
             });
+
+        match search_request {
+            Some(SearchRequest::Apply) => {
+                let mut filter = self.tree_filter.clone();
+                self.search.fill(&mut filter);
+                match filter.validate() {
+                    Ok(()) => {
+                        self.search.error = None;
+                        self.set_filter(filter);
+                    }
+                    Err(e) => self.search.error = Some(e),
+                }
+            }
+            Some(SearchRequest::Clear) => {
+                self.search.error = None;
+                self.set_filter(TreeFilter {
+                    older_than: self.tree_filter.older_than,
+                    ..TreeFilter::default()
+                });
+            }
+            Some(SearchRequest::Goto(path)) => {
+                if let Some(root) = &self.root_node {
+                    self.tree_table.reveal(&root.path, &path);
+                }
+                self.actions.selected_node_path = Some(path);
+            }
+            None => {}
+        }
     }
 
     fn draw_central_panel(&mut self, ctx: &egui::Context) {
@@ -538,6 +993,15 @@ impl TreeSizeApp {
             if let Some(root) = &self.root_node {
                 let total_size = root.size;
                 let total_files = root.file_count;
+                // Arbre affiché par les vues hiérarchiques (filtré ou complet)
+                let shown = if self.tree_filter.is_active() {
+                    self.filtered_root.as_ref()
+                } else {
+                    Some(root)
+                };
+                let mut clear_filter = false;
+                let mut requested_filter = None;
+                let mut histogram_filter = None;
 
                 section_card(ui, "Résultats du scan", |ui| {
                     ui.horizontal(|ui| {
@@ -558,37 +1022,55 @@ impl TreeSizeApp {
                             total_files
                         ));
                     });
+
+                    if self.tree_filter.is_active() {
+                        ui.add_space(4.0);
+                        ui.horizontal(|ui| {
+                            ui.colored_label(
+                                egui::Color32::from_rgb(230, 180, 60),
+                                format!(
+                                    "Filtre actif ({})",
+                                    self.tree_filter.describe()
+                                ),
+                            );
+                            match shown {
+                                Some(filtered) => ui.label(format!(
+                                    "{} dans {} fichiers",
+                                    format_bytes(filtered.size),
+                                    filtered.file_count
+                                )),
+                                None => ui.label("aucun élément retenu"),
+                            };
+                            if ui.button("Retirer le filtre").clicked() {
+                                clear_filter = true;
+                            }
+                        });
+                    }
                 });
 
                 ui.add_space(4.0);
 
                 match self.view_mode {
                     ViewMode::Tree => {
-                        section_card(
-                            ui,
-                            "Arborescence (triée par taille)",
-                            |ui| {
-                                egui::ScrollArea::vertical()
-                                    .auto_shrink([false, false])
-                                    .show(ui, |ui| {
-                                        draw_node_recursive(
-                                            ui,
-                                            root,
-                                            total_size,
-                                            0,
-                                            &mut self
-                                                .selected_node_path,
-                                            &mut self.pending_delete,
-                                            &mut self
-                                                .clipboard_path,
-                                            &mut self
-                                                .clipboard_is_cut,
-                                            &mut self
-                                                .pending_paste_dest,
-                                        );
-                                    });
-                            },
-                        );
+                        section_card(ui, "Arborescence", |ui| {
+                            ui.small(
+                                "Clic sur un en-tête : trier. \
+                                 ←/→ : replier/déplier, ↑/↓ : naviguer, \
+                                 double-clic ou Entrée : ouvrir/fermer.",
+                            );
+                            ui.add_space(4.0);
+
+                            if let Some(shown) = shown {
+                                draw_tree_table(
+                                    ui,
+                                    shown,
+                                    &mut self.tree_table,
+                                    &mut self.actions,
+                                );
+                            } else {
+                                ui.weak("Aucun élément ne correspond au filtre.");
+                            }
+                        });
                     }
                     ViewMode::Treemap => {
                         section_card(ui, "Treemap façon WinDirStat", |ui| {
@@ -598,18 +1080,142 @@ impl TreeSizeApp {
                             );
                             ui.add_space(6.0);
 
-                            draw_treemap(
+                            if let Some(shown) = shown {
+                                draw_treemap(ui, shown, &mut self.actions);
+                            } else {
+                                ui.weak("Aucun élément ne correspond au filtre.");
+                            }
+                        });
+                    }
+                    ViewMode::Sunburst => {
+                        section_card(ui, "Sunburst (vue radiale)", |ui| {
+                            ui.small(
+                                "Chaque anneau est un niveau de profondeur. \
+                                 Double-clic sur un dossier : zoomer, \
+                                 clic au centre : remonter d'un niveau.",
+                            );
+                            ui.add_space(6.0);
+
+                            if let Some(shown) = shown {
+                                draw_sunburst(
+                                    ui,
+                                    shown,
+                                    &mut self.sunburst,
+                                    &mut self.actions,
+                                );
+                            } else {
+                                ui.weak("Aucun élément ne correspond au filtre.");
+                            }
+                        });
+                    }
+                    ViewMode::TopN => {
+                        section_card(ui, "Plus gros éléments", |ui| {
+                            ui.small(
+                                "Double-clic : afficher dans l'arborescence.",
+                            );
+                            ui.add_space(4.0);
+
+                            draw_top_n(
+                                ui,
+                                root,
+                                &mut self.top_n,
+                                &mut self.actions,
+                            );
+                        });
+                    }
+                    ViewMode::Extensions => {
+                        section_card(ui, "Statistiques par extension", |ui| {
+                            draw_extensions(
+                                ui,
+                                root,
+                                &mut self.extensions,
+                                &mut self.actions,
+                            );
+                        });
+                    }
+                    ViewMode::Duplicates => {
+                        section_card(ui, "Fichiers en double", |ui| {
+                            draw_duplicates(
+                                ui,
+                                root,
+                                &mut self.duplicates,
+                                &mut self.actions,
+                            );
+                        });
+                    }
+                    ViewMode::Age => {
+                        section_card(ui, "Ancienneté des fichiers", |ui| {
+                            requested_filter = draw_age(
+                                ui,
+                                root,
+                                &mut self.age,
+                                &mut self.actions,
+                            );
+                        });
+                    }
+                    ViewMode::Owners => {
+                        section_card(ui, "Occupation par propriétaire", |ui| {
+                            draw_owners(
+                                ui,
+                                root,
+                                &mut self.owners,
+                                &mut self.actions,
+                            );
+                        });
+                    }
+                    ViewMode::Empty => {
+                        section_card(ui, "Dossiers vides et fichiers de 0 octet", |ui| {
+                            draw_empty(
+                                ui,
+                                root,
+                                &mut self.empty,
+                                &mut self.actions,
+                            );
+                        });
+                    }
+                    ViewMode::DevCache => {
+                        section_card(ui, "Caches de développement et artefacts de build", |ui| {
+                            draw_dev_cache(
                                 ui,
                                 root,
-                                &mut self.selected_node_path,
-                                &mut self.pending_delete,
-                                &mut self.clipboard_path,
-                                &mut self.clipboard_is_cut,
-                                &mut self.pending_paste_dest,
+                                &mut self.dev_cache,
+                                &mut self.actions,
+                            );
+                        });
+                    }
+                    ViewMode::Histogram => {
+                        section_card(ui, "Répartition des tailles de fichiers", |ui| {
+                            let selected_dir = self
+                                .actions
+                                .selected_node_path
+                                .as_ref()
+                                .and_then(|p| find_node_by_path(root, p))
+                                .filter(|n| n.is_dir);
+                            histogram_filter = draw_histogram(
+                                ui,
+                                root,
+                                selected_dir,
+                                &mut self.histogram,
                             );
                         });
                     }
                 }
+
+                if clear_filter {
+                    self.set_filter(TreeFilter::default());
+                }
+                if let Some(older_than) = requested_filter {
+                    let filter = TreeFilter {
+                        older_than: Some(older_than),
+                        ..self.tree_filter.clone()
+                    };
+                    self.set_filter(filter);
+                    self.view_mode = ViewMode::Tree;
+                }
+                if let Some(filter) = histogram_filter {
+                    self.set_filter(filter);
+                    self.view_mode = ViewMode::Tree;
+                }
             } else {
                 ui.centered_and_justified(|ui| {
                     ui.label(
@@ -621,76 +1227,344 @@ impl TreeSizeApp {
     }
 
     fn draw_delete_window(&mut self, ctx: &egui::Context) {
-        if let Some(path) = self.pending_delete.clone() {
-            egui::Window::new("Confirmer la suppression")
-                .collapsible(false)
-                .resizable(false)
-                .anchor(
-                    egui::Align2::CENTER_CENTER,
-                    egui::vec2(0.0, 0.0),
-                )
-                .show(ctx, |ui| {
+        if self.actions.pending_delete.is_empty() {
+            self.delete_preview = None;
+            self.actions.delete_only_empty = false;
+            return;
+        }
+
+        let paths = self.actions.pending_delete.clone();
+        if self.delete_preview.as_ref().map(|p| &p.paths) != Some(&paths) {
+            self.delete_preview = self
+                .root_node
+                .as_ref()
+                .map(|root| DeletePreview::compute(root, &paths));
+        }
+        // Rendu à la fin : la fenêtre emprunte self
+        let preview = self.delete_preview.take();
+        egui::Window::new("Confirmer la suppression")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(
+                egui::Align2::CENTER_CENTER,
+                egui::vec2(0.0, 0.0),
+            )
+            .show(ctx, |ui| {
+                if let [path] = paths.as_slice() {
                     ui.label("Es-tu sûr de vouloir supprimer :");
                     ui.monospace(path.to_string_lossy());
+                } else {
+                    ui.label(format!(
+                        "Es-tu sûr de vouloir supprimer ces {} éléments :",
+                        paths.len()
+                    ));
+                    egui::ScrollArea::vertical()
+                        .max_height(200.0)
+                        .show(ui, |ui| {
+                            for path in &paths {
+                                ui.monospace(path.to_string_lossy());
+                            }
+                        });
+                }
+                if let Some(preview) = &preview {
                     ui.add_space(8.0);
+                    let permanent = self.permanent_delete || !trash::SUPPORTED;
+                    draw_delete_preview(ui, preview, permanent);
+                }
+                ui.add_space(8.0);
+                if trash::SUPPORTED {
+                    ui.label("Les éléments pourront être restaurés depuis la corbeille.");
+                    ui.checkbox(
+                        &mut self.permanent_delete,
+                        "Supprimer définitivement (sans passer par la corbeille)",
+                    );
+                }
+                let permanent = self.permanent_delete || !trash::SUPPORTED;
+                if permanent {
                     ui.colored_label(
                         egui::Color32::RED,
                         "Attention : la suppression est définitive.",
                     );
-                    ui.add_space(12.0);
+                }
+                ui.add_space(12.0);
+
+                ui.horizontal(|ui| {
+                    if ui.button("Annuler").clicked() {
+                        self.actions.pending_delete.clear();
+                        self.permanent_delete = false;
+                    }
+                    let confirm = if permanent {
+                        egui::RichText::new("Supprimer définitivement")
+                            .color(egui::Color32::RED)
+                    } else {
+                        egui::RichText::new("Mettre à la corbeille")
+                    };
+                    if ui.button(confirm).clicked() {
+                        if self.actions.delete_only_empty {
+                            self.delete_still_empty(&paths, permanent);
+                        } else {
+                            self.delete_paths(&paths, permanent);
+                        }
+                        self.actions.pending_delete.clear();
+                        self.permanent_delete = false;
+                    }
+                });
+            });
+        self.delete_preview = preview;
+    }
+
+    /// Ajoute la mise à la corbeille (ou la suppression définitive) des
+    /// éléments confirmés à la file des opérations.
+    fn delete_paths(&mut self, paths: &[PathBuf], permanent: bool) {
+        // Les éléments contenus dans un dossier du lot partent avec lui
+        let paths = top_level_paths(paths);
+        let operation = ProtectedOperation::Delete { permanent };
+        match ProtectedPrompt::new(operation, paths.clone()) {
+            Some(prompt) => {
+                self.status = "Suppression refusée : chemin protégé.".to_string();
+                self.protected_prompt = Some(prompt);
+            }
+            None => self.enqueue_delete(paths, permanent),
+        }
+    }
+
+    /// Suppression depuis la vue des éléments vides : ceux qui ne le sont
+    /// plus sur le disque sont écartés.
+    fn delete_still_empty(&mut self, paths: &[PathBuf], permanent: bool) {
+        let (empty, changed): (Vec<PathBuf>, Vec<PathBuf>) =
+            paths.iter().cloned().partition(|p| empty::still_empty(p));
+        if !empty.is_empty() {
+            self.delete_paths(&empty, permanent);
+        }
+        if let Some(first) = changed.first() {
+            self.status = format!(
+                "{} élément(s) plus vide(s), non supprimé(s) : {}",
+                changed.len(),
+                first.to_string_lossy()
+            );
+            self.rescan_after_jobs = true;
+        }
+    }
+
+    fn enqueue_delete(&mut self, paths: Vec<PathBuf>, permanent: bool) {
+        let kind = if permanent {
+            JobKind::Delete
+        } else {
+            JobKind::Trash
+        };
+        self.jobs.enqueue(kind, paths, None);
+        self.status = format!("{} en cours…", kind.label());
+    }
+
+    /// Refus d'une opération sur des chemins protégés : annulation, suite
+    /// sans eux, ou passage outre après saisie des chemins.
+    fn draw_protected_window(&mut self, ctx: &egui::Context) {
+        let Some(prompt) = &mut self.protected_prompt else {
+            return;
+        };
+        let paths = match draw_protected_window(ctx, prompt) {
+            PromptAction::None => return,
+            PromptAction::Cancel => {
+                self.protected_prompt = None;
+                self.status = "Opération annulée.".to_string();
+                return;
+            }
+            PromptAction::SkipProtected(others) => others,
+            PromptAction::Override => {
+                protect::grant(&prompt.protected_paths());
+                prompt.paths.clone()
+            }
+        };
+        let Some(prompt) = self.protected_prompt.take() else {
+            return;
+        };
+        match prompt.operation {
+            ProtectedOperation::Delete { permanent } => self.enqueue_delete(paths, permanent),
+            ProtectedOperation::Move { dest } => self.enqueue_move(paths, &dest),
+        }
+    }
+
+    /// Ouvre l'éditeur de renommage : dans la ligne de l'arborescence, ou
+    /// en fenêtre depuis les autres vues.
+    fn begin_rename(&mut self, path: PathBuf) {
+        if self.root_path.as_deref() == Some(path.as_path()) {
+            self.status = "La racine du scan ne peut pas être renommée.".to_string();
+            return;
+        }
+        let inline = self.view_mode == ViewMode::Tree;
+        let Some(edit) = RenameEdit::new(path.clone(), inline) else {
+            self.status = "Renommage impossible : le nom actuel n'est pas en UTF-8.".to_string();
+            return;
+        };
+        if inline {
+            self.reveal_in_tree(path);
+        }
+        self.actions.renaming = Some(edit);
+    }
+
+    /// Fenêtre de renommage (l'éditeur de l'arborescence est dans la ligne).
+    fn draw_rename_window(&mut self, ctx: &egui::Context) {
+        let Some(edit) = &mut self.actions.renaming else {
+            return;
+        };
+        // L'éditeur dans la ligne disparaît si l'on quitte l'arborescence
+        if edit.inline && self.view_mode != ViewMode::Tree {
+            self.actions.renaming = None;
+            return;
+        }
+        if edit.inline {
+            return;
+        }
+        if let RenameAction::Cancel = draw_rename_window(ctx, edit) {
+            self.actions.renaming = None;
+        }
+    }
+
+    /// Choix du format d'archive et, une fois l'archive vérifiée,
+    /// proposition de supprimer l'original (confirmation habituelle).
+    fn draw_archive_windows(&mut self, ctx: &egui::Context) {
+        if let Some(dialog) = &mut self.compress_dialog {
+            match draw_compress_window(ctx, dialog) {
+                CompressAction::None => {}
+                CompressAction::Start(dest) => {
+                    let source = dialog.source.clone();
+                    self.jobs
+                        .enqueue(JobKind::Compress, vec![source], Some(dest));
+                    self.status = "Compression en cours…".to_string();
+                    self.compress_dialog = None;
+                }
+                CompressAction::Cancel => self.compress_dialog = None,
+            }
+        }
 
-                    let mut close = false;
+        if let Some(review) = &self.archive_review {
+            match draw_archive_review(ctx, review) {
+                ReviewAction::None => {}
+                ReviewAction::RemoveOriginal if review.can_remove_original() => {
+                    self.actions.pending_delete = vec![review.source.clone()];
+                    self.archive_review = None;
+                }
+                ReviewAction::RemoveOriginal => {}
+                ReviewAction::Keep => self.archive_review = None,
+            }
+        }
+    }
 
-                    ui.horizontal(|ui| {
-                        if ui.button("Annuler").clicked() {
-                            self.pending_delete = None;
-                            close = true;
-                        }
-                        if ui
-                            .button(
-                                egui::RichText::new("Supprimer")
-                                    .color(egui::Color32::RED),
-                            )
-                            .clicked()
-                        {
-                            match delete_path(&path) {
-                                Ok(()) => {
-                                    self.status = format!(
-                                        "Supprimé : {}",
-                                        path.to_string_lossy()
-                                    );
-                                    if let Some(root) =
-                                        self.root_path.clone()
-                                    {
-                                        self.start_scan(root);
-                                    }
-                                }
-                                Err(e) => {
-                                    self.status = format!(
-                                        "Erreur suppression : {}",
-                                        e
-                                    );
-                                }
-                            }
-                            self.pending_delete = None;
-                            close = true;
-                        }
-                    });
+    /// Renomme sur le disque puis met à jour l'arbre et les chemins
+    /// mémorisés (sélection, presse-papier, dossiers dépliés), sans rescan.
+    fn apply_rename(&mut self) {
+        let Some(mut edit) = self.actions.renaming.take() else {
+            return;
+        };
+        let old = edit.path.clone();
+        if edit.is_unchanged() {
+            return;
+        }
+        let new = match validate_name(&old, &edit.name) {
+            Ok(new) => new,
+            Err(e) => {
+                edit.reject(e);
+                self.actions.renaming = Some(edit);
+                return;
+            }
+        };
+        let bytes = self
+            .root_node
+            .as_ref()
+            .and_then(|root| find_node_by_path(root, &old))
+            .map_or(0, |node| node.size);
+        // Renommer un chemin protégé le ferait disparaître comme un déplacement
+        let protected = protect::check(&old);
+        let refused = protected.is_err();
+        let renamed = protected.and_then(|()| rename_path(&old, &new));
+        audit::record(AuditOp::Rename, &old, Some(&new), bytes, &renamed);
+        if let Err(e) = renamed {
+            if refused {
+                self.status = "Renommage refusé : chemin protégé.".to_string();
+                edit.reject(e.to_string());
+            } else {
+                edit.reject(format!("Renommage impossible : {e}"));
+            }
+            self.actions.renaming = Some(edit);
+            return;
+        }
 
-                    if close {
-                        // la fenêtre disparaîtra car pending_delete = None
-                    }
-                });
+        let mut in_tree = false;
+        if let Some(root) = &mut self.root_node {
+            let root = Arc::make_mut(root);
+            in_tree = rename_node(root, &old, &new);
+            // Le nouveau nom peut changer la place de l'élément
+            root.sort_recursive(&self.tree_table.sort);
         }
+        if !in_tree {
+            self.status = format!("Renommé : {}", new.to_string_lossy());
+            return;
+        }
+
+        let update = |path: &mut PathBuf| {
+            if let Some(renamed) = reprefix(path, &old, &new) {
+                *path = renamed;
+            }
+        };
+        let actions = &mut self.actions;
+        actions.selected_node_path.iter_mut().for_each(update);
+        actions.selection = actions
+            .selection
+            .drain()
+            .map(|p| reprefix(&p, &old, &new).unwrap_or(p))
+            .collect();
+        actions.clipboard_paths.iter_mut().for_each(update);
+        actions.pending_delete.iter_mut().for_each(update);
+        actions.pending_paste_dest.iter_mut().for_each(update);
+        self.tree_table.expanded = self
+            .tree_table
+            .expanded
+            .drain()
+            .map(|p| reprefix(&p, &old, &new).unwrap_or(p))
+            .collect();
+        self.sunburst.focus.iter_mut().for_each(update);
+        self.duplicates.rename_paths(&old, &new);
+
+        // Analyses indexées par chemin : recalculées à la demande
+        self.top_n.invalidate();
+        self.age.invalidate();
+        self.owners.invalidate();
+        self.empty.invalidate();
+        self.dev_cache.invalidate();
+        self.histogram.invalidate();
+        self.extensions.clear();
+        self.apply_filter();
+
+        self.status = format!(
+            "Renommé : {} → {}",
+            old.to_string_lossy(),
+            new.file_name().unwrap_or_default().to_string_lossy()
+        );
     }
 
     fn start_scan(&mut self, path: PathBuf) {
+        // Les doublons sont indexés par chemin : on ne les garde que pour la même racine
+        if self.root_node.as_ref().is_none_or(|r| r.path != path) {
+            self.duplicates = DuplicatesState::default();
+        }
+
         self.is_scanning = true;
+        protect::set_scan_root(Some(path.clone()));
         self.status =
             format!("Scan en cours pour : {}", path.to_string_lossy());
         self.root_node = None;
-        self.selected_node_path = None;
-        self.pending_delete = None;
+        self.filtered_root = None;
+        self.actions.clear_selection();
+        self.actions.pending_delete.clear();
+        self.tree_table.reset(&path);
+        self.sunburst.focus = None;
+        self.top_n.invalidate();
+        self.age.invalidate();
+        self.owners.invalidate();
+        self.empty.invalidate();
+        self.dev_cache.invalidate();
+        self.histogram.invalidate();
+        self.extensions.clear();
 
         let (tx, rx) = unbounded::<ScanResult>();
         let cancel = Arc::new(AtomicBool::new(false));
@@ -699,66 +1573,209 @@ impl TreeSizeApp {
         let progress = Arc::new(ScanProgress::default());
         let progress_clone = progress.clone();
 
+        // Tri de la session, appliqué dans le thread de scan
+        let sort = self.tree_table.sort;
+
         self.scan_receiver = Some(rx);
         self.cancel_flag = Some(cancel);
         self.scan_progress = Some(progress);
 
         thread::spawn(move || {
-            let result =
-                scan_directory_parallel(&path, &cancel_clone, &progress_clone);
+            let result = scan_directory_parallel(
+                &path,
+                &cancel_clone,
+                &progress_clone,
+                &sort,
+            );
             let _ = tx.send(result);
         });
     }
 
-    fn get_selected_node(&self) -> Option<&Node> {
-        let root = self.root_node.as_ref()?;
-        let path = self.selected_node_path.as_ref()?;
-        find_node_by_path(root, path)
+    /// Applique le tri courant à l'arbre déjà scanné (sans rescan).
+    /// Pendant un scan, le tri reste en attente jusqu'à l'arrivée du résultat.
+    fn apply_sort(&mut self) {
+        if let Some(root) = &mut self.root_node {
+            Arc::make_mut(root).sort_recursive(&self.tree_table.sort);
+            self.tree_table.pending_sort = false;
+        }
+        if let Some(filtered) = &mut self.filtered_root {
+            Arc::make_mut(filtered).sort_recursive(&self.tree_table.sort);
+            order_matches(filtered, self.search.matches_mut());
+        }
     }
 
-    fn handle_paste(&mut self, dest_dir: &Path) {
-        let src = match self.clipboard_path.clone() {
-            Some(p) => p,
-            None => {
-                self.status =
-                    "Presse-papier vide, rien à coller.".to_string();
-                return;
-            }
-        };
+    /// Remplace le filtre de l'arborescence et recalcule la copie élaguée.
+    fn set_filter(&mut self, filter: TreeFilter) {
+        self.tree_filter = filter;
+        self.apply_filter();
+    }
 
-        let is_cut = self.clipboard_is_cut;
+    /// (Re)construit l'arbre filtré à partir de l'arbre complet.
+    fn apply_filter(&mut self) {
+        self.filtered_root = None;
+        self.search.set_matches(Vec::new());
 
-        match copy_or_move(&src, dest_dir, is_cut) {
-            Ok(()) => {
-                if is_cut {
-                    self.clipboard_path = None;
-                    self.clipboard_is_cut = false;
-                    self.status = format!(
-                        "Déplacé vers : {}",
-                        dest_dir.to_string_lossy()
-                    );
-                } else {
-                    self.status = format!(
-                        "Copié vers : {}",
-                        dest_dir.to_string_lossy()
-                    );
+        let Some(root) = &self.root_node else {
+            return;
+        };
+        if !self.tree_filter.is_active() {
+            return;
+        }
+
+        match filter_tree(root, &self.tree_filter) {
+            Ok(outcome) => {
+                let mut matches = outcome.matches;
+                if let Some(mut filtered) = outcome.root {
+                    // Les tailles ont changé : le tri par taille doit être refait
+                    filtered.sort_recursive(&self.tree_table.sort);
+                    order_matches(&filtered, &mut matches);
+                    self.filtered_root = Some(Arc::new(filtered));
                 }
+                self.search.set_matches(matches);
+            }
+            Err(e) => self.search.error = Some(e),
+        }
+    }
+
+    /// Bascule sur l'arborescence, déplie les ancêtres et sélectionne l'élément.
+    fn reveal_in_tree(&mut self, path: PathBuf) {
+        // Un élément masqué par le filtre ne peut pas être affiché : on le retire
+        if self.tree_filter.is_active()
+            && self
+                .filtered_root
+                .as_ref()
+                .and_then(|f| find_node_by_path(f, &path))
+                .is_none()
+        {
+            self.set_filter(TreeFilter::default());
+        }
+        if let Some(root) = &self.root_node {
+            self.tree_table.reveal(&root.path, &path);
+        }
+        self.actions.selected_node_path = Some(path);
+        self.view_mode = ViewMode::Tree;
+    }
+
+    fn get_selected_node(&self) -> Option<&Node> {
+        let root = self.root_node.as_ref()?;
+        let path = self.actions.selected_node_path.as_ref()?;
+        find_node_by_path(root, path)
+    }
 
-                if let Some(root) = self.root_path.clone() {
-                    self.start_scan(root);
+    /// Résumé d'une sélection multiple et actions par lot.
+    fn draw_selection_summary(&mut self, ui: &mut egui::Ui, selected: &[PathBuf]) {
+        let top = top_level_paths(selected);
+        let nodes: Vec<&Node> = self
+            .root_node
+            .as_ref()
+            .map(|root| {
+                top.iter()
+                    .filter_map(|p| find_node_by_path(root, p))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let size: u64 = nodes.iter().map(|n| n.size).sum();
+        let files: u64 = nodes.iter().map(|n| n.file_count).sum();
+
+        ui.label(format!("{} éléments sélectionnés", selected.len()));
+        ui.label(format!("Taille totale : {}", format_bytes(size)));
+        ui.label(format!("Fichiers : {files}"));
+        egui::ScrollArea::vertical()
+            .id_source("selection_paths")
+            .max_height(120.0)
+            .show(ui, |ui| {
+                for path in selected {
+                    ui.monospace(path.to_string_lossy());
                 }
+            });
+        ui.add_space(6.0);
+
+        ui.horizontal_wrapped(|ui| {
+            if ui.button("Copier").clicked() {
+                self.actions.clipboard_paths = top.clone();
+                self.actions.clipboard_is_cut = false;
             }
-            Err(e) => {
-                self.status =
-                    format!("Erreur copie/déplacement : {}", e);
+            if ui.button("Couper").clicked() {
+                self.actions.clipboard_paths = top.clone();
+                self.actions.clipboard_is_cut = true;
+            }
+            if ui.button("Désélectionner").clicked() {
+                self.actions.clear_selection();
+            }
+        });
+        if ui
+            .button(
+                egui::RichText::new(format!(
+                    "Supprimer la sélection ({})…",
+                    selected.len()
+                ))
+                .color(egui::Color32::RED),
+            )
+            .clicked()
+        {
+            self.actions.pending_delete = selected.to_vec();
+        }
+    }
+
+    /// Ajoute le collage de tous les éléments du presse-papier à la file
+    /// des opérations.
+    fn handle_paste(&mut self, dest_dir: &Path) {
+        if self.actions.clipboard_paths.is_empty() {
+            self.status = "Presse-papier vide, rien à coller.".to_string();
+            return;
+        }
+
+        let kind = if self.actions.clipboard_is_cut {
+            JobKind::Move
+        } else {
+            JobKind::Copy
+        };
+        if kind == JobKind::Move {
+            let sources = self.actions.clipboard_paths.clone();
+            let operation = ProtectedOperation::Move {
+                dest: dest_dir.to_path_buf(),
+            };
+            if let Some(prompt) = ProtectedPrompt::new(operation, sources.clone()) {
+                self.status = "Déplacement refusé : chemin protégé.".to_string();
+                self.protected_prompt = Some(prompt);
+                return;
             }
+            self.enqueue_move(sources, dest_dir);
+            return;
         }
+        self.jobs.enqueue(
+            kind,
+            self.actions.clipboard_paths.clone(),
+            Some(dest_dir.to_path_buf()),
+        );
+        self.status = format!(
+            "{} en cours vers : {}",
+            kind.label(),
+            dest_dir.to_string_lossy()
+        );
+    }
+
+    fn enqueue_move(&mut self, sources: Vec<PathBuf>, dest_dir: &Path) {
+        // Les éléments coupés ne peuvent être collés qu'une fois
+        self.actions.clipboard_is_cut = false;
+        self.actions.clipboard_paths.clear();
+        self.jobs
+            .enqueue(JobKind::Move, sources, Some(dest_dir.to_path_buf()));
+        self.status = format!(
+            "{} en cours vers : {}",
+            JobKind::Move.label(),
+            dest_dir.to_string_lossy()
+        );
     }
 }
 
 impl eframe::App for TreeSizeApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        if self.is_scanning {
+        if self.is_scanning
+            || self.extensions.is_computing()
+            || self.duplicates.is_running()
+            || self.jobs.is_busy()
+        {
             ctx.request_repaint();
         }
 
@@ -775,7 +1792,11 @@ impl eframe::App for TreeSizeApp {
                         self.root_node = None;
                             self.status = err;
                     } else {
-                        self.root_node = result.root_node;
+                        self.root_node = result.root_node.map(Arc::new);
+                        if let Some(root) = &self.root_node {
+                            self.extensions.start(root.clone());
+                        }
+                        self.apply_filter();
                         self.status = format!(
                             "Scan terminé pour : {}",
                             result.root_path.to_string_lossy()
@@ -787,13 +1808,92 @@ impl eframe::App for TreeSizeApp {
             }
         }
 
+        self.extensions.poll();
+        self.duplicates.poll();
+        if let Some(error) = self.launcher.poll() {
+            self.status = error;
+        }
+
+        let mut jobs_finished = false;
+        for report in self.jobs.poll() {
+            jobs_finished = true;
+            self.status = report.summary();
+            protect::revoke(&report.sources);
+            if let Some(kind) = report.kind.journal_kind() {
+                self.journal.record(kind, report.outcome.items.clone());
+            }
+            // Annulation partielle : seuls les éléments rétablis quittent le journal
+            if let (Some(id), true) = (report.undo_of, report.outcome.done > 0) {
+                self.journal
+                    .keep_items(id, report.outcome.not_undone.clone());
+            }
+            if let (Some(archived), [source]) =
+                (&report.outcome.archived, report.sources.as_slice())
+            {
+                self.archive_review = Some(ArchiveReview {
+                    source: source.clone(),
+                    archived: archived.clone(),
+                });
+            }
+            if report.outcome.done > 0 || !report.outcome.errors.is_empty() {
+                self.rescan_after_jobs = true;
+            }
+        }
+        if jobs_finished && self.audit.open {
+            self.audit.reload();
+        }
+        if self.rescan_after_jobs && !self.jobs.is_busy() {
+            self.rescan_after_jobs = false;
+            if let Some(root) = self.root_path.clone() {
+                self.start_scan(root);
+            }
+        }
+
         self.draw_top_bar(ctx);
         self.draw_left_panel(ctx);
         self.draw_central_panel(ctx);
         self.draw_delete_window(ctx);
+        self.draw_protected_window(ctx);
+        draw_audit_window(ctx, &mut self.audit);
+        self.draw_rename_window(ctx);
+        self.draw_archive_windows(ctx);
+        self.jobs.draw_conflicts(ctx);
+
+        if self.tree_table.pending_sort {
+            self.apply_sort();
+        }
+
+        if let Some(path) = self.actions.pending_reveal.take() {
+            self.reveal_in_tree(path);
+        }
+
+        if let Some((action, path)) = self.actions.pending_launch.take() {
+            self.status = format!("{} : {}", action.label(), path.to_string_lossy());
+            self.launcher.launch(action, path, self.settings.terminal.clone());
+        }
+        if let Some(path) = self.actions.pending_compress.take() {
+            self.compress_dialog = Some(CompressDialog::new(path));
+        }
+        if let Some(job) = self.actions.pending_duplicate_job.take() {
+            self.jobs
+                .enqueue_duplicates(job.kind, job.copies, job.keep, job.size);
+            self.status = format!("{} en cours…", job.kind.label());
+        }
+        if let Some(path) = self.actions.pending_rename.take() {
+            self.begin_rename(path);
+        }
+        if self.actions.renaming.as_ref().is_some_and(|e| e.submitted) {
+            self.apply_rename();
+        }
+
+        if std::mem::take(&mut self.actions.rescan_requested) {
+            if let Some(root) = self.root_path.clone() {
+                self.start_scan(root);
+            }
+        }
 
         // Traitement différé du "Coller ici"
-        if let Some(dest) = self.pending_paste_dest.take() {
+        if let Some(dest) = self.actions.pending_paste_dest.take() {
             self.handle_paste(&dest);
         }
     }
@@ -819,173 +1919,83 @@ fn section_card(
     ui.add_space(6.0);
 }
 
-/// Dessin récursif d'un Node en arbre (vue arborescence) + clic gauche/droit.
-fn draw_node_recursive(
+/// Menu contextuel commun aux vues (arborescence, treemap…) pour un élément.
+fn node_context_menu(
     ui: &mut egui::Ui,
-    node: &Node,
-    root_size: u64,
-    indent_level: usize,
-    selected_node_path: &mut Option<PathBuf>,
-    pending_delete: &mut Option<PathBuf>,
-    clipboard_path: &mut Option<PathBuf>,
-    clipboard_is_cut: &mut bool,
-    pending_paste_dest: &mut Option<PathBuf>,
+    path: &Path,
+    is_dir: bool,
+    actions: &mut NodeActions,
 ) {
-    let indent = 18.0 * indent_level as f32;
-    let percentage = if root_size > 0 {
-        (node.size as f64 / root_size as f64) * 100.0
-    } else {
-        0.0
-    };
-
-    let label = if node.is_dir {
-        format!(
-            "{} ({} | {} fichiers | {:.2}%)",
-            node.name,
-            format_bytes(node.size),
-            node.file_count,
-            percentage
-        )
+    if ui.button("Propriétés").clicked() {
+        actions.selected_node_path = Some(path.to_path_buf());
+        ui.close_menu();
+    }
+    for action in [Launch::Open, Launch::ShowInFileManager, Launch::Terminal] {
+        if ui.button(action.label()).clicked() {
+            actions.pending_launch = Some((action, path.to_path_buf()));
+            ui.close_menu();
+        }
+    }
+    if ui.button("Afficher dans l'arborescence").clicked() {
+        actions.pending_reveal = Some(path.to_path_buf());
+        ui.close_menu();
+    }
+    if ui.button("Copier le chemin").clicked() {
+        let text = path.to_string_lossy().to_string();
+        ui.output_mut(|o| o.copied_text = text);
+        ui.close_menu();
+    }
+    if ui.button("Renommer…").clicked() {
+        actions.pending_rename = Some(path.to_path_buf());
+        ui.close_menu();
+    }
+    if ui.button("Compresser…").clicked() {
+        actions.pending_compress = Some(path.to_path_buf());
+        ui.close_menu();
+    }
+    // Copier/Couper/Supprimer portent sur toute la sélection si elle contient l'élément
+    let targets = actions.targets(path);
+    let count = if targets.len() > 1 {
+        format!(" ({})", targets.len())
     } else {
-        format!(
-            "{} ({}, {:.2}%)",
-            node.name,
-            format_bytes(node.size),
-            percentage
-        )
+        String::new()
     };
+    if ui.button(format!("Copier{count}")).clicked() {
+        actions.clipboard_paths = top_level_paths(&targets);
+        actions.clipboard_is_cut = false;
+        ui.close_menu();
+    }
+    if ui.button(format!("Couper{count}")).clicked() {
+        actions.clipboard_paths = top_level_paths(&targets);
+        actions.clipboard_is_cut = true;
+        ui.close_menu();
+    }
 
-    let is_selected = selected_node_path
-        .as_ref()
-        .map_or(false, |p| p == &node.path);
-
-    if node.is_dir {
-        let header_label = if is_selected {
-            egui::RichText::new(label.clone()).strong()
+    // Coller ici : si on est sur un dossier => dedans, sinon => dans le parent du fichier
+    if !actions.clipboard_paths.is_empty() {
+        let dest_dir = if is_dir {
+            Some(path.to_path_buf())
         } else {
-            egui::RichText::new(label.clone())
+            path.parent().map(|p| p.to_path_buf())
         };
 
-        let header = egui::CollapsingHeader::new(header_label)
-            .default_open(indent_level == 0)
-            .id_source(node.path.to_string_lossy().to_string());
-
-        let collapsing = header.show(ui, |ui| {
-            for child in &node.children {
-                ui.horizontal(|ui| {
-                    ui.add_space(indent + 10.0);
-                    draw_node_recursive(
-                        ui,
-                        child,
-                        root_size,
-                        indent_level + 1,
-                        selected_node_path,
-                        pending_delete,
-                        clipboard_path,
-                        clipboard_is_cut,
-                        pending_paste_dest,
-                    );
-                });
-            }
-        });
-
-        let header_resp = collapsing.header_response;
-
-        if header_resp.clicked() {
-            *selected_node_path = Some(node.path.clone());
-        }
-
-        header_resp.context_menu(|ui| {
-            if ui.button("Propriétés").clicked() {
-                *selected_node_path = Some(node.path.clone());
-                ui.close_menu();
-            }
-            if ui.button("Copier le chemin").clicked() {
-                let text = node.path.to_string_lossy().to_string();
-                ui.output_mut(|o| o.copied_text = text);
-                ui.close_menu();
-            }
-            if ui.button("Copier").clicked() {
-                *clipboard_path = Some(node.path.clone());
-                *clipboard_is_cut = false;
-                ui.close_menu();
-            }
-            if ui.button("Couper").clicked() {
-                *clipboard_path = Some(node.path.clone());
-                *clipboard_is_cut = true;
-                ui.close_menu();
-            }
-            if clipboard_path.is_some() {
-                if ui.button("Coller ici").clicked() {
-                    *pending_paste_dest = Some(node.path.clone());
-                    ui.close_menu();
-                }
-            }
-            if ui
-                .button(
-                    egui::RichText::new("Supprimer…")
-                        .color(egui::Color32::RED),
-                )
-                .clicked()
-            {
-                *pending_delete = Some(node.path.clone());
+        if let Some(dest) = dest_dir {
+            if ui.button("Coller ici").clicked() {
+                actions.pending_paste_dest = Some(dest);
                 ui.close_menu();
             }
-        });
-    } else {
-        let resp = ui
-            .horizontal(|ui| {
-                ui.add_space(indent + 10.0);
-                ui.selectable_label(is_selected, label)
-            })
-            .inner;
-
-        if resp.clicked() {
-            *selected_node_path = Some(node.path.clone());
         }
+    }
 
-        resp.context_menu(|ui| {
-            if ui.button("Propriétés").clicked() {
-                *selected_node_path = Some(node.path.clone());
-                ui.close_menu();
-            }
-            if ui.button("Copier le chemin").clicked() {
-                let text = node.path.to_string_lossy().to_string();
-                ui.output_mut(|o| o.copied_text = text);
-                ui.close_menu();
-            }
-            if ui.button("Copier").clicked() {
-                *clipboard_path = Some(node.path.clone());
-                *clipboard_is_cut = false;
-                ui.close_menu();
-            }
-            if ui.button("Couper").clicked() {
-                *clipboard_path = Some(node.path.clone());
-                *clipboard_is_cut = true;
-                ui.close_menu();
-            }
-
-            // Coller dans le même dossier que ce fichier
-            if clipboard_path.is_some() {
-                if let Some(parent) = node.path.parent() {
-                    if ui.button("Coller ici").clicked() {
-                        *pending_paste_dest = Some(parent.to_path_buf());
-                        ui.close_menu();
-                    }
-                }
-            }
-
-            if ui
-                .button(
-                    egui::RichText::new("Supprimer…")
-                        .color(egui::Color32::RED),
-                )
-                .clicked()
-            {
-                *pending_delete = Some(node.path.clone());
-                ui.close_menu();
-            }
-        });
+    if ui
+        .button(
+            egui::RichText::new(format!("Supprimer{count}…"))
+                .color(egui::Color32::RED),
+        )
+        .clicked()
+    {
+        actions.pending_delete = targets;
+        ui.close_menu();
     }
 }
 
@@ -999,15 +2009,7 @@ struct Hit {
 }
 
 /// Dessin de la treemap façon WinDirStat + clic gauche/droit.
-fn draw_treemap(
-    ui: &mut egui::Ui,
-    root: &Node,
-    selected_path: &mut Option<PathBuf>,
-    pending_delete: &mut Option<PathBuf>,
-    clipboard_path: &mut Option<PathBuf>,
-    clipboard_is_cut: &mut bool,
-    pending_paste_dest: &mut Option<PathBuf>,
-) {
+fn draw_treemap(ui: &mut egui::Ui, root: &Node, actions: &mut NodeActions) {
     let total_size = root.size.max(1);
 
     let available_size = ui.available_size();
@@ -1035,19 +2037,20 @@ fn draw_treemap(
         true,
         children,
         sum_children_size,
-        selected_path,
+        actions,
         &mut hits,
         0,
     );
 
     if let Some(pos) = response.interact_pointer_pos() {
-        // Clic gauche => sélection
+        // Clic gauche => sélection (Ctrl : ajout/retrait, Maj : plage de frères)
         if response.clicked() {
-            for hit in &hits {
-                if hit.rect.contains(pos) {
-                    *selected_path = Some(hit.path.clone());
-                    break;
-                }
+            if let Some(hit) = hits.iter().find(|h| h.rect.contains(pos)) {
+                let modifiers = ui.input(|i| i.modifiers);
+                let anchor = actions.selected_node_path.clone();
+                actions.click_select(&hit.path, modifiers, || {
+                    sibling_range(&hits, anchor.as_deref(), &hit.path)
+                });
             }
         }
 
@@ -1086,53 +2089,7 @@ fn draw_treemap(
                 ui.monospace(hit.path.to_string_lossy());
                 ui.separator();
 
-                if ui.button("Propriétés").clicked() {
-                    *selected_path = Some(hit.path.clone());
-                    ui.close_menu();
-                }
-                if ui.button("Copier le chemin").clicked() {
-                    let text =
-                        hit.path.to_string_lossy().to_string();
-                    ui.output_mut(|o| o.copied_text = text);
-                    ui.close_menu();
-                }
-                if ui.button("Copier").clicked() {
-                    *clipboard_path = Some(hit.path.clone());
-                    *clipboard_is_cut = false;
-                    ui.close_menu();
-                }
-                if ui.button("Couper").clicked() {
-                    *clipboard_path = Some(hit.path.clone());
-                    *clipboard_is_cut = true;
-                    ui.close_menu();
-                }
-
-                // Coller ici : si on est sur un dossier => dedans, sinon => dans le parent du fichier
-                if clipboard_path.is_some() {
-                    let dest_dir = if hit.is_dir {
-                        Some(hit.path.clone())
-                    } else {
-                        hit.path.parent().map(|p| p.to_path_buf())
-                    };
-
-                    if let Some(dest) = dest_dir {
-                        if ui.button("Coller ici").clicked() {
-                            *pending_paste_dest = Some(dest);
-                            ui.close_menu();
-                        }
-                    }
-                }
-
-                if ui
-                    .button(
-                        egui::RichText::new("Supprimer…")
-                            .color(egui::Color32::RED),
-                    )
-                    .clicked()
-                {
-                    *pending_delete = Some(hit.path.clone());
-                    ui.close_menu();
-                }
+                node_context_menu(ui, &hit.path, hit.is_dir, actions);
             } else {
                 ui.weak("Aucun élément ici.");
             }
@@ -1142,14 +2099,37 @@ fn draw_treemap(
     });
 }
 
+/// Blocs frères situés entre l'ancre et l'élément cliqué (ordre de la treemap).
+fn sibling_range(hits: &[Hit], anchor: Option<&Path>, clicked: &Path) -> Vec<PathBuf> {
+    let Some(anchor) = anchor else {
+        return Vec::new();
+    };
+    if anchor.parent() != clicked.parent() {
+        return Vec::new();
+    }
+    let siblings: Vec<&Hit> = hits
+        .iter()
+        .filter(|h| h.path.parent() == clicked.parent())
+        .collect();
+    let position = |path: &Path| siblings.iter().position(|h| h.path == path);
+    match (position(anchor), position(clicked)) {
+        (Some(a), Some(b)) => siblings[a.min(b)..=a.max(b)]
+            .iter()
+            .map(|h| h.path.clone())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
 /// Algorithme de treemap simple (slice-and-dice) avec alternance horizontal/vertical.
+#[allow(clippy::too_many_arguments)]
 fn layout_treemap_rect(
     painter: &egui::Painter,
     rect: egui::Rect,
     horizontal: bool,
     nodes: &[Node],
     total_size: u64,
-    selected_path: &Option<PathBuf>,
+    actions: &NodeActions,
     hits: &mut Vec<Hit>,
     depth: usize,
 ) {
@@ -1194,9 +2174,7 @@ fn layout_treemap_rect(
             continue;
         }
 
-        let is_selected = selected_path
-            .as_ref()
-            .map_or(false, |p| p == &node.path);
+        let is_selected = actions.is_selected(&node.path);
 
         let base_color = color_for_path(&node.path, depth);
         let fill_color = if is_selected {
@@ -1259,7 +2237,7 @@ fn layout_treemap_rect(
                 !horizontal,
                 &node.children,
                 child_total,
-                selected_path,
+                actions,
                 hits,
                 depth + 1,
             );
@@ -1339,6 +2317,7 @@ fn scan_directory_parallel(
     root: &Path,
     cancel: &AtomicBool,
     progress: &ScanProgress,
+    sort: &SortOptions,
 ) -> ScanResult {
     if cancel.load(Ordering::Relaxed) {
         return ScanResult {
@@ -1421,7 +2400,12 @@ fn scan_directory_parallel(
         .map(|os| os.to_string_lossy().to_string())
         .unwrap_or_else(|| root.to_string_lossy().to_string());
 
-    let root_node = Node::new_dir(name, root.to_path_buf(), children_nodes);
+    let meta = root.metadata().ok();
+    let modified = meta.as_ref().and_then(|m| m.modified().ok());
+    let mut root_node =
+        Node::new_dir(name, root.to_path_buf(), modified, children_nodes);
+    root_node.owner = meta.as_ref().and_then(owner_ids);
+    root_node.sort_recursive(sort);
 
     ScanResult {
         root_path: root.to_path_buf(),
@@ -1441,7 +2425,11 @@ fn build_node(
     }
 
     if path.is_file() {
-        let size = path.metadata().map(|m| m.len()).unwrap_or(0);
+        let meta = path.metadata().ok();
+        let size = meta.as_ref().map(|m| m.len()).unwrap_or(0);
+        let allocated = meta.as_ref().map(allocated_size).unwrap_or(0);
+        let modified = meta.as_ref().and_then(|m| m.modified().ok());
+        let accessed = meta.as_ref().and_then(|m| m.accessed().ok());
         // Mise à jour progression
         if size > 0 {
             progress
@@ -1452,7 +2440,16 @@ fn build_node(
             .file_name()
             .map(|os| os.to_string_lossy().to_string())
             .unwrap_or_else(|| path.to_string_lossy().to_string());
-        Ok(Node::new_file(name, path.to_path_buf(), size))
+        let mut node = Node::new_file(
+            name,
+            path.to_path_buf(),
+            size,
+            allocated,
+            modified,
+            accessed,
+        );
+        node.owner = meta.as_ref().and_then(owner_ids);
+        Ok(node)
     } else if path.is_dir() {
         let name = path
             .file_name()
@@ -1476,111 +2473,55 @@ fn build_node(
             })
             .collect();
 
-        Ok(Node::new_dir(name, path.to_path_buf(), children))
+        let meta = path.metadata().ok();
+        let modified = meta.as_ref().and_then(|m| m.modified().ok());
+
+        let mut node =
+            Node::new_dir(name, path.to_path_buf(), modified, children);
+        node.owner = meta.as_ref().and_then(owner_ids);
+        Ok(node)
     } else {
         Err("Type de fichier non pris en charge".to_string())
     }
 }
 
-/// Suppression d'un fichier ou dossier (récursif pour les dossiers).
-fn delete_path(path: &Path) -> std::io::Result<()> {
-    let meta = fs::symlink_metadata(path)?;
-    if meta.is_dir() {
-        fs::remove_dir_all(path)
-    } else {
-        fs::remove_file(path)
-    }
+/// Espace alloué sur le disque pour un fichier (blocs de 512 octets sous Unix).
+#[cfg(unix)]
+fn allocated_size(meta: &fs::Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    meta.blocks() * 512
 }
 
-#[cfg(target_os = "windows")]
-fn is_path_too_long(path: &Path) -> bool {
-    // Windows classique ~260 caractères (MAX_PATH)
-    path.to_string_lossy().len() > 260
+/// Approximation : taille arrondie au cluster NTFS par défaut (4 Ko).
+#[cfg(not(unix))]
+fn allocated_size(meta: &fs::Metadata) -> u64 {
+    meta.len().div_ceil(4096) * 4096
 }
 
-#[cfg(not(target_os = "windows"))]
-fn is_path_too_long(_path: &Path) -> bool {
-    false
+/// Propriétaire (uid, gid) d'une entrée.
+#[cfg(unix)]
+fn owner_ids(meta: &fs::Metadata) -> Option<(u32, u32)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((meta.uid(), meta.gid()))
 }
 
-/// Copie/déplacement de fichier ou dossier dans un dossier cible.
-fn copy_or_move(
-    src: &Path,
-    dest_dir: &Path,
-    is_cut: bool,
-) -> Result<(), String> {
-    if !dest_dir.exists() || !dest_dir.is_dir() {
-        return Err(format!(
-            "Destination invalide : {}",
-            dest_dir.to_string_lossy()
-        ));
-    }
-    if !src.exists() {
-        return Err(format!(
-            "Source introuvable : {}",
-            src.to_string_lossy()
-        ));
-    }
-
-    if is_cut && dest_dir.starts_with(src) {
-        return Err(
-            "Impossible de déplacer un dossier dans lui-même ou un sous-dossier."
-                .to_string(),
-        );
-    }
-
-    let file_name =
-        src.file_name().unwrap_or_else(|| OsStr::new("unnamed"));
-    let dest_path = dest_dir.join(file_name);
-
-    if is_path_too_long(&dest_path) {
-        return Err(format!(
-            "Chemin de destination trop long pour le système ({} caractères).\n{}",
-            dest_path.to_string_lossy().len(),
-            dest_path.to_string_lossy()
-        ));
-    }
-
-    if is_cut {
-        match fs::rename(src, &dest_path) {
-            Ok(()) => return Ok(()),
-            Err(_) => {
-                // cross-device, fallback copy + delete
-            }
-        }
-    }
+/// Pas de uid/gid hors Unix.
+#[cfg(not(unix))]
+fn owner_ids(_meta: &fs::Metadata) -> Option<(u32, u32)> {
+    None
+}
 
-    let metadata = fs::symlink_metadata(src).map_err(|e| e.to_string())?;
-    if metadata.is_dir() {
-        copy_dir_recursive(src, &dest_path).map_err(|e| e.to_string())?;
+/// Suppression d'un fichier ou dossier (récursif pour les dossiers).
+fn delete_path(path: &Path) -> std::io::Result<()> {
+    protect::check(path)?;
+    let meta = fs::symlink_metadata(path)?;
+    if meta.is_dir() {
+        fs::remove_dir_all(path)
     } else {
-        fs::copy(src, &dest_path).map_err(|e| e.to_string())?;
-    }
-
-    if is_cut {
-        delete_path(src).map_err(|e| e.to_string())?;
+        fs::remove_file(path)
     }
-
-    Ok(())
 }
 
-/// Copie récursive de dossier.
-fn copy_dir_recursive(src: &Path, dest: &Path) -> std::io::Result<()> {
-    fs::create_dir_all(dest)?;
-    for entry in fs::read_dir(src)? {
-        let entry = entry?;
-        let entry_path = entry.path();
-        let file_type = entry.file_type()?;
-        let dest_path = dest.join(entry.file_name());
-
-        if file_type.is_dir() {
-            copy_dir_recursive(&entry_path, &dest_path)?;
-        } else {
-            let _ = fs::copy(&entry_path, &dest_path)?;
-        }
-    }
-    Ok(())
-}
 
 /// Recherche d'un Node par chemin.
 fn find_node_by_path<'a>(node: &'a Node, path: &Path) -> Option<&'a Node> {
@@ -0,0 +1,448 @@
+//! Occupation par propriétaire : octets et fichiers par utilisateur et par
+//! groupe, avec les dossiers qui y contribuent le plus pour chacun.
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use eframe::egui;
+use egui_extras::{Column, TableBuilder};
+use rayon::prelude::*;
+
+use crate::tree_table::right_aligned;
+use crate::{format_bytes, node_context_menu, Node, NodeActions};
+
+const ROW_HEIGHT: f32 = 20.0;
+/// Nombre maximal de dossiers listés pour un propriétaire.
+const MAX_DRILL_DIRS: usize = 200;
+
+/// Regroupement affiché : par utilisateur (uid) ou par groupe (gid).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OwnerKind {
+    User,
+    Group,
+}
+
+impl OwnerKind {
+    fn id_of(self, node: &Node) -> Option<u32> {
+        node.owner.map(|(uid, gid)| match self {
+            OwnerKind::User => uid,
+            OwnerKind::Group => gid,
+        })
+    }
+
+    fn id_label(self) -> &'static str {
+        match self {
+            OwnerKind::User => "uid",
+            OwnerKind::Group => "gid",
+        }
+    }
+}
+
+/// Totaux pour un utilisateur ou un groupe.
+struct OwnerTotals {
+    id: u32,
+    name: String,
+    bytes: u64,
+    files: u64,
+}
+
+/// Contribution d'un dossier (fichiers directement dedans) pour un propriétaire.
+struct DirShare {
+    path: PathBuf,
+    name: String,
+    bytes: u64,
+    files: u64,
+}
+
+/// Etat de la vue : totaux en cache et propriétaire détaillé.
+pub struct OwnersState {
+    kind: OwnerKind,
+    users: Vec<OwnerTotals>,
+    groups: Vec<OwnerTotals>,
+    computed: bool,
+    selected: Option<(OwnerKind, u32)>,
+    dirs: Vec<DirShare>,
+    /// Noms déjà résolus (la résolution peut passer par le réseau : LDAP…).
+    names: HashMap<(OwnerKind, u32), String>,
+}
+
+impl Default for OwnersState {
+    fn default() -> Self {
+        Self {
+            kind: OwnerKind::User,
+            users: Vec::new(),
+            groups: Vec::new(),
+            computed: false,
+            selected: None,
+            dirs: Vec::new(),
+            names: HashMap::new(),
+        }
+    }
+}
+
+impl OwnersState {
+    /// A appeler quand l'arbre change (nouveau scan).
+    pub fn invalidate(&mut self) {
+        self.users.clear();
+        self.groups.clear();
+        self.computed = false;
+        self.selected = None;
+        self.dirs.clear();
+    }
+
+    fn refresh(&mut self, root: &Node) {
+        if self.computed {
+            return;
+        }
+        let (users, groups) = compute_totals(root);
+        self.users = self.named(OwnerKind::User, users);
+        self.groups = self.named(OwnerKind::Group, groups);
+        self.computed = true;
+    }
+
+    /// Résout les noms et trie par taille décroissante.
+    fn named(&mut self, kind: OwnerKind, totals: HashMap<u32, (u64, u64)>) -> Vec<OwnerTotals> {
+        let mut list: Vec<OwnerTotals> = totals
+            .into_iter()
+            .map(|(id, (bytes, files))| OwnerTotals {
+                id,
+                name: self.name_of(kind, id),
+                bytes,
+                files,
+            })
+            .collect();
+        list.sort_by_key(|o| Reverse(o.bytes));
+        list
+    }
+
+    fn name_of(&mut self, kind: OwnerKind, id: u32) -> String {
+        self.names
+            .entry((kind, id))
+            .or_insert_with(|| {
+                let resolved = match kind {
+                    OwnerKind::User => user_name(id),
+                    OwnerKind::Group => group_name(id),
+                };
+                resolved.unwrap_or_else(|| format!("{} {id}", kind.id_label()))
+            })
+            .clone()
+    }
+}
+
+type Totals = HashMap<u32, (u64, u64)>;
+
+fn accumulate(node: &Node, users: &mut Totals, groups: &mut Totals) {
+    if node.is_dir {
+        for child in &node.children {
+            accumulate(child, users, groups);
+        }
+        return;
+    }
+
+    if let Some((uid, gid)) = node.owner {
+        for (map, id) in [(&mut *users, uid), (&mut *groups, gid)] {
+            let entry = map.entry(id).or_default();
+            entry.0 += node.size;
+            entry.1 += 1;
+        }
+    }
+}
+
+/// Octets et nombre de fichiers par uid et par gid (en parallèle sur les
+/// enfants de la racine).
+fn compute_totals(root: &Node) -> (Totals, Totals) {
+    let merge = |mut a: Totals, b: Totals| {
+        for (id, (bytes, files)) in b {
+            let entry = a.entry(id).or_default();
+            entry.0 += bytes;
+            entry.1 += files;
+        }
+        a
+    };
+
+    root.children
+        .par_iter()
+        .fold(
+            || (Totals::new(), Totals::new()),
+            |(mut users, mut groups), child| {
+                accumulate(child, &mut users, &mut groups);
+                (users, groups)
+            },
+        )
+        .reduce(
+            || (Totals::new(), Totals::new()),
+            |a, b| (merge(a.0, b.0), merge(a.1, b.1)),
+        )
+}
+
+/// Dossiers dont les fichiers directs appartenant au propriétaire pèsent le plus.
+fn dirs_for_owner(root: &Node, kind: OwnerKind, id: u32) -> Vec<DirShare> {
+    let mut dirs = Vec::new();
+    let mut stack: Vec<&Node> = vec![root];
+    while let Some(node) = stack.pop() {
+        let mut bytes = 0;
+        let mut files = 0;
+        for child in &node.children {
+            if child.is_dir {
+                stack.push(child);
+            } else if kind.id_of(child) == Some(id) {
+                bytes += child.size;
+                files += 1;
+            }
+        }
+        if files > 0 {
+            dirs.push(DirShare {
+                path: node.path.clone(),
+                name: node.name.clone(),
+                bytes,
+                files,
+            });
+        }
+    }
+    dirs.sort_by_key(|d| Reverse(d.bytes));
+    dirs.truncate(MAX_DRILL_DIRS);
+    dirs
+}
+
+/// Nom d'utilisateur via la base des comptes du système (NSS).
+#[cfg(unix)]
+pub fn user_name(uid: u32) -> Option<String> {
+    let mut buf_len = 1024;
+    loop {
+        let mut buf = vec![0 as libc::c_char; buf_len];
+        // SAFETY: `pwd` et `buf` vivent jusqu'à la copie du nom ; `result`
+        // pointe vers `pwd` ou est nul.
+        unsafe {
+            let mut pwd: libc::passwd = std::mem::zeroed();
+            let mut result = std::ptr::null_mut();
+            let rc = libc::getpwuid_r(uid, &mut pwd, buf.as_mut_ptr(), buf.len(), &mut result);
+            if rc == libc::ERANGE && buf_len < 1 << 20 {
+                buf_len *= 2;
+                continue;
+            }
+            if rc != 0 || result.is_null() {
+                return None;
+            }
+            return Some(
+                std::ffi::CStr::from_ptr(pwd.pw_name)
+                    .to_string_lossy()
+                    .into_owned(),
+            );
+        }
+    }
+}
+
+/// Nom de groupe via la base des groupes du système (NSS).
+#[cfg(unix)]
+fn group_name(gid: u32) -> Option<String> {
+    let mut buf_len = 1024;
+    loop {
+        let mut buf = vec![0 as libc::c_char; buf_len];
+        // SAFETY: `grp` et `buf` vivent jusqu'à la copie du nom ; `result`
+        // pointe vers `grp` ou est nul.
+        unsafe {
+            let mut grp: libc::group = std::mem::zeroed();
+            let mut result = std::ptr::null_mut();
+            let rc = libc::getgrgid_r(gid, &mut grp, buf.as_mut_ptr(), buf.len(), &mut result);
+            if rc == libc::ERANGE && buf_len < 1 << 20 {
+                buf_len *= 2;
+                continue;
+            }
+            if rc != 0 || result.is_null() {
+                return None;
+            }
+            return Some(
+                std::ffi::CStr::from_ptr(grp.gr_name)
+                    .to_string_lossy()
+                    .into_owned(),
+            );
+        }
+    }
+}
+
+#[cfg(not(unix))]
+pub fn user_name(_uid: u32) -> Option<String> {
+    None
+}
+
+#[cfg(not(unix))]
+fn group_name(_gid: u32) -> Option<String> {
+    None
+}
+
+/// Dessin de la vue par propriétaire : totaux puis, au clic sur un
+/// utilisateur ou un groupe, les dossiers où il occupe le plus d'espace.
+pub fn draw_owners(
+    ui: &mut egui::Ui,
+    root: &Node,
+    state: &mut OwnersState,
+    actions: &mut NodeActions,
+) {
+    state.refresh(root);
+
+    if state.users.is_empty() {
+        ui.weak("Propriétaires non disponibles (système non Unix ou aucun fichier).");
+        return;
+    }
+
+    ui.horizontal(|ui| {
+        ui.selectable_value(&mut state.kind, OwnerKind::User, "Par utilisateur");
+        ui.selectable_value(&mut state.kind, OwnerKind::Group, "Par groupe");
+    });
+
+    let kind = state.kind;
+    let owners = match kind {
+        OwnerKind::User => &state.users,
+        OwnerKind::Group => &state.groups,
+    };
+    let root_size = root.size.max(1);
+    let mut clicked_owner: Option<u32> = None;
+
+    let table_height = ui.available_height() * 0.4;
+    ui.push_id("owners_table", |ui| {
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(true)
+            .sense(egui::Sense::click())
+            .auto_shrink([false, true])
+            .max_scroll_height(table_height)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(Column::initial(160.0).at_least(80.0).clip(true))
+            .column(Column::initial(70.0).at_least(50.0))
+            .column(Column::initial(90.0).at_least(60.0))
+            .column(Column::initial(90.0).at_least(60.0))
+            .column(Column::remainder().at_least(120.0))
+            .header(ROW_HEIGHT + 4.0, |mut header| {
+                for title in [
+                    match kind {
+                        OwnerKind::User => "Utilisateur",
+                        OwnerKind::Group => "Groupe",
+                    },
+                    kind.id_label(),
+                    "Taille",
+                    "Fichiers",
+                    "% racine",
+                ] {
+                    header.col(|ui| {
+                        ui.strong(title);
+                    });
+                }
+            })
+            .body(|body| {
+                body.rows(ROW_HEIGHT, owners.len(), |mut row| {
+                    let owner = &owners[row.index()];
+                    let fraction = owner.bytes as f32 / root_size as f32;
+                    row.set_selected(state.selected == Some((kind, owner.id)));
+
+                    row.col(|ui| {
+                        ui.add(egui::Label::new(&owner.name).selectable(false));
+                    });
+                    row.col(|ui| {
+                        right_aligned(ui, owner.id.to_string());
+                    });
+                    row.col(|ui| {
+                        right_aligned(ui, format_bytes(owner.bytes));
+                    });
+                    row.col(|ui| {
+                        right_aligned(ui, owner.files.to_string());
+                    });
+                    row.col(|ui| {
+                        ui.add(
+                            egui::ProgressBar::new(fraction)
+                                .text(format!("{:.1}%", fraction * 100.0)),
+                        );
+                    });
+
+                    if row.response().clicked() {
+                        clicked_owner = Some(owner.id);
+                    }
+                });
+            });
+    });
+
+    if let Some(id) = clicked_owner {
+        if state.selected != Some((kind, id)) {
+            state.dirs = dirs_for_owner(root, kind, id);
+            state.selected = Some((kind, id));
+        }
+    }
+
+    let Some((selected_kind, selected_id)) = state.selected else {
+        ui.add_space(6.0);
+        ui.weak("Cliquer sur un propriétaire pour voir les dossiers qui y contribuent le plus.");
+        return;
+    };
+
+    ui.add_space(6.0);
+    ui.separator();
+    ui.label(
+        egui::RichText::new(format!(
+            "Dossiers où « {} » occupe le plus d'espace (fichiers directement dans le dossier)",
+            state.name_of(selected_kind, selected_id)
+        ))
+        .strong(),
+    );
+    ui.small("Double-clic : afficher dans l'arborescence.");
+
+    let dirs = &state.dirs;
+    ui.push_id("owner_dirs_table", |ui| {
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(true)
+            .sense(egui::Sense::click())
+            .auto_shrink([false, false])
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(Column::initial(220.0).at_least(100.0).clip(true))
+            .column(Column::initial(90.0).at_least(60.0))
+            .column(Column::initial(80.0).at_least(60.0))
+            .column(Column::remainder().at_least(150.0).clip(true))
+            .header(ROW_HEIGHT + 4.0, |mut header| {
+                for title in ["Dossier", "Taille", "Fichiers", "Chemin complet"] {
+                    header.col(|ui| {
+                        ui.strong(title);
+                    });
+                }
+            })
+            .body(|body| {
+                body.rows(ROW_HEIGHT, dirs.len(), |mut row| {
+                    let entry = &dirs[row.index()];
+                    row.set_selected(actions.selected_node_path.as_ref() == Some(&entry.path));
+
+                    row.col(|ui| {
+                        ui.add(
+                            egui::Label::new(&entry.name)
+                                .selectable(false)
+                                .truncate(true),
+                        );
+                    });
+                    row.col(|ui| {
+                        right_aligned(ui, format_bytes(entry.bytes));
+                    });
+                    row.col(|ui| {
+                        right_aligned(ui, entry.files.to_string());
+                    });
+                    row.col(|ui| {
+                        ui.add(
+                            egui::Label::new(
+                                egui::RichText::new(entry.path.to_string_lossy()).monospace(),
+                            )
+                            .selectable(false)
+                            .truncate(true),
+                        );
+                    });
+
+                    let resp = row.response();
+                    if resp.clicked() {
+                        actions.selected_node_path = Some(entry.path.clone());
+                    }
+                    if resp.double_clicked() {
+                        actions.pending_reveal = Some(entry.path.clone());
+                    }
+                    resp.context_menu(|ui| {
+                        node_context_menu(ui, &entry.path, true, actions);
+                    });
+                });
+            });
+    });
+}
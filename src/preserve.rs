@@ -0,0 +1,297 @@
+//! Copie fidèle : liens symboliques recréés tels quels, fichiers spéciaux
+//! (FIFO, périphériques) recréés explicitement, et report des métadonnées
+//! (droits, propriétaire, dates, attributs étendus) quand c'est permis.
+//! Renommage qui ne remplace jamais un élément existant.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Recrée le lien symbolique `src` (même cible, non résolue) en `dest`.
+#[cfg(unix)]
+pub fn copy_symlink(src: &Path, dest: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(fs::read_link(src)?, dest)
+}
+
+#[cfg(windows)]
+pub fn copy_symlink(src: &Path, dest: &Path) -> io::Result<()> {
+    let target = fs::read_link(src)?;
+    if fs::metadata(src).is_ok_and(|m| m.is_dir()) {
+        std::os::windows::fs::symlink_dir(target, dest)
+    } else {
+        std::os::windows::fs::symlink_file(target, dest)
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn copy_symlink(_src: &Path, _dest: &Path) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "lien symbolique non reproductible sur ce système",
+    ))
+}
+
+#[cfg(unix)]
+fn c_path(path: &Path) -> io::Result<std::ffi::CString> {
+    use std::os::unix::ffi::OsStrExt;
+
+    std::ffi::CString::new(path.as_os_str().as_bytes())
+        .map_err(|_| io::Error::from(io::ErrorKind::InvalidInput))
+}
+
+/// Recrée un fichier spécial : FIFO, périphérique bloc ou caractère (droits
+/// administrateur nécessaires). Les sockets ne sont pas reproductibles
+/// (erreur `Unsupported`).
+#[cfg(unix)]
+pub fn copy_special(meta: &fs::Metadata, dest: &Path) -> io::Result<()> {
+    use std::os::unix::fs::{FileTypeExt, MetadataExt};
+
+    let file_type = meta.file_type();
+    if file_type.is_socket() {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "socket non copiée (recréée par le programme qui l'utilise)",
+        ));
+    }
+
+    let path = c_path(dest)?;
+    // SAFETY: chemin C valide (terminé par un NUL) pendant l'appel.
+    let rc = if file_type.is_fifo() {
+        unsafe { libc::mkfifo(path.as_ptr(), (meta.mode() & 0o7777) as libc::mode_t) }
+    } else if file_type.is_block_device() || file_type.is_char_device() {
+        unsafe {
+            libc::mknod(
+                path.as_ptr(),
+                meta.mode() as libc::mode_t,
+                meta.rdev() as libc::dev_t,
+            )
+        }
+    } else {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "type de fichier inconnu, non copié",
+        ));
+    };
+    if rc != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn copy_special(_meta: &fs::Metadata, _dest: &Path) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "fichier spécial non copié",
+    ))
+}
+
+/// Renomme `from` en `to` sans jamais remplacer un élément apparu à
+/// l'arrivée depuis la dernière vérification (erreur `AlreadyExists`).
+#[cfg(target_os = "linux")]
+pub fn rename_no_replace(from: &Path, to: &Path) -> io::Result<()> {
+    let (from_c, to_c) = (c_path(from)?, c_path(to)?);
+    // SAFETY: chemins C valides (terminés par un NUL) pendant l'appel.
+    let rc = unsafe {
+        libc::renameat2(
+            libc::AT_FDCWD,
+            from_c.as_ptr(),
+            libc::AT_FDCWD,
+            to_c.as_ptr(),
+            libc::RENAME_NOREPLACE,
+        )
+    };
+    if rc == 0 {
+        return Ok(());
+    }
+    let err = io::Error::last_os_error();
+    match err.raw_os_error() {
+        // Système de fichiers (ou noyau) sans RENAME_NOREPLACE
+        Some(libc::EINVAL | libc::ENOSYS) => link_then_unlink(from, to),
+        _ => Err(err),
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn rename_no_replace(from: &Path, to: &Path) -> io::Result<()> {
+    link_then_unlink(from, to)
+}
+
+/// Renommage exclusif sans appel système dédié : un fichier reçoit d'abord
+/// un lien physique `to` (refusé si le nom existe) puis perd son ancien nom ;
+/// un dossier réserve son nom en créant un dossier vide, qu'un renommage
+/// peut remplacer (jamais un dossier non vide).
+fn link_then_unlink(from: &Path, to: &Path) -> io::Result<()> {
+    if fs::symlink_metadata(from)?.is_dir() {
+        fs::create_dir(to)?;
+        return fs::rename(from, to).inspect_err(|_| {
+            let _ = fs::remove_dir(to);
+        });
+    }
+    fs::hard_link(from, to)?;
+    fs::remove_file(from)
+}
+
+/// Reporte les métadonnées de `src` sur `dest` : attributs étendus,
+/// propriétaire, droits puis dates (dans cet ordre : changer de
+/// propriétaire efface les bits setuid, et les dates d'un dossier changent
+/// quand on le remplit). Renvoie ce qui n'a pas pu être reproduit.
+#[cfg(unix)]
+pub fn apply_metadata(src: &Path, meta: &fs::Metadata, dest: &Path) -> Vec<String> {
+    use std::os::unix::fs::MetadataExt;
+
+    let mut warnings = Vec::new();
+    let shown = dest.to_string_lossy();
+
+    copy_xattrs(src, dest, &mut warnings);
+
+    if let Err(e) = std::os::unix::fs::lchown(dest, Some(meta.uid()), Some(meta.gid())) {
+        // Sans droits d'administration, on devient propriétaire de la copie
+        let same = fs::symlink_metadata(dest)
+            .is_ok_and(|m| m.uid() == meta.uid() && m.gid() == meta.gid());
+        if !same {
+            warnings.push(format!(
+                "{shown} : propriétaire {}:{} non conservé ({e})",
+                meta.uid(),
+                meta.gid()
+            ));
+        }
+    }
+
+    // Les droits d'un lien symbolique ne sont pas modifiables sous Linux
+    if !meta.file_type().is_symlink() {
+        if let Err(e) = fs::set_permissions(dest, meta.permissions()) {
+            warnings.push(format!("{shown} : droits non conservés ({e})"));
+        }
+    }
+
+    let times = [
+        libc::timespec {
+            tv_sec: meta.atime() as libc::time_t,
+            tv_nsec: meta.atime_nsec() as libc::c_long,
+        },
+        libc::timespec {
+            tv_sec: meta.mtime() as libc::time_t,
+            tv_nsec: meta.mtime_nsec() as libc::c_long,
+        },
+    ];
+    let result = c_path(dest).and_then(|path| {
+        // SAFETY: chemin C et tableau de deux timespec valides pendant l'appel.
+        let rc = unsafe {
+            libc::utimensat(
+                libc::AT_FDCWD,
+                path.as_ptr(),
+                times.as_ptr(),
+                libc::AT_SYMLINK_NOFOLLOW,
+            )
+        };
+        if rc == 0 {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    });
+    if let Err(e) = result {
+        warnings.push(format!("{shown} : dates non conservées ({e})"));
+    }
+
+    warnings
+}
+
+#[cfg(not(unix))]
+pub fn apply_metadata(_src: &Path, meta: &fs::Metadata, dest: &Path) -> Vec<String> {
+    let mut warnings = Vec::new();
+    let shown = dest.to_string_lossy();
+
+    if meta.is_file() {
+        let mut times = fs::FileTimes::new();
+        if let Ok(modified) = meta.modified() {
+            times = times.set_modified(modified);
+        }
+        if let Ok(accessed) = meta.accessed() {
+            times = times.set_accessed(accessed);
+        }
+        let result = fs::OpenOptions::new()
+            .write(true)
+            .open(dest)
+            .and_then(|f| f.set_times(times));
+        if let Err(e) = result {
+            warnings.push(format!("{shown} : dates non conservées ({e})"));
+        }
+    }
+    if !meta.file_type().is_symlink() {
+        if let Err(e) = fs::set_permissions(dest, meta.permissions()) {
+            warnings.push(format!("{shown} : droits non conservés ({e})"));
+        }
+    }
+
+    warnings
+}
+
+/// Attributs étendus (`user.*`, ACL, étiquettes de sécurité…), sans suivre
+/// les liens symboliques. Un système de fichiers source sans attributs
+/// n'est pas une erreur.
+#[cfg(all(unix, target_os = "linux"))]
+fn copy_xattrs(src: &Path, dest: &Path, warnings: &mut Vec<String>) {
+    use std::ptr;
+
+    let (Ok(src_c), Ok(dest_c)) = (c_path(src), c_path(dest)) else {
+        return;
+    };
+
+    // SAFETY: chemins C valides ; tampons alloués à la taille annoncée.
+    let size = unsafe { libc::llistxattr(src_c.as_ptr(), ptr::null_mut(), 0) };
+    if size <= 0 {
+        return;
+    }
+    let mut names = vec![0u8; size as usize];
+    let size = unsafe { libc::llistxattr(src_c.as_ptr(), names.as_mut_ptr().cast(), names.len()) };
+    if size < 0 {
+        return;
+    }
+    names.truncate(size as usize);
+
+    for name in names.split(|&b| b == 0).filter(|n| !n.is_empty()) {
+        let Ok(name_c) = std::ffi::CString::new(name) else {
+            continue;
+        };
+        let len = unsafe { libc::lgetxattr(src_c.as_ptr(), name_c.as_ptr(), ptr::null_mut(), 0) };
+        if len < 0 {
+            continue;
+        }
+        let mut value = vec![0u8; len as usize];
+        let len = unsafe {
+            libc::lgetxattr(
+                src_c.as_ptr(),
+                name_c.as_ptr(),
+                value.as_mut_ptr().cast(),
+                value.len(),
+            )
+        };
+        if len < 0 {
+            continue;
+        }
+        value.truncate(len as usize);
+
+        let rc = unsafe {
+            libc::lsetxattr(
+                dest_c.as_ptr(),
+                name_c.as_ptr(),
+                value.as_ptr().cast(),
+                value.len(),
+                0,
+            )
+        };
+        if rc != 0 {
+            warnings.push(format!(
+                "{} : attribut étendu {} non conservé ({})",
+                dest.to_string_lossy(),
+                String::from_utf8_lossy(name),
+                io::Error::last_os_error()
+            ));
+        }
+    }
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+fn copy_xattrs(_src: &Path, _dest: &Path, _warnings: &mut Vec<String>) {}
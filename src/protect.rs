@@ -0,0 +1,345 @@
+//! Chemins protégés : la suppression (corbeille comprise), le déplacement
+//! et le renommage d'un chemin protégé, ou d'un dossier qui en contient un,
+//! sont refusés. Sont protégés la liste configurable (dossiers système et
+//! dossier personnel par défaut), les points de montage, les racines des
+//! systèmes de fichiers et la racine du scan en cours.
+//!
+//! La vérification est faite au plus près du disque (`delete_path`, file des
+//! opérations) ; l'interface la refait avant d'agir pour proposer de passer
+//! outre, en tapant le chemin, via [`grant`].
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use eframe::egui;
+
+struct Protection {
+    /// Liste configurée (None : liste par défaut).
+    configured: Option<Vec<PathBuf>>,
+    scan_root: Option<PathBuf>,
+    /// Chemins autorisés par l'utilisateur jusqu'à la fin de l'opération.
+    granted: Vec<PathBuf>,
+}
+
+static PROTECTION: Mutex<Protection> = Mutex::new(Protection {
+    configured: None,
+    scan_root: None,
+    granted: Vec::new(),
+});
+
+fn state() -> std::sync::MutexGuard<'static, Protection> {
+    PROTECTION.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Liste par défaut : dossiers système et dossier personnel.
+pub fn default_paths() -> Vec<PathBuf> {
+    #[cfg(windows)]
+    let system: &[&str] = &[
+        "C:\\Windows",
+        "C:\\Program Files",
+        "C:\\Program Files (x86)",
+        "C:\\ProgramData",
+        "C:\\Users",
+    ];
+    #[cfg(target_os = "macos")]
+    let system: &[&str] = &[
+        "/Applications",
+        "/Library",
+        "/System",
+        "/Users",
+        "/bin",
+        "/etc",
+        "/private",
+        "/sbin",
+        "/usr",
+        "/var",
+    ];
+    #[cfg(not(any(windows, target_os = "macos")))]
+    let system: &[&str] = &[
+        "/bin",
+        "/boot",
+        "/dev",
+        "/etc",
+        "/home",
+        "/lib",
+        "/lib32",
+        "/lib64",
+        "/opt",
+        "/proc",
+        "/root",
+        "/run",
+        "/sbin",
+        "/srv",
+        "/sys",
+        "/usr",
+        "/usr/bin",
+        "/usr/lib",
+        "/usr/local",
+        "/usr/sbin",
+        "/usr/share",
+        "/var",
+        "/var/lib",
+        "/var/log",
+    ];
+
+    let mut paths: Vec<PathBuf> = system.iter().map(PathBuf::from).collect();
+    paths.extend(dirs::home_dir());
+    paths
+}
+
+/// Remplace la liste configurée (None : liste par défaut).
+pub fn configure(paths: Option<Vec<PathBuf>>) {
+    state().configured = paths;
+}
+
+/// Racine du scan en cours, protégée tant qu'elle est affichée.
+pub fn set_scan_root(root: Option<PathBuf>) {
+    state().scan_root = root;
+}
+
+/// Passe outre la protection de `paths` (saisie du chemin confirmée par
+/// l'utilisateur), jusqu'à [`revoke`].
+pub fn grant(paths: &[PathBuf]) {
+    state().granted.extend(paths.iter().cloned());
+}
+
+/// Fin de l'opération autorisée : les chemins sont de nouveau protégés.
+pub fn revoke(paths: &[PathBuf]) {
+    state().granted.retain(|p| !paths.contains(p));
+}
+
+/// Motif du refus si `path` est protégé ou contient un chemin protégé.
+pub fn protection_reason(path: &Path) -> Option<String> {
+    let path = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    if path.parent().is_none() {
+        return Some(format!(
+            "{} est la racine d'un système de fichiers",
+            path.to_string_lossy()
+        ));
+    }
+
+    let state = state();
+    if state.granted.contains(&path) {
+        return None;
+    }
+    let mut protected: Vec<(PathBuf, &str)> = Vec::new();
+    if let Some(root) = &state.scan_root {
+        protected.push((root.clone(), "racine du scan"));
+    }
+    let home = dirs::home_dir();
+    let configured = state.configured.clone().unwrap_or_else(default_paths);
+    for entry in configured {
+        let label = if Some(&entry) == home.as_ref() {
+            "dossier personnel"
+        } else {
+            "chemin protégé"
+        };
+        protected.push((entry, label));
+    }
+    drop(state);
+    protected.extend(mount_points().into_iter().map(|m| (m, "point de montage")));
+
+    let (blocking, label) = protected.iter().find(|(p, _)| p.starts_with(&path))?;
+    Some(if *blocking == path {
+        format!("{} est protégé ({label})", path.to_string_lossy())
+    } else {
+        format!(
+            "{} contient {} ({label})",
+            path.to_string_lossy(),
+            blocking.to_string_lossy()
+        )
+    })
+}
+
+/// Erreur si `path` est protégé (suppression, déplacement ou renommage).
+pub fn check(path: &Path) -> io::Result<()> {
+    match protection_reason(path) {
+        Some(reason) => Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("Chemin protégé : {reason}"),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Points de montage actuels (`/proc/self/mounts`).
+#[cfg(target_os = "linux")]
+fn mount_points() -> Vec<PathBuf> {
+    let Ok(mounts) = std::fs::read_to_string("/proc/self/mounts") else {
+        return Vec::new();
+    };
+    mounts
+        .lines()
+        .filter_map(|line| line.split(' ').nth(1))
+        .map(|field| PathBuf::from(unescape_mount(field)))
+        .collect()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn mount_points() -> Vec<PathBuf> {
+    Vec::new()
+}
+
+/// Espaces et caractères spéciaux notés `\040` (octal) dans `/proc/self/mounts`.
+#[cfg(target_os = "linux")]
+fn unescape_mount(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let octal = bytes.get(i + 1..i + 4).and_then(|digits| {
+            std::str::from_utf8(digits)
+                .ok()
+                .and_then(|d| u8::from_str_radix(d, 8).ok())
+        });
+        match (bytes[i], octal) {
+            (b'\\', Some(byte)) => {
+                out.push(byte);
+                i += 4;
+            }
+            (byte, _) => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Opération refusée en attente d'une décision.
+pub enum ProtectedOperation {
+    Delete { permanent: bool },
+    Move { dest: PathBuf },
+}
+
+/// Refus d'une opération portant sur des chemins protégés, avec la
+/// possibilité de passer outre en tapant chaque chemin protégé.
+pub struct ProtectedPrompt {
+    pub operation: ProtectedOperation,
+    /// Tous les éléments de l'opération.
+    pub paths: Vec<PathBuf>,
+    /// Eléments protégés et motif du refus.
+    protected: Vec<(PathBuf, String)>,
+    typed: Vec<String>,
+    /// Faux si un élément est la racine d'un système de fichiers : jamais
+    /// autorisé.
+    overridable: bool,
+}
+
+/// Décision de l'utilisateur.
+pub enum PromptAction {
+    None,
+    Cancel,
+    /// Continuer avec les seuls éléments non protégés.
+    SkipProtected(Vec<PathBuf>),
+    /// Passer outre : les chemins protégés ont été tapés.
+    Override,
+}
+
+impl ProtectedPrompt {
+    /// None si aucun élément n'est protégé.
+    pub fn new(operation: ProtectedOperation, paths: Vec<PathBuf>) -> Option<Self> {
+        let protected: Vec<(PathBuf, String)> = paths
+            .iter()
+            .filter_map(|p| protection_reason(p).map(|reason| (p.clone(), reason)))
+            .collect();
+        if protected.is_empty() {
+            return None;
+        }
+        Some(Self {
+            operation,
+            overridable: protected.iter().all(|(p, _)| p.parent().is_some()),
+            typed: vec![String::new(); protected.len()],
+            paths,
+            protected,
+        })
+    }
+
+    /// Chemins autorisés par la saisie (à passer à [`grant`]).
+    pub fn protected_paths(&self) -> Vec<PathBuf> {
+        self.protected.iter().map(|(p, _)| p.clone()).collect()
+    }
+}
+
+/// Fenêtre de refus : message, puis saisie des chemins pour passer outre.
+pub fn draw_protected_window(ctx: &egui::Context, prompt: &mut ProtectedPrompt) -> PromptAction {
+    let mut action = PromptAction::None;
+    let verb = match prompt.operation {
+        ProtectedOperation::Delete { .. } => "supprimé",
+        ProtectedOperation::Move { .. } => "déplacé",
+    };
+
+    egui::Window::new("Chemin protégé")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+        .show(ctx, |ui| {
+            ui.colored_label(
+                egui::Color32::RED,
+                format!(
+                    "{} élément(s) protégé(s) ne peuvent pas être {verb}(s) :",
+                    prompt.protected.len()
+                ),
+            );
+            for (path, reason) in &prompt.protected {
+                ui.monospace(path.to_string_lossy());
+                ui.small(reason);
+            }
+            ui.add_space(8.0);
+
+            if !prompt.overridable {
+                ui.label(
+                    "La racine d'un système de fichiers ne peut jamais être supprimée ni déplacée.",
+                );
+            }
+            ui.add_enabled_ui(prompt.overridable, |ui| {
+                ui.collapsing("Passer outre (administrateur)", |ui| {
+                    ui.label("Pour confirmer, tape le chemin exact de chaque élément protégé :");
+                    for ((path, _), typed) in prompt.protected.iter().zip(&mut prompt.typed) {
+                        let hint = path.to_string_lossy().to_string();
+                        ui.add(
+                            egui::TextEdit::singleline(typed)
+                                .hint_text(hint)
+                                .desired_width(360.0),
+                        );
+                    }
+                    let confirmed = prompt
+                        .protected
+                        .iter()
+                        .zip(&prompt.typed)
+                        .all(|((path, _), typed)| Path::new(typed.trim()) == path);
+                    let button =
+                        egui::RichText::new("Passer outre et continuer").color(egui::Color32::RED);
+                    if ui
+                        .add_enabled(confirmed, egui::Button::new(button))
+                        .clicked()
+                    {
+                        action = PromptAction::Override;
+                    }
+                });
+            });
+            ui.add_space(8.0);
+
+            ui.horizontal(|ui| {
+                if ui.button("Annuler").clicked() {
+                    action = PromptAction::Cancel;
+                }
+                let others: Vec<PathBuf> = prompt
+                    .paths
+                    .iter()
+                    .filter(|p| !prompt.protected.iter().any(|(q, _)| q == *p))
+                    .cloned()
+                    .collect();
+                if !others.is_empty()
+                    && ui
+                        .button(format!("Continuer sans eux ({})", others.len()))
+                        .clicked()
+                {
+                    action = PromptAction::SkipProtected(others);
+                }
+            });
+        });
+
+    action
+}
@@ -0,0 +1,291 @@
+//! Renommage sur place : éditeur dans la ligne de l'arborescence ou fenêtre
+//! (treemap et autres vues), validation du nouveau nom, puis mise à jour de
+//! l'arbre scanné sans rescan.
+
+use std::ffi::OsStr;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use eframe::egui;
+use egui::text::{CCursor, CCursorRange};
+
+use crate::Node;
+
+/// Renommage en cours de saisie.
+#[derive(Debug, Default)]
+pub struct RenameEdit {
+    pub path: PathBuf,
+    pub name: String,
+    /// Editeur dans la ligne de l'arborescence (sinon fenêtre).
+    pub inline: bool,
+    pub error: Option<String>,
+    /// Validé par l'utilisateur : à appliquer par l'application.
+    pub submitted: bool,
+    /// Focus (et sélection du nom sans extension) à donner au champ.
+    focus: bool,
+}
+
+/// Issue de l'éditeur pour cette frame.
+pub enum RenameAction {
+    None,
+    Submit,
+    Cancel,
+}
+
+impl RenameEdit {
+    /// None si le nom actuel n'est pas en UTF-8 : l'éditeur ne pourrait
+    /// qu'en afficher (et en enregistrer) une version altérée.
+    pub fn new(path: PathBuf, inline: bool) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.to_string();
+        Some(Self {
+            path,
+            name,
+            inline,
+            error: None,
+            submitted: false,
+            focus: true,
+        })
+    }
+
+    /// Nom saisi identique au nom actuel (octet par octet).
+    pub fn is_unchanged(&self) -> bool {
+        self.path.file_name() == Some(OsStr::new(&self.name))
+    }
+
+    /// Refus du nom saisi : l'éditeur reste ouvert avec le message.
+    pub fn reject(&mut self, error: String) {
+        self.error = Some(error);
+        self.submitted = false;
+        self.focus = true;
+    }
+
+    fn apply(&mut self, action: &RenameAction) {
+        if matches!(action, RenameAction::Submit) {
+            self.submitted = true;
+        }
+    }
+}
+
+/// Caractères refusés dans un nom de fichier.
+#[cfg(windows)]
+const FORBIDDEN: &[char] = &['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+#[cfg(not(windows))]
+const FORBIDDEN: &[char] = &['/'];
+
+/// Longueur maximale d'un nom (octets, limite courante des systèmes de fichiers).
+const MAX_NAME_LEN: usize = 255;
+
+/// Vérifie le nouveau nom de `path` et renvoie le chemin renommé.
+pub fn validate_name(path: &Path, name: &str) -> Result<PathBuf, String> {
+    if name.trim().is_empty() {
+        return Err("Le nom ne peut pas être vide.".to_string());
+    }
+    if name == "." || name == ".." {
+        return Err(format!("« {name} » n'est pas un nom valide."));
+    }
+    if let Some(c) = name
+        .chars()
+        .find(|c| FORBIDDEN.contains(c) || c.is_control())
+    {
+        return Err(if c.is_control() {
+            "Caractère de contrôle interdit dans le nom.".to_string()
+        } else {
+            format!("Caractère interdit dans le nom : {c}")
+        });
+    }
+    if name.len() > MAX_NAME_LEN {
+        return Err(format!(
+            "Nom trop long ({} octets, maximum {MAX_NAME_LEN}).",
+            name.len()
+        ));
+    }
+    if cfg!(windows) {
+        if name.ends_with(['.', ' ']) {
+            return Err("Un nom ne peut pas se terminer par un point ou une espace.".to_string());
+        }
+        let stem = name.split('.').next().unwrap_or(name).to_ascii_uppercase();
+        let reserved = matches!(stem.as_str(), "CON" | "PRN" | "AUX" | "NUL")
+            || (stem.len() == 4
+                && (stem.starts_with("COM") || stem.starts_with("LPT"))
+                && stem.ends_with(|c: char| ('1'..='9').contains(&c)));
+        if reserved {
+            return Err(format!("« {stem} » est un nom réservé par le système."));
+        }
+    }
+
+    let target = path.with_file_name(name);
+    // Changement de casse seule : même fichier sur un système insensible à la casse
+    if fs::symlink_metadata(&target).is_ok() && !same_entry(path, &target) {
+        return Err(format!("« {name} » existe déjà dans ce dossier."));
+    }
+    Ok(target)
+}
+
+/// Renomme `old` en `new` (renvoyé par [`validate_name`]) sans jamais
+/// remplacer un élément apparu entre-temps sous le nouveau nom. Un simple
+/// changement de casse sur un système insensible à la casse renomme le même
+/// élément.
+pub fn rename_path(old: &Path, new: &Path) -> io::Result<()> {
+    if same_entry(old, new) {
+        return fs::rename(old, new);
+    }
+    crate::preserve::rename_no_replace(old, new).map_err(|e| {
+        if e.kind() == io::ErrorKind::AlreadyExists {
+            let name = new.file_name().unwrap_or_default().to_string_lossy();
+            io::Error::new(e.kind(), format!("« {name} » existe déjà dans ce dossier."))
+        } else {
+            e
+        }
+    })
+}
+
+/// Les deux chemins désignent-ils le même élément ?
+#[cfg(unix)]
+fn same_entry(a: &Path, b: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+
+    match (fs::symlink_metadata(a), fs::symlink_metadata(b)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+fn same_entry(a: &Path, b: &Path) -> bool {
+    let name = |p: &Path| p.file_name().map(|n| n.to_string_lossy().to_lowercase());
+    name(a) == name(b)
+}
+
+/// Remplace le préfixe `old` de `path` par `new` (None si `path` n'est pas
+/// sous `old`).
+pub fn reprefix(path: &Path, old: &Path, new: &Path) -> Option<PathBuf> {
+    let rest = path.strip_prefix(old).ok()?;
+    Some(if rest.as_os_str().is_empty() {
+        new.to_path_buf()
+    } else {
+        new.join(rest)
+    })
+}
+
+/// Met à jour le nœud renommé et les chemins de tous ses descendants.
+/// Renvoie false si `old` n'est pas dans l'arbre.
+pub fn rename_node(root: &mut Node, old: &Path, new: &Path) -> bool {
+    if !old.starts_with(&root.path) {
+        return false;
+    }
+    if root.path == old {
+        root.name = new
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        reprefix_tree(root, old, new);
+        return true;
+    }
+    root.children
+        .iter_mut()
+        .any(|child| rename_node(child, old, new))
+}
+
+fn reprefix_tree(node: &mut Node, old: &Path, new: &Path) {
+    if let Some(path) = reprefix(&node.path, old, new) {
+        node.path = path;
+    }
+    for child in &mut node.children {
+        reprefix_tree(child, old, new);
+    }
+}
+
+/// Champ de saisie dans la ligne de l'arborescence : Entrée valide, Echap
+/// (ou un clic ailleurs) annule.
+pub fn draw_inline_editor(ui: &mut egui::Ui, edit: &mut RenameEdit) -> RenameAction {
+    let output = egui::TextEdit::singleline(&mut edit.name)
+        .desired_width(ui.available_width())
+        .show(ui);
+    let response = &output.response;
+
+    if std::mem::take(&mut edit.focus) {
+        focus_stem(ui, &edit.name, output.state, response.id);
+        response.request_focus();
+    }
+    if let Some(error) = &edit.error {
+        // Message sous le champ, la ligne étant trop basse pour l'afficher
+        egui::show_tooltip_for(
+            ui.ctx(),
+            response.id.with("rename_error"),
+            &response.rect,
+            |ui| ui.colored_label(egui::Color32::RED, error),
+        );
+    }
+
+    let action = if response.lost_focus() {
+        if ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+            RenameAction::Submit
+        } else {
+            RenameAction::Cancel
+        }
+    } else {
+        RenameAction::None
+    };
+    edit.apply(&action);
+    action
+}
+
+/// Fenêtre de renommage (vues autres que l'arborescence).
+pub fn draw_rename_window(ctx: &egui::Context, edit: &mut RenameEdit) -> RenameAction {
+    let mut action = RenameAction::None;
+
+    egui::Window::new("Renommer")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+        .show(ctx, |ui| {
+            if let Some(parent) = edit.path.parent() {
+                ui.label("Dans le dossier :");
+                ui.monospace(parent.to_string_lossy());
+                ui.add_space(4.0);
+            }
+
+            let output = egui::TextEdit::singleline(&mut edit.name)
+                .desired_width(320.0)
+                .show(ui);
+            if std::mem::take(&mut edit.focus) {
+                focus_stem(ui, &edit.name, output.state, output.response.id);
+                output.response.request_focus();
+            }
+            if output.response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                action = RenameAction::Submit;
+            }
+
+            if let Some(error) = &edit.error {
+                ui.colored_label(egui::Color32::RED, error);
+            }
+            ui.add_space(8.0);
+
+            ui.horizontal(|ui| {
+                if ui.button("Annuler").clicked() || ui.input(|i| i.key_pressed(egui::Key::Escape))
+                {
+                    action = RenameAction::Cancel;
+                }
+                if ui.button("Renommer").clicked() {
+                    action = RenameAction::Submit;
+                }
+            });
+        });
+
+    edit.apply(&action);
+    action
+}
+
+/// Sélectionne le nom sans son extension, comme les gestionnaires de fichiers.
+fn focus_stem(ui: &egui::Ui, name: &str, mut state: egui::text_edit::TextEditState, id: egui::Id) {
+    let stem_len = match name.rfind('.') {
+        Some(dot) if dot > 0 => name[..dot].chars().count(),
+        _ => name.chars().count(),
+    };
+    state.cursor.set_char_range(Some(CCursorRange::two(
+        CCursor::new(0),
+        CCursor::new(stem_len),
+    )));
+    state.store(ui.ctx(), id);
+}
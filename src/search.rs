@@ -0,0 +1,194 @@
+//! Panneau de recherche : saisie des critères du filtre de l'arborescence
+//! (nom, taille, type, dates) et navigation entre les résultats.
+
+use std::path::PathBuf;
+
+use chrono::{Local, NaiveDate};
+use eframe::egui;
+use egui_extras::DatePickerButton;
+
+use crate::filter::{KindFilter, NameMode, TreeFilter};
+use crate::format_bytes;
+
+/// Demande du panneau, traitée par l'application après le dessin.
+pub enum SearchRequest {
+    /// Appliquer les critères saisis.
+    Apply,
+    /// Retirer les critères de recherche.
+    Clear,
+    /// Sélectionner et afficher un résultat.
+    Goto(PathBuf),
+}
+
+/// Critères en cours de saisie et résultats du filtre appliqué.
+pub struct SearchState {
+    query: String,
+    name_mode: NameMode,
+    kind: KindFilter,
+    /// Taille minimale saisie en Mo.
+    min_size_mb: f64,
+    use_from: bool,
+    date_from: NaiveDate,
+    use_to: bool,
+    date_to: NaiveDate,
+    /// Eléments retenus, dans l'ordre d'affichage.
+    matches: Vec<PathBuf>,
+    current: Option<usize>,
+    pub error: Option<String>,
+}
+
+impl Default for SearchState {
+    fn default() -> Self {
+        let today = Local::now().date_naive();
+        Self {
+            query: String::new(),
+            name_mode: NameMode::Substring,
+            kind: KindFilter::All,
+            min_size_mb: 0.0,
+            use_from: false,
+            date_from: today,
+            use_to: false,
+            date_to: today,
+            matches: Vec::new(),
+            current: None,
+            error: None,
+        }
+    }
+}
+
+impl SearchState {
+    /// Reporte les critères saisis dans le filtre. Seul le critère
+    /// d'ancienneté éventuel est conservé (la tranche de l'histogramme est
+    /// remplacée).
+    pub fn fill(&self, filter: &mut TreeFilter) {
+        filter.max_size = None;
+        filter.within = None;
+        filter.name = self.query.trim().to_string();
+        filter.name_mode = self.name_mode;
+        filter.kind = self.kind;
+        filter.min_size = (self.min_size_mb * 1024.0 * 1024.0) as u64;
+        filter.modified_from = self.use_from.then_some(self.date_from);
+        filter.modified_to = self.use_to.then_some(self.date_to);
+    }
+
+    pub fn matches_mut(&mut self) -> &mut Vec<PathBuf> {
+        &mut self.matches
+    }
+
+    /// Nouveaux résultats (après application du filtre ou rescan).
+    pub fn set_matches(&mut self, matches: Vec<PathBuf>) {
+        self.matches = matches;
+        self.current = None;
+    }
+
+    fn step(&mut self, forward: bool) -> Option<PathBuf> {
+        let len = self.matches.len();
+        if len == 0 {
+            return None;
+        }
+        let next = match (self.current, forward) {
+            (None, true) => 0,
+            (None, false) => len - 1,
+            (Some(i), true) => (i + 1) % len,
+            (Some(i), false) => (i + len - 1) % len,
+        };
+        self.current = Some(next);
+        Some(self.matches[next].clone())
+    }
+}
+
+/// Dessin du panneau. `filtered_size` : taille totale des éléments retenus
+/// (None si aucun filtre n'est actif).
+pub fn draw_search(
+    ui: &mut egui::Ui,
+    state: &mut SearchState,
+    filtered_size: Option<u64>,
+) -> Option<SearchRequest> {
+    let mut request = None;
+
+    let resp = ui.add(
+        egui::TextEdit::singleline(&mut state.query)
+            .hint_text("Nom, motif *.log ou regex")
+            .desired_width(f32::INFINITY),
+    );
+    if resp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+        request = Some(SearchRequest::Apply);
+    }
+
+    ui.horizontal(|ui| {
+        ui.label("Nom :");
+        for mode in NameMode::ALL {
+            ui.selectable_value(&mut state.name_mode, mode, mode.label());
+        }
+    });
+
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_label("Type")
+            .selected_text(state.kind.label())
+            .show_ui(ui, |ui| {
+                for kind in KindFilter::ALL {
+                    ui.selectable_value(&mut state.kind, kind, kind.label());
+                }
+            });
+        ui.label("Taille ≥");
+        ui.add(
+            egui::DragValue::new(&mut state.min_size_mb)
+                .clamp_range(0.0..=f64::MAX)
+                .speed(1.0)
+                .suffix(" Mo"),
+        );
+    });
+
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut state.use_from, "Modifié du");
+        ui.add_enabled_ui(state.use_from, |ui| {
+            ui.add(DatePickerButton::new(&mut state.date_from).id_source("search_date_from"));
+        });
+        ui.checkbox(&mut state.use_to, "au");
+        ui.add_enabled_ui(state.use_to, |ui| {
+            ui.add(DatePickerButton::new(&mut state.date_to).id_source("search_date_to"));
+        });
+    });
+
+    ui.horizontal(|ui| {
+        if ui.button("🔍 Rechercher").clicked() {
+            request = Some(SearchRequest::Apply);
+        }
+        if ui.button("Effacer").clicked() {
+            request = Some(SearchRequest::Clear);
+        }
+    });
+
+    if let Some(error) = &state.error {
+        ui.colored_label(egui::Color32::RED, error);
+    }
+
+    if let Some(total) = filtered_size {
+        ui.add_space(4.0);
+        ui.label(format!(
+            "{} résultat(s), {} au total",
+            state.matches.len(),
+            format_bytes(total)
+        ));
+        ui.horizontal(|ui| {
+            let enabled = !state.matches.is_empty();
+            if ui
+                .add_enabled(enabled, egui::Button::new("◀ Précédent"))
+                .clicked()
+            {
+                request = state.step(false).map(SearchRequest::Goto);
+            }
+            if ui
+                .add_enabled(enabled, egui::Button::new("Suivant ▶"))
+                .clicked()
+            {
+                request = state.step(true).map(SearchRequest::Goto);
+            }
+            if let Some(current) = state.current {
+                ui.weak(format!("{} / {}", current + 1, state.matches.len()));
+            }
+        });
+    }
+
+    request
+}
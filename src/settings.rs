@@ -0,0 +1,156 @@
+//! Préférences de l'utilisateur, conservées entre les sessions dans un
+//! fichier texte `clé = valeur` du dossier de configuration
+//! (`~/.config/treesize_rust/settings.conf` sous Linux).
+
+use std::fs;
+use std::path::PathBuf;
+
+use eframe::egui;
+
+use crate::protect;
+
+#[derive(Default)]
+pub struct Settings {
+    /// Commande du terminal ("{dir}" : dossier à ouvrir ; vide : détection
+    /// automatique).
+    pub terminal: String,
+    /// Chemins protégés (None : liste par défaut de [`protect`]).
+    protected: Option<Vec<PathBuf>>,
+    /// Liste des chemins protégés en cours d'édition (un par ligne).
+    protected_text: String,
+    path: Option<PathBuf>,
+    /// Dernière erreur d'enregistrement.
+    error: Option<String>,
+}
+
+impl Settings {
+    pub fn load() -> Self {
+        let mut settings = Settings {
+            path: dirs::config_dir().map(|d| d.join("treesize_rust").join("settings.conf")),
+            ..Settings::default()
+        };
+        let text = settings
+            .path
+            .as_ref()
+            .and_then(|p| fs::read_to_string(p).ok())
+            .unwrap_or_default();
+        for line in text.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = value.trim();
+            match key.trim() {
+                "terminal" => settings.terminal = value.to_string(),
+                // Une ligne vide "protected =" : liste volontairement vide
+                "protected" => {
+                    let list = settings.protected.get_or_insert_with(Vec::new);
+                    if !value.is_empty() {
+                        list.push(PathBuf::from(value));
+                    }
+                }
+                _ => {}
+            }
+        }
+        settings.apply_protected();
+        settings
+    }
+
+    /// Transmet la liste des chemins protégés et remet le texte édité à jour.
+    fn apply_protected(&mut self) {
+        let list = self
+            .protected
+            .clone()
+            .unwrap_or_else(protect::default_paths);
+        self.protected_text = list
+            .iter()
+            .map(|p| p.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("\n");
+        protect::configure(self.protected.clone());
+    }
+
+    pub fn save(&mut self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let mut text = format!("terminal = {}\n", self.terminal);
+        match &self.protected {
+            Some(list) if list.is_empty() => text.push_str("protected =\n"),
+            Some(list) => {
+                for path in list {
+                    text.push_str(&format!("protected = {}\n", path.to_string_lossy()));
+                }
+            }
+            None => {}
+        }
+        let result = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::write(path, text));
+        self.error = result
+            .err()
+            .map(|e| format!("Préférences non enregistrées : {e}"));
+    }
+}
+
+/// Panneau des préférences (enregistrées dès qu'un champ est quitté).
+pub fn draw_settings(ui: &mut egui::Ui, settings: &mut Settings) {
+    ui.label("Terminal :");
+    let response = ui
+        .add(
+            egui::TextEdit::singleline(&mut settings.terminal)
+                .hint_text("détection automatique")
+                .desired_width(f32::INFINITY),
+        )
+        .on_hover_text(
+            "Commande lancée dans le dossier, par exemple « konsole » ou \
+             « alacritty --working-directory {dir} ».",
+        );
+    if response.lost_focus() {
+        settings.save();
+    }
+    ui.small("{dir} est remplacé par le dossier à ouvrir.");
+
+    ui.add_space(6.0);
+    let count = settings.protected_text.lines().count();
+    ui.collapsing(format!("Chemins protégés ({count})"), |ui| {
+        let response = ui
+            .add(
+                egui::TextEdit::multiline(&mut settings.protected_text)
+                    .desired_rows(4)
+                    .desired_width(f32::INFINITY),
+            )
+            .on_hover_text(
+                "Un chemin par ligne : ni supprimé ni déplacé, pas plus que les dossiers \
+                 qui le contiennent. Les points de montage et la racine du scan sont \
+                 toujours protégés.",
+            );
+        if response.lost_focus() {
+            let list: Vec<PathBuf> = settings
+                .protected_text
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(PathBuf::from)
+                .collect();
+            // Liste par défaut gardée telle quelle si rien n'a changé
+            let current = settings
+                .protected
+                .clone()
+                .unwrap_or_else(protect::default_paths);
+            if list != current {
+                settings.protected = Some(list);
+                settings.save();
+            }
+            settings.apply_protected();
+        }
+        if settings.protected.is_some() && ui.button("Liste par défaut").clicked() {
+            settings.protected = None;
+            settings.apply_protected();
+            settings.save();
+        }
+    });
+    if let Some(error) = &settings.error {
+        ui.colored_label(egui::Color32::RED, error);
+    }
+}
@@ -0,0 +1,134 @@
+//! Options de tri / regroupement de l'arborescence, appliquées sans rescan.
+
+use std::cmp::Ordering;
+use std::path::Path;
+
+use rayon::prelude::*;
+
+use crate::Node;
+
+/// Clé de tri des enfants d'un dossier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortColumn {
+    Name,
+    Size,
+    Files,
+    Allocated,
+    Modified,
+    Extension,
+}
+
+impl SortColumn {
+    pub const ALL: [SortColumn; 6] = [
+        SortColumn::Name,
+        SortColumn::Size,
+        SortColumn::Files,
+        SortColumn::Allocated,
+        SortColumn::Modified,
+        SortColumn::Extension,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SortColumn::Name => "Nom",
+            SortColumn::Size => "Taille",
+            SortColumn::Files => "Nombre de fichiers",
+            SortColumn::Allocated => "Taille allouée",
+            SortColumn::Modified => "Date de modification",
+            SortColumn::Extension => "Extension",
+        }
+    }
+
+    /// Sens par défaut lorsqu'on choisit une nouvelle colonne
+    /// (alphabétique pour le nom / l'extension, plus gros / plus récent d'abord sinon).
+    pub fn default_descending(self) -> bool {
+        !matches!(self, SortColumn::Name | SortColumn::Extension)
+    }
+}
+
+/// Tri courant de la session (conservé d'un scan à l'autre).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortOptions {
+    pub column: SortColumn,
+    pub descending: bool,
+    /// Regroupe les dossiers avant les fichiers, quel que soit le tri.
+    pub dirs_first: bool,
+}
+
+impl Default for SortOptions {
+    fn default() -> Self {
+        Self {
+            column: SortColumn::Size,
+            descending: true,
+            dirs_first: false,
+        }
+    }
+}
+
+impl SortOptions {
+    /// Sélectionne une colonne : inverse le sens si elle est déjà active.
+    pub fn select_column(&mut self, column: SortColumn) {
+        if self.column == column {
+            self.descending = !self.descending;
+        } else {
+            self.column = column;
+            self.descending = column.default_descending();
+        }
+    }
+
+    pub fn compare(&self, a: &Node, b: &Node) -> Ordering {
+        if self.dirs_first && a.is_dir != b.is_dir {
+            return b.is_dir.cmp(&a.is_dir);
+        }
+
+        let ord = compare_nodes(a, b, self.column);
+        if self.descending {
+            ord.reverse()
+        } else {
+            ord
+        }
+    }
+}
+
+/// Compare deux nœuds selon une colonne (ordre croissant).
+pub fn compare_nodes(a: &Node, b: &Node, column: SortColumn) -> Ordering {
+    match column {
+        SortColumn::Name => compare_names(&a.name, &b.name),
+        SortColumn::Size => a.size.cmp(&b.size),
+        SortColumn::Files => a.file_count.cmp(&b.file_count),
+        SortColumn::Allocated => a.allocated.cmp(&b.allocated),
+        SortColumn::Modified => a.modified.cmp(&b.modified),
+        SortColumn::Extension => extension_of(a)
+            .cmp(&extension_of(b))
+            .then_with(|| compare_names(&a.name, &b.name)),
+    }
+}
+
+/// Comparaison insensible à la casse, sans allocation.
+fn compare_names(a: &str, b: &str) -> Ordering {
+    a.chars()
+        .flat_map(char::to_lowercase)
+        .cmp(b.chars().flat_map(char::to_lowercase))
+}
+
+/// Extension en minuscules d'un fichier (vide pour un dossier).
+pub fn extension_of(node: &Node) -> String {
+    if node.is_dir {
+        return String::new();
+    }
+    Path::new(&node.name)
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
+
+impl Node {
+    /// Trie récursivement les enfants (en parallèle sur les sous-dossiers).
+    pub fn sort_recursive(&mut self, options: &SortOptions) {
+        self.children.sort_by(|a, b| options.compare(a, b));
+        self.children
+            .par_iter_mut()
+            .filter(|c| c.is_dir)
+            .for_each(|c| c.sort_recursive(options));
+    }
+}
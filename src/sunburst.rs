@@ -0,0 +1,308 @@
+//! Vue radiale (sunburst) : la hiérarchie des `Node` en anneaux concentriques.
+
+use std::f32::consts::{PI, TAU};
+use std::path::PathBuf;
+
+use eframe::egui;
+use eframe::epaint::{Mesh, PathShape};
+
+use crate::{
+    color_for_path, find_node_by_path, format_bytes, node_context_menu, Node, NodeActions,
+};
+
+/// Nombre maximal d'anneaux dessinés autour du centre.
+const MAX_DEPTH: usize = 6;
+/// En dessous de cet angle (radians), un secteur n'est pas dessiné.
+const MIN_ANGLE: f32 = 0.004;
+
+/// Etat de la vue : dossier actuellement au centre.
+#[derive(Default)]
+pub struct SunburstState {
+    pub focus: Option<PathBuf>,
+}
+
+/// Un secteur cliquable de la vue radiale.
+struct Segment {
+    depth: usize,
+    start: f32,
+    end: f32,
+    path: PathBuf,
+    name: String,
+    size: u64,
+    is_dir: bool,
+}
+
+/// Géométrie commune : centre, rayon du disque central, épaisseur d'un anneau.
+struct Rings {
+    center: egui::Pos2,
+    inner_radius: f32,
+    ring_width: f32,
+}
+
+impl Rings {
+    fn radii(&self, depth: usize) -> (f32, f32) {
+        let r0 = self.inner_radius + self.ring_width * depth as f32;
+        (r0, r0 + self.ring_width)
+    }
+}
+
+/// Dessin de la vue sunburst + survol, clic gauche/droit, double-clic pour
+/// zoomer sur un dossier et clic au centre pour remonter d'un niveau.
+pub fn draw_sunburst(
+    ui: &mut egui::Ui,
+    root: &Node,
+    state: &mut SunburstState,
+    actions: &mut NodeActions,
+) {
+    let focus = state
+        .focus
+        .as_ref()
+        .and_then(|p| find_node_by_path(root, p))
+        .unwrap_or(root);
+    let total_size = root.size.max(1);
+
+    let available_size = ui.available_size();
+    let size = egui::vec2(available_size.x.max(200.0), available_size.y.max(200.0));
+
+    let (response, painter) = ui.allocate_painter(size, egui::Sense::click());
+    let rect = response.rect;
+
+    let outer_radius = rect.width().min(rect.height()) / 2.0 - 6.0;
+    let inner_radius = (outer_radius * 0.18).max(30.0);
+    let rings = Rings {
+        center: rect.center(),
+        inner_radius,
+        ring_width: (outer_radius - inner_radius) / MAX_DEPTH as f32,
+    };
+
+    // Secteurs
+    let mut segments: Vec<Segment> = Vec::new();
+    layout_segments(
+        &focus.children,
+        focus.size,
+        -PI / 2.0,
+        TAU,
+        0,
+        &mut segments,
+    );
+
+    let mut mesh = Mesh::default();
+    let mut outlines: Vec<egui::Shape> = Vec::new();
+    for seg in &segments {
+        let is_selected = actions.selected_node_path.as_ref() == Some(&seg.path);
+        let base_color = color_for_path(&seg.path, seg.depth);
+        let fill_color = if is_selected {
+            base_color.gamma_multiply(0.8)
+        } else {
+            base_color
+        };
+        let stroke = if is_selected {
+            egui::Stroke::new(2.0, egui::Color32::WHITE)
+        } else {
+            egui::Stroke::new(0.5, egui::Color32::from_gray(40))
+        };
+
+        let (r0, r1) = rings.radii(seg.depth);
+        let points = add_annular_sector(
+            &mut mesh,
+            rings.center,
+            r0,
+            r1,
+            seg.start,
+            seg.end,
+            fill_color,
+        );
+        outlines.push(egui::Shape::Path(PathShape::closed_line(points, stroke)));
+    }
+    painter.add(egui::Shape::mesh(mesh));
+    painter.extend(outlines);
+
+    // Libellés des secteurs assez grands
+    for seg in &segments {
+        let (r0, r1) = rings.radii(seg.depth);
+        let mid_radius = (r0 + r1) / 2.0;
+        let arc_length = (seg.end - seg.start) * mid_radius;
+        if arc_length > 60.0 && rings.ring_width > 14.0 {
+            let mid_angle = (seg.start + seg.end) / 2.0;
+            let pos = rings.center + egui::vec2(mid_angle.cos(), mid_angle.sin()) * mid_radius;
+            painter.text(
+                pos,
+                egui::Align2::CENTER_CENTER,
+                &seg.name,
+                egui::FontId::proportional(10.0),
+                egui::Color32::WHITE,
+            );
+        }
+    }
+
+    // Disque central : dossier courant
+    let center_color = ui.visuals().widgets.inactive.bg_fill;
+    painter.circle_filled(rings.center, rings.inner_radius, center_color);
+    painter.text(
+        rings.center,
+        egui::Align2::CENTER_CENTER,
+        format!("{}\n{}", focus.name, format_bytes(focus.size)),
+        egui::FontId::proportional(11.0),
+        ui.visuals().text_color(),
+    );
+
+    let hit_at = |pos: egui::Pos2| -> Option<&Segment> {
+        let delta = pos - rings.center;
+        let radius = delta.length();
+        if radius < rings.inner_radius {
+            return None;
+        }
+        let depth = ((radius - rings.inner_radius) / rings.ring_width) as usize;
+        // Angle ramené dans [-PI/2, 3PI/2[ comme les secteurs
+        let mut angle = delta.y.atan2(delta.x);
+        if angle < -PI / 2.0 {
+            angle += TAU;
+        }
+        segments
+            .iter()
+            .find(|s| s.depth == depth && angle >= s.start && angle < s.end)
+    };
+    let in_center = |pos: egui::Pos2| (pos - rings.center).length() < rings.inner_radius;
+
+    if let Some(pos) = response.interact_pointer_pos() {
+        if response.double_clicked() {
+            // Double-clic sur un dossier => il devient le centre
+            if let Some(seg) = hit_at(pos).filter(|s| s.is_dir) {
+                state.focus = Some(seg.path.clone());
+            }
+        } else if response.clicked() {
+            if in_center(pos) {
+                // Clic au centre => remonter d'un niveau
+                if focus.path != root.path {
+                    state.focus = focus
+                        .path
+                        .parent()
+                        .map(|p| p.to_path_buf())
+                        .filter(|p| p.starts_with(&root.path));
+                }
+            } else if let Some(seg) = hit_at(pos) {
+                actions.selected_node_path = Some(seg.path.clone());
+            }
+        }
+    }
+
+    // Tooltip au survol
+    if let Some(pos) = response.hover_pos() {
+        if let Some(seg) = hit_at(pos) {
+            let percent = (seg.size as f64 / total_size as f64) * 100.0;
+            let text = format!(
+                "{}\n{}\n{} ({:.2}%)",
+                seg.name,
+                seg.path.display(),
+                format_bytes(seg.size),
+                percent
+            );
+            egui::show_tooltip_at_pointer(ui.ctx(), egui::Id::new("sunburst_tooltip"), |ui| {
+                ui.label(text);
+            });
+        } else if in_center(pos) && focus.path != root.path {
+            egui::show_tooltip_at_pointer(ui.ctx(), egui::Id::new("sunburst_tooltip"), |ui| {
+                ui.label("Clic : remonter d'un niveau");
+            });
+        }
+    }
+
+    // Menu contextuel (clic droit), identique à la treemap
+    response.context_menu(|ui| {
+        if let Some(pos) = ui.ctx().pointer_latest_pos() {
+            if let Some(seg) = hit_at(pos) {
+                ui.label(seg.name.clone());
+                ui.monospace(seg.path.to_string_lossy());
+                ui.separator();
+
+                node_context_menu(ui, &seg.path, seg.is_dir, actions);
+            } else {
+                ui.weak("Aucun élément ici.");
+            }
+        } else {
+            ui.weak("Aucun pointeur.");
+        }
+    });
+}
+
+/// Répartit l'angle [start, start + span[ entre les nœuds, proportionnellement à leur taille.
+fn layout_segments(
+    nodes: &[Node],
+    parent_size: u64,
+    start: f32,
+    span: f32,
+    depth: usize,
+    segments: &mut Vec<Segment>,
+) {
+    if depth >= MAX_DEPTH || parent_size == 0 {
+        return;
+    }
+
+    let mut angle = start;
+    for node in nodes {
+        let node_span = span * (node.size as f64 / parent_size as f64) as f32;
+        if node_span < MIN_ANGLE {
+            angle += node_span;
+            continue;
+        }
+
+        segments.push(Segment {
+            depth,
+            start: angle,
+            end: angle + node_span,
+            path: node.path.clone(),
+            name: node.name.clone(),
+            size: node.size,
+            is_dir: node.is_dir,
+        });
+
+        if !node.children.is_empty() {
+            layout_segments(
+                &node.children,
+                node.size,
+                angle,
+                node_span,
+                depth + 1,
+                segments,
+            );
+        }
+
+        angle += node_span;
+    }
+}
+
+/// Ajoute un secteur d'anneau au maillage et renvoie son contour.
+fn add_annular_sector(
+    mesh: &mut Mesh,
+    center: egui::Pos2,
+    r0: f32,
+    r1: f32,
+    a0: f32,
+    a1: f32,
+    color: egui::Color32,
+) -> Vec<egui::Pos2> {
+    // Un point tous les ~4 px sur l'arc extérieur
+    let steps = (((a1 - a0) * r1) / 4.0).ceil().max(1.0) as usize;
+    let base = mesh.vertices.len() as u32;
+
+    let mut outer = Vec::with_capacity(steps + 1);
+    let mut inner = Vec::with_capacity(steps + 1);
+    for i in 0..=steps {
+        let a = a0 + (a1 - a0) * i as f32 / steps as f32;
+        let dir = egui::vec2(a.cos(), a.sin());
+        let p_inner = center + dir * r0;
+        let p_outer = center + dir * r1;
+        mesh.colored_vertex(p_inner, color);
+        mesh.colored_vertex(p_outer, color);
+        inner.push(p_inner);
+        outer.push(p_outer);
+    }
+    for i in 0..steps as u32 {
+        let k = base + 2 * i;
+        mesh.add_triangle(k, k + 1, k + 2);
+        mesh.add_triangle(k + 1, k + 3, k + 2);
+    }
+
+    outer.extend(inner.into_iter().rev());
+    outer
+}
@@ -0,0 +1,377 @@
+//! Vue "Top N" : liste à plat des plus gros fichiers et des dossiers au contenu
+//! propre le plus lourd, extraits de l'arbre avec un tas borné.
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use eframe::egui;
+use egui_extras::{Column, TableBuilder};
+
+use crate::tree_table::{format_modified, right_aligned};
+use crate::{format_bytes, node_context_menu, Node, NodeActions};
+
+pub const DEFAULT_TOP_N: usize = 100;
+const ROW_HEIGHT: f32 = 20.0;
+
+/// Liste affichée : fichiers, ou dossiers classés par contenu propre
+/// (fichiers directement dans le dossier, hors sous-dossiers).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TopKind {
+    Files,
+    Dirs,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TopColumn {
+    Name,
+    Size,
+    Path,
+    Modified,
+}
+
+/// Une ligne de la liste.
+pub struct TopEntry {
+    pub path: PathBuf,
+    pub name: String,
+    pub size: u64,
+    pub is_dir: bool,
+    pub modified: Option<SystemTime>,
+}
+
+impl TopEntry {
+    fn from_node(node: &Node, size: u64) -> Self {
+        Self {
+            path: node.path.clone(),
+            name: node.name.clone(),
+            size,
+            is_dir: node.is_dir,
+            modified: node.modified,
+        }
+    }
+}
+
+/// Etat de la vue : N, liste affichée, tri, résultats en cache.
+pub struct TopNState {
+    pub n: usize,
+    kind: TopKind,
+    column: TopColumn,
+    descending: bool,
+    files: Vec<TopEntry>,
+    dirs: Vec<TopEntry>,
+    /// N utilisé pour le calcul en cache (None = à recalculer).
+    computed_n: Option<usize>,
+}
+
+impl Default for TopNState {
+    fn default() -> Self {
+        Self {
+            n: DEFAULT_TOP_N,
+            kind: TopKind::Files,
+            column: TopColumn::Size,
+            descending: true,
+            files: Vec::new(),
+            dirs: Vec::new(),
+            computed_n: None,
+        }
+    }
+}
+
+impl TopNState {
+    /// A appeler quand l'arbre change (nouveau scan).
+    pub fn invalidate(&mut self) {
+        self.files.clear();
+        self.dirs.clear();
+        self.computed_n = None;
+    }
+
+    fn refresh(&mut self, root: &Node) {
+        if self.computed_n == Some(self.n) {
+            return;
+        }
+        let (files, dirs) = collect_largest(root, self.n);
+        self.files = files;
+        self.dirs = dirs;
+        self.computed_n = Some(self.n);
+        self.sort_entries();
+    }
+
+    fn click_header(&mut self, column: TopColumn) {
+        if self.column == column {
+            self.descending = !self.descending;
+        } else {
+            self.column = column;
+            self.descending = matches!(column, TopColumn::Size | TopColumn::Modified);
+        }
+        self.sort_entries();
+    }
+
+    fn sort_entries(&mut self) {
+        let column = self.column;
+        let descending = self.descending;
+        let compare = |a: &TopEntry, b: &TopEntry| {
+            let ord = match column {
+                TopColumn::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+                TopColumn::Size => a.size.cmp(&b.size),
+                TopColumn::Path => a.path.cmp(&b.path),
+                TopColumn::Modified => a.modified.cmp(&b.modified),
+            };
+            if descending {
+                ord.reverse()
+            } else {
+                ord
+            }
+        };
+        self.files.sort_by(compare);
+        self.dirs.sort_by(compare);
+    }
+}
+
+/// Candidat dans le tas borné (ordonné par taille).
+struct Candidate<'a> {
+    size: u64,
+    node: &'a Node,
+}
+
+impl PartialEq for Candidate<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate<'_> {}
+
+impl PartialOrd for Candidate<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.size
+            .cmp(&other.size)
+            .then_with(|| other.node.path.cmp(&self.node.path))
+    }
+}
+
+/// Tas min de taille bornée : ne garde que les N plus gros candidats.
+struct BoundedHeap<'a> {
+    limit: usize,
+    heap: BinaryHeap<Reverse<Candidate<'a>>>,
+}
+
+impl<'a> BoundedHeap<'a> {
+    fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            heap: BinaryHeap::with_capacity(limit + 1),
+        }
+    }
+
+    fn push(&mut self, size: u64, node: &'a Node) {
+        if self.limit == 0 {
+            return;
+        }
+        if self.heap.len() == self.limit {
+            match self.heap.peek() {
+                Some(Reverse(min)) if min.size >= size => return,
+                _ => {}
+            }
+        }
+        self.heap.push(Reverse(Candidate { size, node }));
+        if self.heap.len() > self.limit {
+            self.heap.pop();
+        }
+    }
+
+    /// Entrées triées par taille décroissante.
+    fn into_entries(self) -> Vec<TopEntry> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(c)| TopEntry::from_node(c.node, c.size))
+            .collect()
+    }
+}
+
+/// Parcourt l'arbre et renvoie les N plus gros fichiers et les N dossiers
+/// dont le contenu propre est le plus lourd.
+pub fn collect_largest(root: &Node, n: usize) -> (Vec<TopEntry>, Vec<TopEntry>) {
+    let mut files = BoundedHeap::new(n);
+    let mut dirs = BoundedHeap::new(n);
+
+    let mut stack: Vec<&Node> = vec![root];
+    while let Some(node) = stack.pop() {
+        if !node.is_dir {
+            files.push(node.size, node);
+            continue;
+        }
+
+        let own: u64 = node
+            .children
+            .iter()
+            .filter(|c| !c.is_dir)
+            .map(|c| c.size)
+            .sum();
+        if own > 0 {
+            dirs.push(own, node);
+        }
+        stack.extend(node.children.iter());
+    }
+
+    (files.into_entries(), dirs.into_entries())
+}
+
+/// Dessin de la vue Top N (liste virtualisée, triable, clic gauche/droit).
+pub fn draw_top_n(
+    ui: &mut egui::Ui,
+    root: &Node,
+    state: &mut TopNState,
+    actions: &mut NodeActions,
+) {
+    ui.horizontal(|ui| {
+        ui.selectable_value(&mut state.kind, TopKind::Files, "Plus gros fichiers");
+        ui.selectable_value(
+            &mut state.kind,
+            TopKind::Dirs,
+            "Plus gros dossiers (contenu propre)",
+        );
+        ui.separator();
+        ui.label("N :");
+        ui.add(
+            egui::DragValue::new(&mut state.n)
+                .clamp_range(10..=10_000)
+                .speed(10),
+        );
+    });
+
+    state.refresh(root);
+
+    let entries = match state.kind {
+        TopKind::Files => &state.files,
+        TopKind::Dirs => &state.dirs,
+    };
+    let total: u64 = entries.iter().map(|e| e.size).sum();
+    let root_size = root.size.max(1);
+
+    ui.small(format!(
+        "{} éléments, {} au total ({:.1}% de la racine)",
+        entries.len(),
+        format_bytes(total),
+        total as f64 / root_size as f64 * 100.0
+    ));
+    ui.add_space(4.0);
+
+    let mut clicked_header: Option<TopColumn> = None;
+    let header_text = |title: &str, column: TopColumn| {
+        if state.column == column {
+            let arrow = if state.descending { "⬇" } else { "⬆" };
+            format!("{title} {arrow}")
+        } else {
+            title.to_string()
+        }
+    };
+
+    TableBuilder::new(ui)
+        .striped(true)
+        .resizable(true)
+        .sense(egui::Sense::click())
+        .auto_shrink([false, false])
+        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+        .column(Column::initial(40.0).at_least(30.0))
+        .column(Column::initial(220.0).at_least(100.0).clip(true))
+        .column(Column::initial(90.0).at_least(60.0))
+        .column(Column::initial(60.0).at_least(50.0))
+        .column(Column::initial(120.0).at_least(100.0))
+        .column(Column::remainder().at_least(150.0).clip(true))
+        .header(ROW_HEIGHT + 4.0, |mut header| {
+            header.col(|ui| {
+                ui.strong("#");
+            });
+            for (title, column) in [
+                ("Nom", Some(TopColumn::Name)),
+                ("Taille", Some(TopColumn::Size)),
+                ("%", None),
+                ("Modifié le", Some(TopColumn::Modified)),
+                ("Chemin complet", Some(TopColumn::Path)),
+            ] {
+                header.col(|ui| match column {
+                    Some(column) => {
+                        if ui
+                            .add(
+                                egui::Label::new(
+                                    egui::RichText::new(header_text(title, column)).strong(),
+                                )
+                                .selectable(false)
+                                .sense(egui::Sense::click()),
+                            )
+                            .on_hover_text("Cliquer pour trier")
+                            .clicked()
+                        {
+                            clicked_header = Some(column);
+                        }
+                    }
+                    None => {
+                        ui.strong(title);
+                    }
+                });
+            }
+        })
+        .body(|body| {
+            body.rows(ROW_HEIGHT, entries.len(), |mut row| {
+                let index = row.index();
+                let entry = &entries[index];
+                let is_selected = actions.selected_node_path.as_ref() == Some(&entry.path);
+                row.set_selected(is_selected);
+
+                row.col(|ui| {
+                    ui.weak((index + 1).to_string());
+                });
+                row.col(|ui| {
+                    ui.add(
+                        egui::Label::new(&entry.name)
+                            .selectable(false)
+                            .truncate(true),
+                    );
+                });
+                row.col(|ui| {
+                    right_aligned(ui, format_bytes(entry.size));
+                });
+                row.col(|ui| {
+                    right_aligned(
+                        ui,
+                        format!("{:.2}%", entry.size as f64 / root_size as f64 * 100.0),
+                    );
+                });
+                row.col(|ui| {
+                    ui.label(format_modified(entry.modified));
+                });
+                row.col(|ui| {
+                    ui.add(
+                        egui::Label::new(
+                            egui::RichText::new(entry.path.to_string_lossy()).monospace(),
+                        )
+                        .selectable(false)
+                        .truncate(true),
+                    );
+                });
+
+                let resp = row.response();
+                if resp.clicked() {
+                    actions.selected_node_path = Some(entry.path.clone());
+                }
+                if resp.double_clicked() {
+                    actions.pending_reveal = Some(entry.path.clone());
+                }
+                resp.context_menu(|ui| {
+                    node_context_menu(ui, &entry.path, entry.is_dir, actions);
+                });
+            });
+        });
+
+    if let Some(column) = clicked_header {
+        state.click_header(column);
+    }
+}
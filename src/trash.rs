@@ -0,0 +1,254 @@
+//! Corbeille selon la spécification freedesktop.org (Trash 1.0) : corbeille
+//! personnelle (`$XDG_DATA_HOME/Trash`) pour les fichiers du même système de
+//! fichiers, sinon corbeille du point de montage (`$topdir/.Trash/$uid` ou
+//! `$topdir/.Trash-$uid`). Chaque élément est accompagné d'un `.trashinfo`.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// La corbeille est-elle disponible sur ce système ?
+pub const SUPPORTED: bool = cfg!(all(unix, not(target_os = "macos")));
+
+/// Déplace un fichier ou un dossier dans la corbeille et renvoie son nouvel
+/// emplacement.
+#[cfg(all(unix, not(target_os = "macos")))]
+pub fn move_to_trash(path: &Path) -> io::Result<PathBuf> {
+    use std::os::unix::fs::MetadataExt;
+
+    let path = std::path::absolute(path)?;
+    let device = std::fs::symlink_metadata(&path)?.dev();
+
+    let home_trash = dirs::data_dir()
+        .map(|d| d.join("Trash"))
+        .ok_or_else(|| io::Error::other("Dossier de données utilisateur introuvable"))?;
+    if device_of_nearest(&home_trash)? == device {
+        return trash_into(&path, &home_trash, None);
+    }
+
+    // Fichier sur un autre système de fichiers : corbeille de son point de montage
+    let topdir = mount_point(&path, device);
+    let trash_dir = topdir_trash(&topdir)?;
+    trash_into(&path, &trash_dir, Some(&topdir))
+}
+
+#[cfg(not(all(unix, not(target_os = "macos"))))]
+pub fn move_to_trash(_path: &Path) -> io::Result<PathBuf> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "Corbeille non prise en charge sur ce système",
+    ))
+}
+
+/// Remet en place un élément de la corbeille (`trashed`, renvoyé par
+/// [`move_to_trash`]) et supprime son `.trashinfo`. La corbeille est sur le
+/// même système de fichiers que l'emplacement d'origine : un renommage suffit,
+/// refusé si un élément occupe entre-temps l'emplacement d'origine.
+#[cfg(all(unix, not(target_os = "macos")))]
+pub fn restore(trashed: &Path, original: &Path) -> io::Result<()> {
+    let files_dir = trashed
+        .parent()
+        .filter(|dir| dir.file_name() == Some(std::ffi::OsStr::new("files")))
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Pas dans une corbeille"))?;
+    let name = trashed
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Chemin sans nom"))?;
+
+    crate::preserve::rename_no_replace(trashed, original).map_err(|e| {
+        if e.kind() == io::ErrorKind::AlreadyExists {
+            io::Error::new(
+                e.kind(),
+                format!(
+                    "emplacement d'origine occupé : {}",
+                    original.to_string_lossy()
+                ),
+            )
+        } else {
+            e
+        }
+    })?;
+    if let Some(trash_dir) = files_dir.parent() {
+        let mut info = name.to_os_string();
+        info.push(".trashinfo");
+        let _ = std::fs::remove_file(trash_dir.join("info").join(info));
+    }
+    Ok(())
+}
+
+#[cfg(not(all(unix, not(target_os = "macos"))))]
+pub fn restore(_trashed: &Path, _original: &Path) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "Corbeille non prise en charge sur ce système",
+    ))
+}
+
+/// Périphérique du chemin, ou de son plus proche ancêtre existant.
+#[cfg(all(unix, not(target_os = "macos")))]
+fn device_of_nearest(path: &Path) -> io::Result<u64> {
+    use std::os::unix::fs::MetadataExt;
+
+    for ancestor in path.ancestors() {
+        if let Ok(meta) = std::fs::metadata(ancestor) {
+            return Ok(meta.dev());
+        }
+    }
+    Err(io::Error::new(io::ErrorKind::NotFound, "Aucun ancêtre existant"))
+}
+
+/// Plus haut ancêtre situé sur le même périphérique (point de montage).
+#[cfg(all(unix, not(target_os = "macos")))]
+fn mount_point(path: &Path, device: u64) -> PathBuf {
+    use std::os::unix::fs::MetadataExt;
+
+    let mut topdir = path.parent().unwrap_or(path).to_path_buf();
+    while let Some(parent) = topdir.parent() {
+        match std::fs::metadata(parent) {
+            Ok(meta) if meta.dev() == device => topdir = parent.to_path_buf(),
+            _ => break,
+        }
+    }
+    topdir
+}
+
+/// Corbeille d'un point de montage : `$topdir/.Trash/$uid` si `.Trash` est
+/// un vrai dossier avec le sticky bit, sinon `$topdir/.Trash-$uid`.
+#[cfg(all(unix, not(target_os = "macos")))]
+fn topdir_trash(topdir: &Path) -> io::Result<PathBuf> {
+    use std::os::unix::fs::PermissionsExt;
+
+    // SAFETY: getuid ne peut pas échouer.
+    let uid = unsafe { libc::getuid() };
+
+    let shared = topdir.join(".Trash");
+    if let Ok(meta) = std::fs::symlink_metadata(&shared) {
+        let sticky = meta.permissions().mode() & 0o1000 != 0;
+        if meta.is_dir() && sticky {
+            let dir = shared.join(uid.to_string());
+            if create_private_dir(&dir).is_ok() {
+                return Ok(dir);
+            }
+        }
+    }
+
+    let dir = topdir.join(format!(".Trash-{uid}"));
+    create_private_dir(&dir)?;
+    Ok(dir)
+}
+
+/// Crée un dossier accessible au seul utilisateur (s'il n'existe pas).
+#[cfg(all(unix, not(target_os = "macos")))]
+fn create_private_dir(dir: &Path) -> io::Result<()> {
+    use std::os::unix::fs::DirBuilderExt;
+
+    std::fs::DirBuilder::new()
+        .recursive(true)
+        .mode(0o700)
+        .create(dir)
+}
+
+/// Réserve un nom dans la corbeille en créant le `.trashinfo` (création
+/// exclusive), puis déplace l'élément dans `files/`.
+#[cfg(all(unix, not(target_os = "macos")))]
+fn trash_into(path: &Path, trash_dir: &Path, topdir: Option<&Path>) -> io::Result<PathBuf> {
+    use std::fs::OpenOptions;
+    use std::io::Write;
+
+    let files_dir = trash_dir.join("files");
+    let info_dir = trash_dir.join("info");
+    create_private_dir(&files_dir)?;
+    create_private_dir(&info_dir)?;
+
+    let name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Chemin sans nom"))?;
+
+    // Chemin d'origine : relatif au point de montage pour les corbeilles de $topdir
+    let original = match topdir {
+        Some(topdir) => path.strip_prefix(topdir).unwrap_or(path),
+        None => path,
+    };
+    let info = format!(
+        "[Trash Info]\nPath={}\nDeletionDate={}\n",
+        percent_encode(original),
+        chrono::Local::now().format("%Y-%m-%dT%H:%M:%S")
+    );
+
+    for attempt in 1u32.. {
+        let candidate = trash_name(Path::new(name), attempt);
+        let mut info_name = candidate.clone();
+        info_name.push(".trashinfo");
+        let info_path = info_dir.join(info_name);
+        let target = files_dir.join(&candidate);
+
+        let mut file = match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&info_path)
+        {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        };
+        if target.symlink_metadata().is_ok() {
+            // Reste d'une corbeille incohérente : nom suivant
+            let _ = std::fs::remove_file(&info_path);
+            continue;
+        }
+
+        let moved = file
+            .write_all(info.as_bytes())
+            .and_then(|()| std::fs::rename(path, &target));
+        return match moved {
+            Ok(()) => Ok(target),
+            Err(e) => {
+                let _ = std::fs::remove_file(&info_path);
+                Err(e)
+            }
+        };
+    }
+    unreachable!()
+}
+
+/// Nom dans la corbeille : `nom.ext`, puis `nom.2.ext`, `nom.3.ext`… Les
+/// octets du nom d'origine sont conservés tels quels (même hors UTF-8).
+#[cfg(all(unix, not(target_os = "macos")))]
+fn trash_name(name: &Path, attempt: u32) -> std::ffi::OsString {
+    if attempt == 1 {
+        return name.as_os_str().to_os_string();
+    }
+    match (name.file_stem(), name.extension()) {
+        (Some(stem), Some(ext)) => {
+            let mut candidate = stem.to_os_string();
+            candidate.push(format!(".{attempt}."));
+            candidate.push(ext);
+            candidate
+        }
+        _ => {
+            let mut candidate = name.as_os_str().to_os_string();
+            candidate.push(format!(".{attempt}"));
+            candidate
+        }
+    }
+}
+
+/// Encodage "URL" (RFC 2396) des octets du chemin, `/` conservé.
+#[cfg(all(unix, not(target_os = "macos")))]
+pub fn percent_encode(path: &Path) -> String {
+    use std::os::unix::ffi::OsStrExt;
+
+    let mut encoded = String::new();
+    for &byte in path.as_os_str().as_bytes() {
+        match byte {
+            b'A'..=b'Z'
+            | b'a'..=b'z'
+            | b'0'..=b'9'
+            | b'/'
+            | b'-'
+            | b'_'
+            | b'.'
+            | b'~' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
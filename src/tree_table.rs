@@ -0,0 +1,381 @@
+//! Vue arborescence sous forme de tableau virtualisé : seules les lignes visibles
+//! sont mises en page, avec des colonnes triables et redimensionnables.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use eframe::egui;
+use egui_extras::{Column, TableBuilder};
+
+use crate::rename::{draw_inline_editor, RenameAction};
+use crate::sorting::{SortColumn, SortOptions};
+use crate::{format_bytes, node_context_menu, Node, NodeActions};
+
+const ROW_HEIGHT: f32 = 20.0;
+const INDENT_WIDTH: f32 = 16.0;
+
+/// Etat de la vue tableau : dossiers dépliés, tri courant, défilement.
+#[derive(Default)]
+pub struct TreeTableState {
+    pub expanded: HashSet<PathBuf>,
+    pub sort: SortOptions,
+    /// Tri demandé (en-tête ou panneau "Vue") : appliqué après le dessin,
+    /// l'arbre étant emprunté pendant le rendu.
+    pub pending_sort: bool,
+    scroll_to_selected: bool,
+}
+
+impl TreeTableState {
+    /// Réinitialise les dossiers dépliés pour un nouvel arbre (racine ouverte).
+    pub fn reset(&mut self, root: &Path) {
+        self.expanded.clear();
+        self.expanded.insert(root.to_path_buf());
+        self.scroll_to_selected = false;
+    }
+
+    /// Déplie tous les ancêtres de `path` (sous `root`) et fait défiler jusqu'à lui.
+    pub fn reveal(&mut self, root: &Path, path: &Path) {
+        for ancestor in path.ancestors().skip(1) {
+            if !ancestor.starts_with(root) {
+                break;
+            }
+            self.expanded.insert(ancestor.to_path_buf());
+        }
+        self.scroll_to_selected = true;
+    }
+
+    fn toggle(&mut self, path: &Path) {
+        if !self.expanded.remove(path) {
+            self.expanded.insert(path.to_path_buf());
+        }
+    }
+
+    fn click_header(&mut self, column: SortColumn) {
+        self.sort.select_column(column);
+        self.pending_sort = true;
+    }
+}
+
+/// Ligne visible du tableau (nœud + profondeur + index de la ligne parente).
+struct TreeRow<'a> {
+    node: &'a Node,
+    depth: usize,
+    parent: Option<usize>,
+}
+
+/// Aplatit les nœuds visibles (dossiers dépliés uniquement) dans l'ordre d'affichage.
+fn collect_visible_rows<'a>(
+    node: &'a Node,
+    depth: usize,
+    parent: Option<usize>,
+    expanded: &HashSet<PathBuf>,
+    rows: &mut Vec<TreeRow<'a>>,
+) {
+    let index = rows.len();
+    rows.push(TreeRow {
+        node,
+        depth,
+        parent,
+    });
+
+    if node.is_dir && expanded.contains(&node.path) {
+        for child in &node.children {
+            collect_visible_rows(child, depth + 1, Some(index), expanded, rows);
+        }
+    }
+}
+
+/// Dessin de l'arborescence en tableau virtualisé + clic gauche/droit + clavier.
+pub fn draw_tree_table(
+    ui: &mut egui::Ui,
+    root: &Node,
+    state: &mut TreeTableState,
+    actions: &mut NodeActions,
+) {
+    let root_size = root.size;
+
+    let mut rows: Vec<TreeRow> = Vec::new();
+    collect_visible_rows(root, 0, None, &state.expanded, &mut rows);
+
+    let selected_index = actions
+        .selected_node_path
+        .as_ref()
+        .and_then(|p| rows.iter().position(|r| &r.node.path == p));
+
+    handle_keyboard(ui, &rows, selected_index, state, actions);
+
+    let mut table = TableBuilder::new(ui)
+        .striped(true)
+        .resizable(true)
+        .sense(egui::Sense::click())
+        .auto_shrink([false, false])
+        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+        .column(Column::initial(340.0).at_least(120.0).clip(true))
+        .column(Column::initial(90.0).at_least(60.0))
+        .column(Column::initial(130.0).at_least(60.0))
+        .column(Column::initial(80.0).at_least(50.0))
+        .column(Column::initial(90.0).at_least(60.0))
+        .column(Column::remainder().at_least(110.0));
+
+    if state.scroll_to_selected {
+        state.scroll_to_selected = false;
+        if let Some(path) = &actions.selected_node_path {
+            if let Some(index) = rows.iter().position(|r| &r.node.path == path) {
+                table = table.scroll_to_row(index, None);
+            }
+        }
+    }
+
+    let mut toggled: Option<PathBuf> = None;
+    let mut rename_cancelled = false;
+
+    table
+        .header(ROW_HEIGHT + 4.0, |mut header| {
+            header_cell(&mut header, state, "Nom", Some(SortColumn::Name));
+            header_cell(&mut header, state, "Taille", Some(SortColumn::Size));
+            header_cell(&mut header, state, "%", None);
+            header_cell(&mut header, state, "Fichiers", Some(SortColumn::Files));
+            header_cell(&mut header, state, "Alloué", Some(SortColumn::Allocated));
+            header_cell(&mut header, state, "Modifié le", Some(SortColumn::Modified));
+        })
+        .body(|body| {
+            body.rows(ROW_HEIGHT, rows.len(), |mut row| {
+                let TreeRow { node, depth, .. } = rows[row.index()];
+                let is_selected = actions.is_selected(&node.path);
+                let is_expanded = node.is_dir && state.expanded.contains(&node.path);
+                row.set_selected(is_selected);
+
+                row.col(|ui| {
+                    ui.add_space(INDENT_WIDTH * depth as f32);
+                    if node.is_dir && !node.children.is_empty() {
+                        let (_, resp) = ui.allocate_exact_size(
+                            egui::vec2(INDENT_WIDTH, INDENT_WIDTH),
+                            egui::Sense::click(),
+                        );
+                        let openness = if is_expanded { 1.0 } else { 0.0 };
+                        egui::collapsing_header::paint_default_icon(ui, openness, &resp);
+                        if resp.clicked() {
+                            toggled = Some(node.path.clone());
+                        }
+                    } else {
+                        ui.add_space(INDENT_WIDTH);
+                    }
+                    // Renommage en cours sur cette ligne : champ de saisie à la place du nom
+                    if let Some(edit) = actions
+                        .renaming
+                        .as_mut()
+                        .filter(|e| e.inline && e.path == node.path)
+                    {
+                        if node.is_dir {
+                            ui.label("📁");
+                        }
+                        if let RenameAction::Cancel = draw_inline_editor(ui, edit) {
+                            rename_cancelled = true;
+                        }
+                        return;
+                    }
+                    let text = if node.is_dir {
+                        egui::RichText::new(format!("📁 {}", node.name))
+                    } else {
+                        egui::RichText::new(&node.name)
+                    };
+                    ui.add(
+                        egui::Label::new(if is_selected { text.strong() } else { text })
+                            .selectable(false)
+                            .truncate(true),
+                    );
+                });
+
+                row.col(|ui| {
+                    right_aligned(ui, format_bytes(node.size));
+                });
+
+                row.col(|ui| {
+                    let fraction = if root_size > 0 {
+                        node.size as f32 / root_size as f32
+                    } else {
+                        0.0
+                    };
+                    draw_percentage_bar(ui, fraction);
+                });
+
+                row.col(|ui| {
+                    if node.is_dir {
+                        right_aligned(ui, node.file_count.to_string());
+                    }
+                });
+
+                row.col(|ui| {
+                    right_aligned(ui, format_bytes(node.allocated));
+                });
+
+                row.col(|ui| {
+                    ui.label(format_modified(node.modified));
+                });
+
+                let resp = row.response();
+                if resp.clicked() {
+                    let modifiers = resp.ctx.input(|i| i.modifiers);
+                    actions.click_select(&node.path, modifiers, || {
+                        visible_range(&rows, selected_index, row.index())
+                    });
+                }
+                if resp.double_clicked() && node.is_dir {
+                    toggled = Some(node.path.clone());
+                }
+                resp.context_menu(|ui| {
+                    node_context_menu(ui, &node.path, node.is_dir, actions);
+                });
+            });
+        });
+
+    if let Some(path) = toggled {
+        state.toggle(&path);
+    }
+    if rename_cancelled {
+        actions.renaming = None;
+    }
+}
+
+fn header_cell(
+    header: &mut egui_extras::TableRow<'_, '_>,
+    state: &mut TreeTableState,
+    title: &str,
+    column: Option<SortColumn>,
+) {
+    header.col(|ui| {
+        let Some(column) = column else {
+            ui.strong(title);
+            return;
+        };
+
+        let text = if state.sort.column == column {
+            let arrow = if state.sort.descending { "⬇" } else { "⬆" };
+            format!("{title} {arrow}")
+        } else {
+            title.to_string()
+        };
+
+        if ui
+            .add(
+                egui::Label::new(egui::RichText::new(text).strong())
+                    .selectable(false)
+                    .sense(egui::Sense::click()),
+            )
+            .on_hover_text("Cliquer pour trier")
+            .clicked()
+        {
+            state.click_header(column);
+        }
+    });
+}
+
+pub fn right_aligned(ui: &mut egui::Ui, text: String) -> egui::Response {
+    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+        ui.label(text)
+    })
+    .inner
+}
+
+/// Petite barre de proportion par rapport à la racine + pourcentage.
+fn draw_percentage_bar(ui: &mut egui::Ui, fraction: f32) {
+    let fraction = fraction.clamp(0.0, 1.0);
+    let width = (ui.available_width() - 50.0).max(20.0);
+    let (rect, _) =
+        ui.allocate_exact_size(egui::vec2(width, ROW_HEIGHT * 0.55), egui::Sense::hover());
+
+    let painter = ui.painter();
+    painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+    let mut filled = rect;
+    filled.set_width(rect.width() * fraction);
+    painter.rect_filled(filled, 2.0, ui.visuals().selection.bg_fill);
+
+    ui.label(format!("{:.1}%", fraction * 100.0));
+}
+
+/// Date de modification au format local (jj/mm/aaaa hh:mm).
+pub fn format_modified(modified: Option<SystemTime>) -> String {
+    match modified {
+        Some(t) => chrono::DateTime::<chrono::Local>::from(t)
+            .format("%d/%m/%Y %H:%M")
+            .to_string(),
+        None => "—".to_string(),
+    }
+}
+
+/// Lignes visibles entre l'élément courant et la ligne cliquée (incluses).
+fn visible_range(rows: &[TreeRow], anchor: Option<usize>, clicked: usize) -> Vec<PathBuf> {
+    let Some(anchor) = anchor else {
+        return Vec::new();
+    };
+    rows[anchor.min(clicked)..=anchor.max(clicked)]
+        .iter()
+        .map(|r| r.node.path.clone())
+        .collect()
+}
+
+/// Navigation clavier : ↑/↓ déplacent la sélection, → déplie, ← replie
+/// (ou remonte au parent), Entrée bascule le dossier sélectionné, F2 renomme.
+fn handle_keyboard(
+    ui: &egui::Ui,
+    rows: &[TreeRow],
+    selected_index: Option<usize>,
+    state: &mut TreeTableState,
+    actions: &mut NodeActions,
+) {
+    if rows.is_empty() || ui.ctx().wants_keyboard_input() {
+        return;
+    }
+
+    let (up, down, left, right, enter, rename) = ui.input(|i| {
+        (
+            i.key_pressed(egui::Key::ArrowUp),
+            i.key_pressed(egui::Key::ArrowDown),
+            i.key_pressed(egui::Key::ArrowLeft),
+            i.key_pressed(egui::Key::ArrowRight),
+            i.key_pressed(egui::Key::Enter),
+            i.key_pressed(egui::Key::F2),
+        )
+    });
+
+    let Some(index) = selected_index else {
+        if up || down {
+            actions.selected_node_path = Some(rows[0].node.path.clone());
+            state.scroll_to_selected = true;
+        }
+        return;
+    };
+
+    let row = &rows[index];
+    let node = row.node;
+    let is_expanded = node.is_dir && state.expanded.contains(&node.path);
+    let mut new_index = None;
+
+    if down && index + 1 < rows.len() {
+        new_index = Some(index + 1);
+    } else if up && index > 0 {
+        new_index = Some(index - 1);
+    } else if right && node.is_dir {
+        if !is_expanded {
+            state.expanded.insert(node.path.clone());
+        } else if !node.children.is_empty() {
+            new_index = Some(index + 1);
+        }
+    } else if left {
+        if is_expanded {
+            state.expanded.remove(&node.path);
+        } else {
+            new_index = row.parent;
+        }
+    } else if enter && node.is_dir {
+        state.toggle(&node.path);
+    } else if rename {
+        actions.pending_rename = Some(node.path.clone());
+    }
+
+    if let Some(i) = new_index {
+        actions.selected_node_path = Some(rows[i].node.path.clone());
+        state.scroll_to_selected = true;
+    }
+}